@@ -0,0 +1,45 @@
+// Run with `cargo bench --no-default-features`. Every iteration starts from the same
+// `Simulation::benchmark` state, so runs on one machine compare before and after a change.
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use dg4::sim::{SimParams, SimParamsBuilder, Simulation, BENCHMARK_EDGE_LENGTH};
+use std::hint::black_box;
+
+const VERTEX_COUNTS: [usize; 3] = [100, 1_000, 10_000];
+
+fn bench_steps(c: &mut Criterion, name: &str, params: SimParams) {
+    let mut group = c.benchmark_group(name);
+    // 10k-vertex repulsion steps take a good fraction of a second each.
+    group.sample_size(10);
+    for vertices in VERTEX_COUNTS {
+        let start = Simulation::benchmark(vertices);
+        group.bench_with_input(BenchmarkId::from_parameter(vertices), &start, |b, start| {
+            b.iter_batched_ref(
+                || start.clone(),
+                |sim| black_box(sim.step(black_box(params))),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+// Repulsion on, growth and splitting off, at the spacing the polygon was built with.
+fn step(c: &mut Criterion) {
+    let params = SimParamsBuilder::new().target_edge_length(BENCHMARK_EDGE_LENGTH).build();
+    bench_steps(c, "step_repulsion", params);
+}
+
+// Every other pass off and edges twice the split length, so each one is subdivided.
+fn split(c: &mut Criterion) {
+    let params = SimParamsBuilder::new()
+        .edge_regularization_enabled(false)
+        .repulsion_enabled(false)
+        .jitter_enabled(false)
+        .split_enabled(true)
+        .split_length(BENCHMARK_EDGE_LENGTH / 2.0)
+        .build();
+    bench_steps(c, "split_pass", params);
+}
+
+criterion_group!(benches, step, split);
+criterion_main!(benches);
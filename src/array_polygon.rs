@@ -0,0 +1,100 @@
+use crate::geometry::consts::TAU;
+use crate::geometry::{edges, signed_area, Polygon, Real, Vec2};
+use crate::sim::{accumulate_fixed_forces, SimParams};
+
+// Closed polygon of at most `N` vertices stored inline, for targets without an
+// allocator. Nothing here touches the heap except `to_polygon`; the crate itself still
+// needs `std` for the RNG, images and threads used elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrayPolygon<const N: usize> {
+    vertices: [Vec2; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for ArrayPolygon<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ArrayPolygon<N> {
+    pub const fn new() -> Self {
+        Self {
+            vertices: [Vec2::ZERO; N],
+            len: 0,
+        }
+    }
+
+    // Same vertices as `Polygon::regular_ngon`, or `None` if `sides` exceeds `N`.
+    pub fn regular_ngon(radius: Real, sides: usize) -> Option<Self> {
+        if sides > N {
+            return None;
+        }
+        let mut polygon = Self::new();
+        if sides < 3 || radius <= 0.0 {
+            return Some(polygon);
+        }
+        for i in 0..sides {
+            let t = TAU * (i as Real) / (sides as Real);
+            polygon.vertices[i] = Vec2::new(radius * t.cos(), radius * t.sin());
+        }
+        polygon.len = sides;
+        Some(polygon)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices[..self.len]
+    }
+
+    pub fn vertices_mut(&mut self) -> &mut [Vec2] {
+        &mut self.vertices[..self.len]
+    }
+
+    // Append a vertex, handing it back when all `N` slots are taken.
+    pub fn push(&mut self, vertex: Vec2) -> Result<(), Vec2> {
+        if self.len == N {
+            return Err(vertex);
+        }
+        self.vertices[self.len] = vertex;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn perimeter(&self) -> Real {
+        edges(self.vertices(), true).map(|(a, b)| a.distance(b)).sum()
+    }
+
+    pub fn signed_area(&self) -> Real {
+        signed_area(self.vertices())
+    }
+
+    // Advance one step using the forces `accumulate_fixed_forces` supports, with a stack
+    // scratch buffer. There is no splitting, so the vertex count never changes.
+    pub fn step(&mut self, params: &SimParams) {
+        let mut delta = [Vec2::ZERO; N];
+        let delta = &mut delta[..self.len];
+        accumulate_fixed_forces(self.vertices(), params, delta);
+        for (v, d) in self.vertices_mut().iter_mut().zip(delta.iter()) {
+            *v += *d;
+        }
+    }
+
+    // Heap copy, e.g. for rendering or comparing against a `Simulation`.
+    pub fn to_polygon(&self) -> Polygon {
+        let mut polygon = Polygon::new();
+        polygon.replace_vertices(self.vertices().to_vec());
+        polygon
+    }
+}
@@ -0,0 +1,53 @@
+use crate::geometry::Real;
+
+// Maps a normalized scalar in [0, 1] to a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    // Blue through orange, as used for edge-length coloring in the viewport.
+    BlueOrange,
+    // Perceptually uniform dark purple through yellow.
+    Viridis,
+    Grayscale,
+}
+
+impl Colormap {
+    pub const ALL: [Self; 3] = [Self::BlueOrange, Self::Viridis, Self::Grayscale];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BlueOrange => "Blue-Orange",
+            Self::Viridis => "Viridis",
+            Self::Grayscale => "Grayscale",
+        }
+    }
+
+    // RGB for `t`, clamped to [0, 1].
+    pub fn sample(self, t: Real) -> [u8; 3] {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            Self::BlueOrange => lerp_rgb([60, 120, 230], [240, 150, 60], t),
+            Self::Viridis => {
+                // Piecewise-linear through five samples of the matplotlib map.
+                const STOPS: [[u8; 3]; 5] = [
+                    [68, 1, 84],
+                    [59, 82, 139],
+                    [33, 145, 140],
+                    [94, 201, 98],
+                    [253, 231, 37],
+                ];
+                let x = t * (STOPS.len() - 1) as Real;
+                let k = (x.floor() as usize).min(STOPS.len() - 2);
+                lerp_rgb(STOPS[k], STOPS[k + 1], x - k as Real)
+            }
+            Self::Grayscale => {
+                let v = (t * 255.0).round() as u8;
+                [v, v, v]
+            }
+        }
+    }
+}
+
+fn lerp_rgb(a: [u8; 3], b: [u8; 3], t: Real) -> [u8; 3] {
+    let lerp = |a: u8, b: u8| (a as Real + (b as Real - a as Real) * t).round() as u8;
+    [lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])]
+}
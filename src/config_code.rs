@@ -0,0 +1,393 @@
+use crate::error::GrowthError;
+use crate::geometry::{CentroidKind, Real, Vec2};
+use crate::param_info::{read_params, write_params};
+use crate::render::{Appearance, VertexMarker};
+use crate::sim::{
+    Attractor, ConstraintFalloff, ConstraintShape, ForceKind, GrowthCurvatureFilter, SimParams,
+};
+use crate::starter::{Starter, StarterShape};
+
+// Leading byte of every code. Bump it whenever the binary layout changes, including
+// reordering or adding `SimParams` fields, so old codes are rejected instead of misread.
+pub const CONFIG_CODE_VERSION: u8 = 15;
+
+// Everything needed to reproduce a run: parameters, seed and starter shape, plus how the
+// viewport draws it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SharedConfig {
+    pub params: SimParams,
+    pub seed: u64,
+    pub starter: Starter,
+    pub appearance: Appearance,
+}
+
+// Compact base64url (unpadded) string for pasting into chat. Reals are stored as f64, so
+// codes decode identically in both precision builds of the same version.
+pub fn encode_config(config: &SharedConfig) -> String {
+    let mut bytes = vec![CONFIG_CODE_VERSION];
+    config.seed.write(&mut bytes);
+    config.starter.write(&mut bytes);
+    config.appearance.write(&mut bytes);
+    write_params(&config.params, &mut bytes);
+    base64url_encode(&bytes)
+}
+
+pub fn decode_config(code: &str) -> Result<SharedConfig, GrowthError> {
+    let bytes = base64url_decode(code.trim())?;
+    let mut input = bytes.as_slice();
+    let version = u8::read(&mut input)?;
+    if version != CONFIG_CODE_VERSION {
+        return Err(GrowthError::Parse(format!(
+            "config code version {version} is not supported (expected {CONFIG_CODE_VERSION})"
+        )));
+    }
+    let config = SharedConfig {
+        seed: u64::read(&mut input)?,
+        starter: Starter::read(&mut input)?,
+        appearance: Appearance::read(&mut input)?,
+        params: read_params(&mut input)?,
+    };
+    if !input.is_empty() {
+        return Err(GrowthError::Parse(format!("{} unexpected trailing bytes", input.len())));
+    }
+    Ok(config)
+}
+
+// Marks a params code, which follows the version byte, so the two kinds of code are not
+// mistaken for each other.
+const PARAMS_CODE_TAG: u8 = b'P';
+
+// Parameters and seed only, for applying a saved configuration to any starter shape.
+// Shares the config code's version and encoding.
+pub fn encode_params(params: &SimParams, seed: u64) -> String {
+    let mut bytes = vec![CONFIG_CODE_VERSION, PARAMS_CODE_TAG];
+    seed.write(&mut bytes);
+    write_params(params, &mut bytes);
+    base64url_encode(&bytes)
+}
+
+// Inverse of `encode_params`: `(params, seed)`.
+pub fn decode_params(code: &str) -> Result<(SimParams, u64), GrowthError> {
+    let bytes = base64url_decode(code.trim())?;
+    let mut input = bytes.as_slice();
+    let version = u8::read(&mut input)?;
+    if version != CONFIG_CODE_VERSION {
+        return Err(GrowthError::Parse(format!(
+            "params code version {version} is not supported (expected {CONFIG_CODE_VERSION})"
+        )));
+    }
+    if u8::read(&mut input)? != PARAMS_CODE_TAG {
+        return Err(GrowthError::Parse("not a params code".to_owned()));
+    }
+    let seed = u64::read(&mut input)?;
+    let params = read_params(&mut input)?;
+    if !input.is_empty() {
+        return Err(GrowthError::Parse(format!("{} unexpected trailing bytes", input.len())));
+    }
+    Ok((params, seed))
+}
+
+// Fixed little-endian binary form of one value in a config code.
+pub(crate) trait CodeValue: Sized {
+    fn write(&self, out: &mut Vec<u8>);
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError>;
+}
+
+fn take<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], GrowthError> {
+    if input.len() < N {
+        return Err(GrowthError::Parse("config code is truncated".to_owned()));
+    }
+    let (head, rest) = input.split_at(N);
+    *input = rest;
+    Ok(head.try_into().expect("split_at returned N bytes"))
+}
+
+impl CodeValue for u8 {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(take::<1>(input)?[0])
+    }
+}
+
+impl CodeValue for u64 {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(u64::from_le_bytes(take(input)?))
+    }
+}
+
+impl CodeValue for usize {
+    fn write(&self, out: &mut Vec<u8>) {
+        (*self as u64).write(out);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        let value = u64::read(input)?;
+        usize::try_from(value)
+            .map_err(|_| GrowthError::Parse(format!("count {value} is out of range")))
+    }
+}
+
+impl CodeValue for bool {
+    fn write(&self, out: &mut Vec<u8>) {
+        u8::from(*self).write(out);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        match u8::read(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(GrowthError::Parse(format!("invalid flag byte {other}"))),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "f32"), allow(clippy::unnecessary_cast))]
+impl CodeValue for Real {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(*self as f64).to_le_bytes());
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(f64::from_le_bytes(take(input)?) as Real)
+    }
+}
+
+impl CodeValue for Vec2 {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.x.write(out);
+        self.y.write(out);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(Vec2::new(Real::read(input)?, Real::read(input)?))
+    }
+}
+
+impl<T: CodeValue> CodeValue for Option<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.is_some().write(out);
+        if let Some(value) = self {
+            value.write(out);
+        }
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(if bool::read(input)? { Some(T::read(input)?) } else { None })
+    }
+}
+
+impl<T: CodeValue, const N: usize> CodeValue for [T; N] {
+    fn write(&self, out: &mut Vec<u8>) {
+        for value in self {
+            value.write(out);
+        }
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        let values = (0..N).map(|_| T::read(input)).collect::<Result<Vec<_>, _>>()?;
+        Ok(values.try_into().unwrap_or_else(|_| unreachable!("read exactly N values")))
+    }
+}
+
+impl CodeValue for Attractor {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.position.write(out);
+        self.radius.write(out);
+        self.strength.write(out);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(Attractor {
+            position: Vec2::read(input)?,
+            radius: Real::read(input)?,
+            strength: Real::read(input)?,
+        })
+    }
+}
+
+impl CodeValue for ConstraintShape {
+    fn write(&self, out: &mut Vec<u8>) {
+        match *self {
+            Self::Circle => 0u8.write(out),
+            Self::Square => 1u8.write(out),
+            Self::Triangle => 2u8.write(out),
+            Self::Rectangle {
+                half_width,
+                half_height,
+            } => {
+                3u8.write(out);
+                half_width.write(out);
+                half_height.write(out);
+            }
+        }
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        match u8::read(input)? {
+            0 => Ok(Self::Circle),
+            1 => Ok(Self::Square),
+            2 => Ok(Self::Triangle),
+            3 => Ok(Self::Rectangle {
+                half_width: Real::read(input)?,
+                half_height: Real::read(input)?,
+            }),
+            other => Err(GrowthError::Parse(format!("invalid constraint shape tag {other}"))),
+        }
+    }
+}
+
+// Unit-variant enums encode as their index in a fixed table.
+macro_rules! tagged_enum {
+    ($ty:ty, [$($variant:path),* $(,)?]) => {
+        impl CodeValue for $ty {
+            fn write(&self, out: &mut Vec<u8>) {
+                let tag = [$($variant),*]
+                    .iter()
+                    .position(|v| v == self)
+                    .expect("every variant is listed");
+                (tag as u8).write(out);
+            }
+
+            fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+                let tag = u8::read(input)?;
+                [$($variant),*].get(tag as usize).copied().ok_or_else(|| {
+                    GrowthError::Parse(format!("invalid {} tag {tag}", stringify!($ty)))
+                })
+            }
+        }
+    };
+}
+
+tagged_enum!(ConstraintFalloff, [ConstraintFalloff::Linear, ConstraintFalloff::Quadratic]);
+tagged_enum!(
+    GrowthCurvatureFilter,
+    [
+        GrowthCurvatureFilter::All,
+        GrowthCurvatureFilter::ConvexOnly,
+        GrowthCurvatureFilter::ConcaveOnly,
+    ]
+);
+tagged_enum!(CentroidKind, [CentroidKind::VertexMean, CentroidKind::Area]);
+tagged_enum!(
+    ForceKind,
+    [
+        ForceKind::Spring,
+        ForceKind::Repulsion,
+        ForceKind::EdgeRepulsion,
+        ForceKind::Growth,
+        ForceKind::Constraint,
+        ForceKind::Attractor,
+        ForceKind::Centering,
+        ForceKind::Jitter,
+    ]
+);
+tagged_enum!(
+    StarterShape,
+    [
+        StarterShape::Ngon,
+        StarterShape::Star,
+        StarterShape::RoundedRect,
+        StarterShape::SymmetricNgon,
+    ]
+);
+
+impl CodeValue for Starter {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.shape.write(out);
+        self.radius.write(out);
+        self.sides.write(out);
+        self.star_inner_ratio.write(out);
+        self.rect_aspect.write(out);
+        self.corner_ratio.write(out);
+        self.center.write(out);
+        self.perturb_magnitude.write(out);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(Starter {
+            shape: StarterShape::read(input)?,
+            radius: Real::read(input)?,
+            sides: usize::read(input)?,
+            star_inner_ratio: Real::read(input)?,
+            rect_aspect: Real::read(input)?,
+            corner_ratio: Real::read(input)?,
+            center: Vec2::read(input)?,
+            perturb_magnitude: Real::read(input)?,
+        })
+    }
+}
+
+tagged_enum!(
+    VertexMarker,
+    [VertexMarker::FilledCircle, VertexMarker::HollowCircle, VertexMarker::Square]
+);
+
+// Pixel sizes are f32 in both builds and travel as `Real`, like every other float.
+#[cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+impl CodeValue for Appearance {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.background.write(out);
+        self.curve.write(out);
+        self.vertex.write(out);
+        self.constraint_fill.write(out);
+        self.constraint_stroke.write(out);
+        self.vertex_marker.write(out);
+        Real::from(self.vertex_marker_size).write(out);
+        Real::from(self.line_width).write(out);
+    }
+
+    fn read(input: &mut &[u8]) -> Result<Self, GrowthError> {
+        Ok(Appearance {
+            background: CodeValue::read(input)?,
+            curve: CodeValue::read(input)?,
+            vertex: CodeValue::read(input)?,
+            constraint_fill: CodeValue::read(input)?,
+            constraint_stroke: CodeValue::read(input)?,
+            vertex_marker: VertexMarker::read(input)?,
+            vertex_marker_size: Real::read(input)? as f32,
+            line_width: Real::read(input)? as f32,
+        })
+    }
+}
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let group = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        // One input byte yields two characters, two yield three, three yield four.
+        for k in 0..=chunk.len() {
+            text.push(BASE64URL[(group >> (18 - 6 * k) & 63) as usize] as char);
+        }
+    }
+    text
+}
+
+fn base64url_decode(text: &str) -> Result<Vec<u8>, GrowthError> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut group = 0u32;
+    let mut bits = 0;
+    for c in text.chars() {
+        let value = BASE64URL
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or_else(|| GrowthError::Parse(format!("invalid character '{c}' in config code")))?;
+        group = group << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+            group &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
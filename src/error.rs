@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+// Error returned by the library's fallible operations.
+#[derive(Debug)]
+pub enum GrowthError {
+    // Malformed input such as SVG path data or a config code.
+    Parse(String),
+    // Well-formed arguments that make no sense, e.g. a non-positive mass.
+    InvalidParameter(String),
+    Io(io::Error),
+    // Encoding or writing an image failed.
+    Image(image::ImageError),
+}
+
+impl fmt::Display for GrowthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(message) | Self::InvalidParameter(message) => f.write_str(message),
+            Self::Io(err) => err.fmt(f),
+            Self::Image(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for GrowthError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(_) | Self::InvalidParameter(_) => None,
+            Self::Io(err) => Some(err),
+            Self::Image(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for GrowthError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<image::ImageError> for GrowthError {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::geometry::Vec2;
+use crate::sim::{ForceBuffers, ForceKind, OtherPolygons, SimParams};
+use rand::rngs::StdRng;
+use std::fmt::Debug;
+
+// Per-step context handed to every force alongside the positions it acts on.
+#[derive(Debug, Clone, Copy)]
+pub struct PolygonMeta<'a> {
+    // Parameters after the simulation's adjustments, e.g. the constraint ramp-in.
+    pub params: &'a SimParams,
+    // Generation the step starts from.
+    pub generation: u64,
+    // Remaining refractory steps per vertex; empty when none are tracked.
+    pub refractory: &'a [u64],
+    // Growth follows the detected outward normals, see `Simulation::set_growth_outward`.
+    pub growth_outward: bool,
+}
+
+// Mutable state shared by the passes of one force evaluation: the simulation's RNG, and
+// the scratch memory and step-wide inputs the built-in passes use. Only the simulation
+// builds one.
+#[derive(Debug)]
+pub struct ForceContext<'a> {
+    pub rng: &'a mut StdRng,
+    pub(crate) buffers: &'a mut ForceBuffers,
+    // Repulsion may run on the GPU.
+    pub(crate) gpu: bool,
+    // The other polygons' vertices, when there are any to repel.
+    pub(crate) others: Option<&'a OtherPolygons<'a>>,
+}
+
+// One force pass: adds the displacement it asks for at each vertex into `delta`, which
+// has one entry per position and already holds the passes run before it. Register
+// custom passes with `Simulation::add_force`; `ForceKind` implements it for the
+// built-in ones.
+pub trait Force: Debug + Send {
+    fn accumulate(
+        &self,
+        positions: &[Vec2],
+        meta: &PolygonMeta,
+        ctx: &mut ForceContext,
+        delta: &mut [Vec2],
+    );
+
+    // The built-in pass this is, so step profiles can time it; `None` for custom passes.
+    fn kind(&self) -> Option<ForceKind> {
+        None
+    }
+
+    // Short display name.
+    fn name(&self) -> &'static str;
+
+    // `Simulation` is `Clone`, so boxed forces must be too.
+    fn box_clone(&self) -> Box<dyn Force>;
+}
+
+impl Clone for Box<dyn Force> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
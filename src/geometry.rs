@@ -1,13 +1,48 @@
-use glam::DVec2;
-use std::f64::consts::TAU;
+// Scalar and vector types for all geometry and simulation math. The `f32` feature
+// halves vertex memory at the cost of precision.
+#[cfg(not(feature = "f32"))]
+mod precision {
+    pub type Real = f64;
+    pub type Vec2 = glam::DVec2;
+    pub use std::f64::consts;
+}
+
+#[cfg(feature = "f32")]
+mod precision {
+    pub type Real = f32;
+    pub type Vec2 = glam::Vec2;
+    pub use std::f32::consts;
+}
 
-pub type Real = f64;
-pub type Vec2 = DVec2;
+pub use precision::{consts, Real, Vec2};
+use crate::validate::intersecting_edges;
+use consts::TAU;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
 
-// Closed polygon represented as ordered vertices.
-#[derive(Debug, Clone, Default)]
+// Which notion of "center" a computation should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CentroidKind {
+    // Mean of the vertices; biased toward densely sampled regions.
+    VertexMean,
+    // Centroid of the enclosed area; independent of sampling.
+    Area,
+}
+
+// Ordered vertices of a closed loop, or of an open chain once `set_closed(false)`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Polygon {
     vertices: Vec<Vec2>,
+    closed: bool,
+}
+
+impl Default for Polygon {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            closed: true,
+        }
+    }
 }
 
 impl Polygon {
@@ -15,10 +50,26 @@ impl Polygon {
         Self::default()
     }
 
+    // Whether an edge joins the last vertex back to the first. `perimeter`, the edge
+    // statistics and rendering follow it; the loop algorithms (area, simplification,
+    // resampling, ...) always treat the vertices as closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn set_closed(&mut self, closed: bool) {
+        self.closed = closed;
+    }
+
     pub(crate) fn replace_vertices(&mut self, vertices: Vec<Vec2>) {
         self.vertices = vertices;
     }
 
+    // Exchange the vertex buffer with `vertices`, keeping both allocations alive.
+    pub(crate) fn swap_vertices(&mut self, vertices: &mut Vec<Vec2>) {
+        std::mem::swap(&mut self.vertices, vertices);
+    }
+
     pub fn len(&self) -> usize {
         self.vertices.len()
     }
@@ -35,7 +86,39 @@ impl Polygon {
         &mut self.vertices
     }
 
+    // Vertex after `i` around the closed loop, so the last wraps to 0. Panics when empty.
+    pub fn next_index(&self, i: usize) -> usize {
+        next_index(i, self.vertices.len())
+    }
+
+    // Vertex before `i` around the closed loop, so 0 wraps to the last. Panics when empty.
+    pub fn prev_index(&self, i: usize) -> usize {
+        prev_index(i, self.vertices.len())
+    }
+
+    // Replace every vertex with `f(vertex)`, e.g. for shears or polar warps.
+    pub fn map_vertices(&mut self, f: impl Fn(Vec2) -> Vec2) {
+        for v in &mut self.vertices {
+            *v = f(*v);
+        }
+    }
+
+    // Vertex-by-vertex comparison within `epsilon` per vertex. The same loop starting
+    // at a different vertex, or traversed in reverse, is not considered equal.
+    pub fn approx_eq(&self, other: &Polygon, epsilon: Real) -> bool {
+        self.vertices.len() == other.vertices.len()
+            && self
+                .vertices
+                .iter()
+                .zip(&other.vertices)
+                .all(|(a, b)| a.distance(*b) <= epsilon)
+    }
+
     pub fn regular_ngon(radius: Real, sides: usize) -> Self {
+        Self::regular_ngon_at(Vec2::ZERO, radius, sides)
+    }
+
+    pub fn regular_ngon_at(center: Vec2, radius: Real, sides: usize) -> Self {
         if sides < 3 || radius <= 0.0 {
             return Self::new();
         }
@@ -44,25 +127,590 @@ impl Polygon {
         let mut vertices = Vec::with_capacity(sides);
         for i in 0..sides {
             let t = TAU * (i as Real) / (sides as Real);
-            vertices.push(Vec2::new(radius * t.cos(), radius * t.sin()));
+            vertices.push(center + Vec2::new(radius * t.cos(), radius * t.sin()));
+        }
+        Self {
+            vertices,
+            closed: true,
         }
-        Self { vertices }
     }
 
+    // Regular polygon centered on the origin that mirrors across the line through it along
+    // `axis`: vertex 0 sits on the axis in its direction and vertex `sides - i` is the
+    // reflection of vertex `i`, so any number of sides pairs up cleanly. For `Vec2::Y` the
+    // reflection negates x exactly; other axes match to rounding. Counter-clockwise like
+    // `regular_ngon`; empty for a zero or non-finite axis.
+    pub fn mirror_symmetric_ngon(radius: Real, sides: usize, axis: Vec2) -> Self {
+        let Some(up) = axis.try_normalize() else {
+            return Self::new();
+        };
+        if sides < 3 || radius <= 0.0 {
+            return Self::new();
+        }
+
+        // Built about the y-axis, then turned so +y lands on `up` and +x on `across`.
+        let across = Vec2::new(up.y, -up.x);
+        let mut vertices = vec![Vec2::ZERO; sides];
+        for i in 0..=sides / 2 {
+            let t = consts::FRAC_PI_2 + TAU * (i as Real) / (sides as Real);
+            // Vertices on the axis get an exact zero offset instead of a rounded cosine.
+            let on_axis = i == 0 || 2 * i == sides;
+            let x = if on_axis { 0.0 } else { radius * t.cos() };
+            let y = radius * t.sin();
+            vertices[i] = across * x + up * y;
+            vertices[(sides - i) % sides] = across * -x + up * y;
+        }
+        Self {
+            vertices,
+            closed: true,
+        }
+    }
+
+    // Star centered on the origin alternating `outer_radius` and `inner_radius` tips,
+    // `2 * points` vertices in CCW order starting with an outer tip on the +x axis.
+    pub fn star(points: usize, inner_radius: Real, outer_radius: Real) -> Self {
+        if points < 2 || inner_radius <= 0.0 || outer_radius <= 0.0 {
+            return Self::new();
+        }
+
+        let count = 2 * points;
+        let vertices = (0..count)
+            .map(|i| {
+                let t = TAU * (i as Real) / (count as Real);
+                let r = if i % 2 == 0 { outer_radius } else { inner_radius };
+                Vec2::new(r * t.cos(), r * t.sin())
+            })
+            .collect();
+        Self {
+            vertices,
+            closed: true,
+        }
+    }
+
+    // `width` x `height` rectangle centered on the origin with each corner replaced by a
+    // quarter circle of `segments` edges, in CCW order. The corner radius is limited to
+    // half the shorter side; a zero radius yields the plain four-corner rectangle.
+    pub fn rounded_rect(width: Real, height: Real, corner_radius: Real, segments: usize) -> Self {
+        if width <= 0.0 || height <= 0.0 {
+            return Self::new();
+        }
+
+        let (hw, hh) = (width * 0.5, height * 0.5);
+        let r = corner_radius.clamp(0.0, hw.min(hh));
+        let segments = if r > 0.0 { segments.max(1) } else { 0 };
+        let corners = [
+            Vec2::new(hw - r, hh - r),
+            Vec2::new(-hw + r, hh - r),
+            Vec2::new(-hw + r, -hh + r),
+            Vec2::new(hw - r, -hh + r),
+        ];
+        // Half-lengths of the straight side leading into each corner: right, top, left, bottom.
+        let sides = [hh - r, hw - r, hh - r, hw - r];
+        let mut vertices = Vec::with_capacity(4 * (segments + 1));
+        for (k, center) in corners.into_iter().enumerate() {
+            // Without a straight side, this arc starts where the previous one ended.
+            let first = if sides[k] <= 1e-12 { 1 } else { 0 };
+            for s in first..=segments {
+                let t = TAU * 0.25 * (k as Real + s as Real / segments.max(1) as Real);
+                vertices.push(center + Vec2::new(t.cos(), t.sin()) * r);
+            }
+        }
+        Self {
+            vertices,
+            closed: true,
+        }
+    }
+
+    // Length of the outline, counting the closing edge only when `is_closed`.
     pub fn perimeter(&self) -> Real {
+        self.curve_length(self.closed)
+    }
+
+    // Edges in the outline: `len()` when closed, one fewer for an open chain that omits
+    // the edge from the last vertex back to the first.
+    pub fn edge_count(&self, closed: bool) -> usize {
+        match self.vertices.len() {
+            0 | 1 => 0,
+            n if closed => n,
+            n => n - 1,
+        }
+    }
+
+    // `(start, end)` of each edge in order, `edge_count(self.is_closed())` of them, ending
+    // with the closing edge back to vertex 0 only when the outline is closed.
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> {
+        edges(&self.vertices, self.closed)
+    }
+
+    // `edges` including the closing edge even for an open chain, for area and containment
+    // tests that treat every outline as a loop.
+    pub fn closed_edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> {
+        edges(&self.vertices, true)
+    }
+
+    // Sum of edge lengths, counting the closing edge only when `closed`.
+    pub fn curve_length(&self, closed: bool) -> Real {
+        edges(&self.vertices, closed).map(|(a, b)| a.distance(b)).sum()
+    }
+
+    // Perimeter, or `None` when fewer than two vertices leave it undefined.
+    pub fn try_perimeter(&self) -> Option<Real> {
+        (self.vertices.len() >= 2).then(|| self.perimeter())
+    }
+
+    // Shoelace area: positive for counter-clockwise winding, zero below three vertices.
+    pub fn signed_area(&self) -> Real {
+        signed_area(&self.vertices)
+    }
+
+    // +1 or -1 such that an edge's left-hand normal `(-d.y, d.x)` times this sign points
+    // outward: -1 for counter-clockwise loops, +1 for clockwise ones. Degenerate
+    // (zero-area) polygons count as counter-clockwise.
+    pub fn outward_sign(&self) -> Real {
+        outward_sign(&self.vertices)
+    }
+
+    // Enclosed area regardless of winding.
+    pub fn area(&self) -> Real {
+        self.signed_area().abs()
+    }
+
+    // Enclosed area, or `None` when fewer than three vertices leave it undefined.
+    pub fn try_area(&self) -> Option<Real> {
+        (self.vertices.len() >= 3).then(|| self.area())
+    }
+
+    // Isoperimetric ratio `4π·area / perimeter²`: 1 for a circle, toward 0 for crinkly
+    // outlines. `None` below three vertices or for a zero perimeter. Shoelace area, so
+    // lobes of a self-intersecting outline partly cancel.
+    pub fn compactness(&self) -> Option<Real> {
+        let perimeter = self.try_perimeter()?;
+        (self.vertices.len() >= 3 && perimeter > 1e-12)
+            .then(|| 2.0 * TAU * self.area() / (perimeter * perimeter))
+    }
+
+    // Signed discrete curvature at vertex `i`: turning angle over mean adjacent edge
+    // length. Positive for left turns, i.e. convex vertices of a CCW polygon.
+    pub fn curvature(&self, i: usize) -> Real {
+        vertex_curvature(&self.vertices, i)
+    }
+
+    // Shortest distance from `p` to any edge, including the closing edge when closed.
+    // Infinite for an empty polygon.
+    pub fn distance_to_boundary(&self, p: Vec2) -> Real {
         let n = self.vertices.len();
-        if n < 2 {
-            return 0.0;
+        match n {
+            0 => Real::INFINITY,
+            1 => p.distance(self.vertices[0]),
+            _ => self
+                .edges()
+                .map(|(a, b)| p.distance(closest_point_on_segment(p, a, b)))
+                .fold(Real::INFINITY, Real::min),
+        }
+    }
+
+    // Symmetric Hausdorff distance using vertex-to-boundary distances in both
+    // directions. Infinite if either polygon is empty.
+    pub fn hausdorff_distance(&self, other: &Polygon) -> Real {
+        let directed = |from: &Polygon, to: &Polygon| {
+            from.vertices
+                .iter()
+                .map(|v| to.distance_to_boundary(*v))
+                .fold(0.0, Real::max)
+        };
+        if self.is_empty() || other.is_empty() {
+            return Real::INFINITY;
         }
+        directed(self, other).max(directed(other, self))
+    }
 
-        // Sum edge lengths, including the closing edge.
-        let mut total = 0.0;
-        for i in 0..n {
-            let a = self.vertices[i];
-            let b = self.vertices[(i + 1) % n];
-            total += a.distance(b);
+    // Distance between every pair of vertices: row `i`, column `j` holds |v_i - v_j|.
+    // O(n^2) time and memory, so meant for small polygons, e.g. as ground truth when
+    // checking neighbor queries.
+    pub fn distance_matrix(&self) -> Vec<Vec<Real>> {
+        self.vertices
+            .iter()
+            .map(|a| self.vertices.iter().map(|b| a.distance(*b)).collect())
+            .collect()
+    }
+
+    // Per-vertex displacement `(origin, offset)` that carries this polygon onto `other`.
+    // With equal vertex counts vertices correspond by index, as they do between
+    // generations without splits; otherwise each vertex maps to the closest point on
+    // `other`'s boundary.
+    pub fn displacements_to(&self, other: &Polygon) -> Vec<(Vec2, Vec2)> {
+        let n = other.vertices.len();
+        if n == self.vertices.len() {
+            return self
+                .vertices
+                .iter()
+                .zip(&other.vertices)
+                .map(|(a, b)| (*a, *b - *a))
+                .collect();
+        }
+        if n == 0 {
+            return Vec::new();
+        }
+
+        self.vertices
+            .iter()
+            .map(|&p| {
+                let target = other
+                    .edges()
+                    .map(|(a, b)| closest_point_on_segment(p, a, b))
+                    .min_by(|a, b| a.distance_squared(p).total_cmp(&b.distance_squared(p)))
+                    .unwrap_or(other.vertices[0]);
+                (p, target - p)
+            })
+            .collect()
+    }
+
+    // Axis-aligned bounding box over all vertices.
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        let points = &self.vertices;
+        if points.is_empty() {
+            return None;
+        }
+
+        let mut min = points[0];
+        let mut max = points[0];
+
+        for p in points.iter().copied().skip(1) {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+
+        Some((min, max))
+    }
+
+    // Box-counting estimate of the outline's fractal dimension: for each grid cell size in
+    // `scales`, count the cells the edges pass through, then fit the slope of
+    // log(count) against log(1 / scale). Smooth curves give about 1, space-filling ones
+    // approach 2. NaN unless at least two distinct positive scales are given.
+    pub fn box_counting_dimension(&self, scales: &[Real]) -> Real {
+        let mut samples: Vec<(Real, Real)> = Vec::new();
+        for &scale in scales.iter().filter(|s| **s > 0.0) {
+            let mut cells = HashSet::new();
+            for (a, b) in self.edges() {
+                // Sample densely enough that no crossed cell is skipped.
+                let steps = ((a.distance(b) / (scale * 0.25)).ceil() as usize).max(1);
+                for k in 0..=steps {
+                    let p = a.lerp(b, k as Real / steps as Real);
+                    cells.insert(((p.x / scale).floor() as i64, (p.y / scale).floor() as i64));
+                }
+            }
+            if !cells.is_empty() {
+                samples.push(((1.0 / scale).ln(), (cells.len() as Real).ln()));
+            }
+        }
+
+        // Least-squares slope.
+        let count = samples.len() as Real;
+        let mean_x = samples.iter().map(|s| s.0).sum::<Real>() / count;
+        let mean_y = samples.iter().map(|s| s.1).sum::<Real>() / count;
+        let sxx: Real = samples.iter().map(|s| (s.0 - mean_x).powi(2)).sum();
+        let sxy: Real = samples.iter().map(|s| (s.0 - mean_x) * (s.1 - mean_y)).sum();
+        if samples.len() < 2 || sxx <= 1e-12 {
+            return Real::NAN;
+        }
+        sxy / sxx
+    }
+
+    // Reduce to `target_count` vertices (never fewer than three) by repeatedly removing
+    // the vertex whose triangle with its current neighbors has the smallest area, i.e.
+    // the one whose removal changes the outline least (Visvalingam-Whyatt). Remaining
+    // vertices keep their order and positions.
+    pub fn decimate_to(&self, target_count: usize) -> Polygon {
+        let n = self.vertices.len();
+        let target = target_count.max(3);
+        if n <= target {
+            return self.clone();
+        }
+
+        let v = &self.vertices;
+        let mut prev: Vec<usize> = (0..n).map(|i| prev_index(i, n)).collect();
+        let mut next: Vec<usize> = (0..n).map(|i| next_index(i, n)).collect();
+        let mut removed = vec![false; n];
+        // Bumped whenever a vertex's neighbors change, invalidating its queued cost.
+        let mut version = vec![0u32; n];
+        let cost = |i: usize, prev: &[usize], next: &[usize]| {
+            let (a, b, c) = (v[prev[i]], v[i], v[next[i]]);
+            0.5 * (b - a).perp_dot(c - a).abs()
+        };
+
+        let mut heap: BinaryHeap<Reverse<RemovalCandidate>> = (0..n)
+            .map(|i| {
+                Reverse(RemovalCandidate {
+                    cost: cost(i, &prev, &next),
+                    index: i,
+                    version: 0,
+                })
+            })
+            .collect();
+        let mut remaining = n;
+        while remaining > target
+            && let Some(Reverse(candidate)) = heap.pop()
+        {
+            let i = candidate.index;
+            if removed[i] || version[i] != candidate.version {
+                continue;
+            }
+            removed[i] = true;
+            remaining -= 1;
+            let (p, q) = (prev[i], next[i]);
+            next[p] = q;
+            prev[q] = p;
+            for j in [p, q] {
+                version[j] += 1;
+                heap.push(Reverse(RemovalCandidate {
+                    cost: cost(j, &prev, &next),
+                    index: j,
+                    version: version[j],
+                }));
+            }
+        }
+
+        let vertices = (0..n).filter(|&i| !removed[i]).map(|i| v[i]).collect();
+        Polygon {
+            vertices,
+            closed: true,
+        }
+    }
+
+    // Convex hull in counter-clockwise order (Andrew's monotone chain), dropping
+    // collinear points. Polygons with fewer than three vertices come back unchanged.
+    pub fn convex_hull(&self) -> Polygon {
+        if self.vertices.len() < 3 {
+            return self.clone();
+        }
+        let mut points = self.vertices.clone();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+
+        let turn = |o: Vec2, a: Vec2, b: Vec2| (a - o).perp_dot(b - o);
+        let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+        // Lower chain left to right, then the upper chain back.
+        for &p in &points {
+            while hull.len() >= 2 && turn(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        let lower_len = hull.len() + 1;
+        for &p in points.iter().rev().skip(1) {
+            while hull.len() >= lower_len
+                && turn(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop();
+        Polygon {
+            vertices: hull,
+            closed: true,
+        }
+    }
+
+    // Douglas-Peucker on the closed outline: keep only the vertices that deviate more than
+    // `tolerance` from the simplified edges around them. The loop is split at vertex 0
+    // and the vertex farthest from it, so at least those two stay; kept vertices keep
+    // their order and positions.
+    pub fn simplify(&self, tolerance: Real) -> Polygon {
+        let n = self.vertices.len();
+        if n <= 3 || tolerance <= 0.0 {
+            return self.clone();
+        }
+
+        let v = &self.vertices;
+        let far = (1..n)
+            .max_by(|&a, &b| v[0].distance_squared(v[a]).total_cmp(&v[0].distance_squared(v[b])))
+            .expect("polygon has more than three vertices");
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        keep[far] = true;
+        // Spans `(start, end)` of the loop, walking forward from `start`, still to check.
+        let mut spans = vec![(0, far), (far, n)];
+        while let Some((start, end)) = spans.pop() {
+            let (a, b) = (v[start], v[end % n]);
+            let farthest = (start + 1..end)
+                .map(|i| (i, v[i].distance(closest_point_on_segment(v[i], a, b))))
+                .max_by(|x, y| x.1.total_cmp(&y.1));
+            if let Some((i, deviation)) = farthest
+                && deviation > tolerance
+            {
+                keep[i] = true;
+                spans.push((start, i));
+                spans.push((i, end));
+            }
+        }
+        let vertices = (0..n).filter(|&i| keep[i]).map(|i| v[i]).collect();
+        Polygon {
+            vertices,
+            closed: true,
+        }
+    }
+
+    // Ear-clipping triangulation of the interior, for filled rendering. Triangles are
+    // counter-clockwise whatever the winding. Self-intersecting outlines and those with
+    // fewer than three vertices have no well-defined interior and yield none.
+    pub fn triangulate(&self) -> Vec<[Vec2; 3]> {
+        let v = &self.vertices;
+        let n = v.len();
+        if n < 3 || !intersecting_edges(v).is_empty() {
+            return Vec::new();
+        }
+        let mut remaining: Vec<usize> = (0..n).collect();
+        if signed_area(v) < 0.0 {
+            remaining.reverse();
+        }
+        let turn = |a: Vec2, b: Vec2, c: Vec2| (b - a).perp_dot(c - a);
+        let inside = |p: Vec2, a: Vec2, b: Vec2, c: Vec2| {
+            turn(a, b, p) >= 0.0 && turn(b, c, p) >= 0.0 && turn(c, a, p) >= 0.0
+        };
+
+        let mut triangles = Vec::with_capacity(n - 2);
+        let mut i = 0;
+        // Corners tried since the last clip; a full lap without an ear means only
+        // collinear or numerically degenerate corners are left, so clip one anyway.
+        let mut misses = 0;
+        while remaining.len() > 3 {
+            let m = remaining.len();
+            let (prev, cur, next) =
+                (remaining[(i + m - 1) % m], remaining[i], remaining[(i + 1) % m]);
+            let (a, b, c) = (v[prev], v[cur], v[next]);
+            let is_ear = turn(a, b, c) > 0.0
+                && !remaining
+                    .iter()
+                    .any(|&k| k != prev && k != cur && k != next && inside(v[k], a, b, c));
+            if is_ear || misses >= m {
+                triangles.push([a, b, c]);
+                remaining.remove(i);
+                i %= remaining.len();
+                misses = 0;
+            } else {
+                i = (i + 1) % m;
+                misses += 1;
+            }
+        }
+        triangles.push([v[remaining[0]], v[remaining[1]], v[remaining[2]]]);
+        triangles
+    }
+
+    // Even-odd test: true when a ray from `p` crosses the outline an odd number of
+    // times, so self-overlapping loops leave their doubly covered parts outside.
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        let mut inside = false;
+        for (a, b) in self.closed_edges() {
+            if (a.y > p.y) != (b.y > p.y) {
+                let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if p.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    // Occupancy grid of `resolution = (columns, rows)` cells spanning `bounds`, in
+    // row-major order from the `min` corner: cell `(c, r)` is at `r * columns + c` and
+    // is set when its center lies inside the polygon.
+    pub fn rasterize(&self, bounds: (Vec2, Vec2), resolution: (usize, usize)) -> Vec<bool> {
+        let (min, max) = bounds;
+        let (columns, rows) = resolution;
+        let cell = (max - min) / Vec2::new(columns.max(1) as Real, rows.max(1) as Real);
+        let mut grid = Vec::with_capacity(columns * rows);
+        for r in 0..rows {
+            for c in 0..columns {
+                let center = min + cell * Vec2::new(c as Real + 0.5, r as Real + 0.5);
+                grid.push(self.contains_point(center));
+            }
+        }
+        grid
+    }
+
+    // Vertices `start` through `end` inclusive, wrapping past the last vertex when
+    // `end < start`. The result is an open chain, see `is_closed`.
+    // Empty when either index is out of range.
+    pub fn subpath(&self, start: usize, end: usize) -> Polygon {
+        let n = self.vertices.len();
+        if start >= n || end >= n {
+            return Polygon::new();
+        }
+        let count = (end + n - start) % n + 1;
+        let vertices = (0..count).map(|k| self.vertices[(start + k) % n]).collect();
+        Polygon {
+            vertices,
+            closed: false,
+        }
+    }
+
+    // Point at fraction `f` of the perimeter, walking from vertex 0 in vertex order along
+    // the edges `perimeter` counts; `f` is wrapped into [0, 1). `None` for fewer than two
+    // vertices or a zero perimeter.
+    pub fn point_at_fraction(&self, f: Real) -> Option<Vec2> {
+        let n = self.vertices.len();
+        let perimeter = self.perimeter();
+        if n < 2 || perimeter <= 1e-12 || !f.is_finite() {
+            return None;
+        }
+
+        let mut remaining = f.rem_euclid(1.0) * perimeter;
+        for (a, b) in self.edges() {
+            let len = a.distance(b);
+            if remaining <= len && len > 1e-12 {
+                return Some(a.lerp(b, remaining / len));
+            }
+            remaining -= len;
+        }
+        // Rounding can leave a sliver past the last edge: the start again on a loop, the
+        // last vertex on an open chain.
+        Some(if self.closed { self.vertices[0] } else { self.vertices[n - 1] })
+    }
+
+    // `count` vertices evenly spaced by arc length around the closed outline, starting at
+    // vertex 0. Polygons with fewer than two vertices are returned unchanged.
+    pub fn resampled(&self, count: usize) -> Polygon {
+        let n = self.vertices.len();
+        let perimeter = self.perimeter();
+        if n < 2 || count == 0 || perimeter <= 1e-12 {
+            return self.clone();
+        }
+
+        let spacing = perimeter / count as Real;
+        let mut vertices = Vec::with_capacity(count);
+        let mut edge = 0;
+        let mut edge_start = 0.0;
+        for k in 0..count {
+            let s = k as Real * spacing;
+            let mut len = self.vertices[edge].distance(self.vertices[next_index(edge, n)]);
+            while edge_start + len < s && edge + 1 < n {
+                edge_start += len;
+                edge += 1;
+                len = self.vertices[edge].distance(self.vertices[next_index(edge, n)]);
+            }
+            let t = if len > 1e-12 { ((s - edge_start) / len).clamp(0.0, 1.0) } else { 0.0 };
+            vertices.push(self.vertices[edge].lerp(self.vertices[next_index(edge, n)], t));
+        }
+        Polygon {
+            vertices,
+            closed: true,
+        }
+    }
+
+    // Centroid of the enclosed region, independent of vertex density. Falls back to the
+    // vertex mean when the area is (near) zero.
+    pub fn area_centroid(&self) -> Option<Vec2> {
+        area_centroid(&self.vertices)
+    }
+
+    pub fn centroid_of_kind(&self, kind: CentroidKind) -> Option<Vec2> {
+        match kind {
+            CentroidKind::VertexMean => self.centroid(),
+            CentroidKind::Area => self.area_centroid(),
         }
-        total
     }
 
     pub fn centroid(&self) -> Option<Vec2> {
@@ -78,3 +726,131 @@ impl Polygon {
         Some(sum / (self.vertices.len() as Real))
     }
 }
+
+// Queue entry for `decimate_to`; ties break by index so results are deterministic.
+#[derive(Debug, Clone, Copy)]
+struct RemovalCandidate {
+    cost: Real,
+    index: usize,
+    version: u32,
+}
+
+impl PartialEq for RemovalCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RemovalCandidate {}
+
+impl PartialOrd for RemovalCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RemovalCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cost
+            .total_cmp(&other.cost)
+            .then(self.index.cmp(&other.index))
+            .then(self.version.cmp(&other.version))
+    }
+}
+
+// `Polygon::edges` over a bare vertex slice.
+pub(crate) fn edges(points: &[Vec2], closed: bool) -> impl Iterator<Item = (Vec2, Vec2)> {
+    let closing = (closed && points.len() > 1).then(|| (points[points.len() - 1], points[0]));
+    points.windows(2).map(|w| (w[0], w[1])).chain(closing)
+}
+
+// Neighbor indices on a closed loop of `n` vertices, for code working on bare slices.
+pub(crate) fn next_index(i: usize, n: usize) -> usize {
+    assert!(n > 0, "no vertex after {i} in an empty polygon");
+    (i + 1) % n
+}
+
+pub(crate) fn prev_index(i: usize, n: usize) -> usize {
+    assert!(n > 0, "no vertex before {i} in an empty polygon");
+    (i + n - 1) % n
+}
+
+pub(crate) fn vertex_curvature(points: &[Vec2], i: usize) -> Real {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+
+    let prev = points[prev_index(i, n)];
+    let v = points[i % n];
+    let next = points[next_index(i, n)];
+    let a = v - prev;
+    let b = next - v;
+    let mean_len = 0.5 * (a.length() + b.length());
+    if mean_len <= 1e-12 {
+        return 0.0;
+    }
+    a.perp_dot(b).atan2(a.dot(b)) / mean_len
+}
+
+pub fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq <= 1e-24 {
+        return a;
+    }
+    let t = ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+pub(crate) fn outward_sign(points: &[Vec2]) -> Real {
+    if signed_area(points) >= 0.0 { -1.0 } else { 1.0 }
+}
+
+pub(crate) fn signed_area(points: &[Vec2]) -> Real {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+
+    let mut sum = 0.0;
+    for i in 0..n {
+        let a = points[i];
+        let b = points[next_index(i, n)];
+        sum += a.x * b.y - b.x * a.y;
+    }
+    0.5 * sum
+}
+
+pub(crate) fn centroid_of_kind(points: &[Vec2], kind: CentroidKind) -> Option<Vec2> {
+    match kind {
+        CentroidKind::VertexMean if !points.is_empty() => {
+            let sum = points.iter().copied().fold(Vec2::ZERO, |acc, v| acc + v);
+            Some(sum / points.len() as Real)
+        }
+        CentroidKind::VertexMean => None,
+        CentroidKind::Area => area_centroid(points),
+    }
+}
+
+pub(crate) fn area_centroid(points: &[Vec2]) -> Option<Vec2> {
+    let n = points.len();
+    if n == 0 {
+        return None;
+    }
+
+    let mut area2 = 0.0;
+    let mut weighted = Vec2::ZERO;
+    for i in 0..n {
+        let a = points[i];
+        let b = points[next_index(i, n)];
+        let cross = a.x * b.y - b.x * a.y;
+        area2 += cross;
+        weighted += (a + b) * cross;
+    }
+    if area2.abs() <= 1e-12 {
+        let sum = points.iter().copied().fold(Vec2::ZERO, |acc, v| acc + v);
+        return Some(sum / n as Real);
+    }
+    Some(weighted / (3.0 * area2))
+}
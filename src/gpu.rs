@@ -0,0 +1,328 @@
+use crate::geometry::{Real, Vec2};
+use crate::sim::{effective_repulsion_strength, SimParams};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::mpsc;
+use std::sync::OnceLock;
+use std::task::{Context, Poll, Waker};
+
+// Largest per-vertex difference from the CPU pass, relative to `repulsion_strength`, that
+// `tests/gpu.rs` accepts on random clouds. The kernel sums in f32 and in a different
+// order, so results match to this tolerance rather than bit for bit.
+pub const GPU_REPULSION_TOLERANCE: Real = 1e-4;
+
+// Dense grids above this many cells fall back to the CPU instead of allocating them.
+const MAX_GRID_CELLS: usize = 1 << 22;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+// Gathers each vertex's pushes from the 3x3 block of grid cells around it, with the
+// same exclusion, falloff and 0.5 factor as `repulsion_push`. Positions arrive as an
+// integer cell plus an f32 offset within it, so nearby vertices keep their separation
+// to f32 precision relative to the cell rather than to the whole cloud.
+const REPULSION_WGSL: &str = r#"
+struct Params {
+    n: u32,
+    gx: u32,
+    gy: u32,
+    exclusion: u32,
+    cell: f32,
+    radius: f32,
+    strength: f32,
+    pad: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> offsets: array<vec2<f32>>;
+@group(0) @binding(2) var<storage, read> cells: array<vec2<i32>>;
+@group(0) @binding(3) var<storage, read> cell_start: array<u32>;
+@group(0) @binding(4) var<storage, read> sorted: array<u32>;
+@group(0) @binding(5) var<storage, read_write> delta: array<vec2<f32>>;
+
+fn within_ring_distance(i: u32, j: u32) -> bool {
+    let n = params.n;
+    if n < 2u || i == j {
+        return true;
+    }
+    let forward = (j + n - i) % n;
+    return min(forward, n - forward) <= params.exclusion;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= params.n {
+        return;
+    }
+    let p = offsets[i];
+    let own = cells[i];
+    let radius_sq = params.radius * params.radius;
+    var sum = vec2<f32>(0.0, 0.0);
+    for (var y = own.y - 1; y <= own.y + 1; y++) {
+        if y < 0 || y >= i32(params.gy) {
+            continue;
+        }
+        for (var x = own.x - 1; x <= own.x + 1; x++) {
+            if x < 0 || x >= i32(params.gx) {
+                continue;
+            }
+            let c = u32(y) * params.gx + u32(x);
+            for (var k = cell_start[c]; k < cell_start[c + 1u]; k++) {
+                let j = sorted[k];
+                if within_ring_distance(i, j) {
+                    continue;
+                }
+                let d = vec2<f32>(cells[j] - own) * params.cell + (offsets[j] - p);
+                let dist_sq = dot(d, d);
+                if dist_sq <= 1e-18 || dist_sq >= radius_sq {
+                    continue;
+                }
+                let dist = sqrt(dist_sq);
+                let mag = params.strength * (1.0 - dist / params.radius);
+                sum -= d / dist * (mag * 0.5);
+            }
+        }
+    }
+    delta[i] = sum;
+}
+"#;
+
+// Vertex repulsion as a wgpu compute pass. Each call bins the positions into a grid of
+// `repulsion_radius` cells on the CPU, uploads them, runs the kernel and reads the
+// displacements back.
+#[derive(Debug)]
+pub struct GpuRepulsion {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+#[cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+impl GpuRepulsion {
+    // `None` when no adapter or device is available.
+    pub fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let options = wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        };
+        let adapter = block_on(instance.request_adapter(&options)).ok()?;
+        let (device, queue) =
+            block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).ok()?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("repulsion"),
+            source: wgpu::ShaderSource::Wgsl(REPULSION_WGSL.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("repulsion"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Some(Self {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    // Add the repulsion pass into `delta`, as `ForceKind::Repulsion` would on the CPU.
+    // Returns false without touching `delta` for what the kernel does not cover: the
+    // pass switched off, a neighbor cap, or a grid or vertex count too large, so the
+    // caller can run the CPU pass instead.
+    pub fn accumulate(&self, positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) -> bool {
+        let n = positions.len();
+        if !params.repulsion_enabled
+            || params.repulsion_radius <= 0.0
+            || params.repulsion_max_neighbors > 0
+            || n < 2
+            || n > (u32::MAX / WORKGROUP_SIZE) as usize
+        {
+            return false;
+        }
+        let strength = if params.adaptive_repulsion {
+            effective_repulsion_strength(positions, params)
+        } else {
+            params.repulsion_strength
+        };
+        if strength <= 0.0 {
+            return false;
+        }
+        let Some(grid) = Grid::build(positions, params.repulsion_radius) else {
+            return false;
+        };
+
+        let mut uniforms = Vec::with_capacity(32);
+        for word in [n as u32, grid.gx as u32, grid.gy as u32] {
+            uniforms.extend_from_slice(&word.to_le_bytes());
+        }
+        let exclusion = params.repulsion_neighbor_exclusion.min(u32::MAX as usize) as u32;
+        uniforms.extend_from_slice(&exclusion.to_le_bytes());
+        for value in [params.repulsion_radius, params.repulsion_radius, strength, 0.0] {
+            uniforms.extend_from_slice(&(value as f32).to_le_bytes());
+        }
+
+        let out_size = (n * 8) as wgpu::BufferAddress;
+        let buffers = [
+            self.buffer(&uniforms, wgpu::BufferUsages::UNIFORM),
+            self.buffer(&f32_bytes(&grid.offsets), wgpu::BufferUsages::STORAGE),
+            self.buffer(&i32_bytes(&grid.cells), wgpu::BufferUsages::STORAGE),
+            self.buffer(&u32_bytes(&grid.cell_start), wgpu::BufferUsages::STORAGE),
+            self.buffer(&u32_bytes(&grid.sorted), wgpu::BufferUsages::STORAGE),
+        ];
+        let out = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("repulsion delta"),
+            size: out_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("repulsion readback"),
+            size: out_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .chain([&out])
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("repulsion"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups((n as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&out, 0, &readback, 0, out_size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        readback.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        if self.device.poll(wgpu::PollType::wait_indefinitely()).is_err()
+            || !matches!(receiver.recv(), Ok(Ok(())))
+        {
+            return false;
+        }
+        let bytes = readback.slice(..).get_mapped_range();
+        for (d, chunk) in delta.iter_mut().zip(bytes.chunks_exact(8)) {
+            let x = f32::from_le_bytes(chunk[..4].try_into().expect("four bytes"));
+            let y = f32::from_le_bytes(chunk[4..].try_into().expect("four bytes"));
+            *d += Vec2::new(x as Real, y as Real);
+        }
+        true
+    }
+
+    fn buffer(&self, contents: &[u8], usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: contents.len() as wgpu::BufferAddress,
+            usage: usage | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        self.queue.write_buffer(&buffer, 0, contents);
+        buffer
+    }
+}
+
+// Process-wide instance, created on first use; `None` when no GPU is available.
+pub fn shared() -> Option<&'static GpuRepulsion> {
+    static SHARED: OnceLock<Option<GpuRepulsion>> = OnceLock::new();
+    SHARED.get_or_init(GpuRepulsion::new).as_ref()
+}
+
+// Each vertex's cell and its offset from that cell's corner, as pairs, with vertex
+// indices sorted by row-major cell and each cell's start in that order (plus a final end).
+struct Grid {
+    gx: usize,
+    gy: usize,
+    offsets: Vec<f32>,
+    cells: Vec<i32>,
+    cell_start: Vec<u32>,
+    sorted: Vec<u32>,
+}
+
+#[cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+impl Grid {
+    fn build(positions: &[Vec2], cell: Real) -> Option<Self> {
+        let min = positions.iter().copied().reduce(Vec2::min)?;
+        let max = positions.iter().copied().reduce(Vec2::max)?;
+        let gx = ((max.x - min.x) / cell).floor() as usize + 1;
+        let gy = ((max.y - min.y) / cell).floor() as usize + 1;
+        if gx.checked_mul(gy).is_none_or(|cells| cells > MAX_GRID_CELLS) {
+            return None;
+        }
+
+        let mut offsets = Vec::with_capacity(2 * positions.len());
+        let mut cells = Vec::with_capacity(2 * positions.len());
+        let mut flat = Vec::with_capacity(positions.len());
+        for p in positions {
+            let local = *p - min;
+            let cx = ((local.x / cell).floor() as usize).min(gx - 1);
+            let cy = ((local.y / cell).floor() as usize).min(gy - 1);
+            offsets.push((local.x - cx as Real * cell) as f32);
+            offsets.push((local.y - cy as Real * cell) as f32);
+            cells.extend([cx as i32, cy as i32]);
+            flat.push(cy * gx + cx);
+        }
+        let mut cell_start = vec![0u32; gx * gy + 1];
+        for &c in &flat {
+            cell_start[c + 1] += 1;
+        }
+        for c in 0..gx * gy {
+            cell_start[c + 1] += cell_start[c];
+        }
+        let mut fill = cell_start.clone();
+        let mut sorted = vec![0u32; positions.len()];
+        for (i, &c) in flat.iter().enumerate() {
+            sorted[fill[c] as usize] = i as u32;
+            fill[c] += 1;
+        }
+        Some(Self {
+            gx,
+            gy,
+            offsets,
+            cells,
+            cell_start,
+            sorted,
+        })
+    }
+}
+
+fn f32_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn u32_bytes(values: &[u32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn i32_bytes(values: &[i32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+// wgpu's native futures resolve without a reactor, so polling until ready suffices.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
@@ -0,0 +1,134 @@
+use crate::geometry::Polygon;
+use crate::sim::Simulation;
+use std::collections::VecDeque;
+
+// Polygons captured at one generation, in simulation order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub generation: u64,
+    pub polygons: Vec<Polygon>,
+}
+
+// Bounded buffer of snapshots taken every `interval` generations; the oldest snapshot
+// is dropped once `capacity` is reached.
+#[derive(Debug, Clone)]
+pub struct History {
+    capacity: usize,
+    interval: u64,
+    snapshots: VecDeque<Snapshot>,
+}
+
+impl History {
+    pub fn new(capacity: usize, interval: u64) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            interval: interval.max(1),
+            snapshots: VecDeque::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    // Store a copy of `polygons` if `generation` falls on the interval and is newer than
+    // the last snapshot. Returns whether a snapshot was taken.
+    pub fn record(&mut self, generation: u64, polygons: &[Polygon]) -> bool {
+        if !generation.is_multiple_of(self.interval)
+            || self.snapshots.back().is_some_and(|last| last.generation >= generation)
+        {
+            return false;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(Snapshot {
+            generation,
+            polygons: polygons.to_vec(),
+        });
+        true
+    }
+
+    // Drop every snapshot newer than `generation`.
+    pub fn truncate_after(&mut self, generation: u64) {
+        while self.snapshots.back().is_some_and(|last| last.generation > generation) {
+            self.snapshots.pop_back();
+        }
+    }
+
+    // Snapshots from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Snapshot> + ExactSizeIterator {
+        self.snapshots.iter()
+    }
+
+    pub fn latest(&self) -> Option<&Snapshot> {
+        self.snapshots.back()
+    }
+
+    pub fn get(&self, generation: u64) -> Option<&Snapshot> {
+        self.snapshots
+            .binary_search_by_key(&generation, |s| s.generation)
+            .ok()
+            .map(|i| &self.snapshots[i])
+    }
+}
+
+// Bounded buffer of full simulation states, oldest first, for stepping back. A restored
+// clone carries its RNG, so stepping on from it repeats the original run exactly.
+#[derive(Debug, Clone)]
+pub struct Checkpoints {
+    capacity: usize,
+    states: VecDeque<Simulation>,
+}
+
+impl Checkpoints {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            states: VecDeque::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.states.clear();
+    }
+
+    pub fn push(&mut self, sim: &Simulation) {
+        if self.states.len() == self.capacity {
+            self.states.pop_front();
+        }
+        self.states.push_back(sim.clone());
+    }
+
+    // Latest state from before `generation`, dropping every later one; `None` leaves the
+    // buffer alone when there is no earlier state.
+    pub fn step_back(&mut self, generation: u64) -> Option<Simulation> {
+        let index = self.states.iter().rposition(|s| s.generation() < generation)?;
+        self.states.truncate(index + 1);
+        Some(self.states[index].clone())
+    }
+}
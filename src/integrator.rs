@@ -0,0 +1,87 @@
+use crate::geometry::Vec2;
+use std::fmt::Debug;
+
+// Advances positions by one step given the force field, writing them into `next`.
+// `forces` fills its buffer with the per-vertex displacement the forces ask for at the
+// positions passed in (already scaled by mass); an integrator may evaluate it several
+// times, and each call draws jitter afresh. Buffers are reused across steps, so
+// implementations should overwrite them rather than allocate.
+pub trait Integrator: Debug + Send {
+    fn integrate(
+        &mut self,
+        positions: &[Vec2],
+        forces: &mut dyn FnMut(&[Vec2], &mut Vec<Vec2>),
+        next: &mut Vec<Vec2>,
+    );
+
+    // Short display name.
+    fn name(&self) -> &'static str;
+
+    // `Simulation` is `Clone`, so boxed integrators must be too.
+    fn box_clone(&self) -> Box<dyn Integrator>;
+}
+
+impl Clone for Box<dyn Integrator> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+// Apply the displacement directly: `x + f(x)`. The original update rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Euler;
+
+impl Integrator for Euler {
+    fn integrate(
+        &mut self,
+        positions: &[Vec2],
+        forces: &mut dyn FnMut(&[Vec2], &mut Vec<Vec2>),
+        next: &mut Vec<Vec2>,
+    ) {
+        forces(positions, next);
+        for (n, p) in next.iter_mut().zip(positions) {
+            *n += *p;
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Euler"
+    }
+
+    fn box_clone(&self) -> Box<dyn Integrator> {
+        Box::new(*self)
+    }
+}
+
+// Second-order Runge-Kutta: evaluate the forces again halfway along the Euler step and
+// apply that displacement instead. Twice the force cost per step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Midpoint {
+    // Positions halfway along the Euler step, kept between steps to reuse the allocation.
+    halfway: Vec<Vec2>,
+}
+
+impl Integrator for Midpoint {
+    fn integrate(
+        &mut self,
+        positions: &[Vec2],
+        forces: &mut dyn FnMut(&[Vec2], &mut Vec<Vec2>),
+        next: &mut Vec<Vec2>,
+    ) {
+        forces(positions, next);
+        self.halfway.clear();
+        self.halfway.extend(positions.iter().zip(next.iter()).map(|(p, d)| *p + *d * 0.5));
+        forces(&self.halfway, next);
+        for (n, p) in next.iter_mut().zip(positions) {
+            *n += *p;
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Midpoint"
+    }
+
+    fn box_clone(&self) -> Box<dyn Integrator> {
+        Box::new(self.clone())
+    }
+}
@@ -1,2 +1,23 @@
+pub mod array_polygon;
+pub mod colormap;
+pub mod config_code;
+pub mod error;
+pub mod force;
 pub mod geometry;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod history;
+pub mod integrator;
+pub mod lottie;
+pub mod param_info;
+pub mod param_log;
+pub mod render;
 pub mod sim;
+pub mod split;
+pub mod starter;
+pub mod svg;
+#[cfg(feature = "text")]
+pub mod text;
+pub mod validate;
+#[cfg(feature = "gui")]
+pub mod view;
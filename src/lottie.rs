@@ -0,0 +1,105 @@
+use crate::geometry::{Polygon, Real, Vec2};
+use crate::history::History;
+use std::fmt::Write;
+
+// Canvas, timing and stroke settings for Lottie export.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LottieStyle {
+    pub width: u32,
+    pub height: u32,
+    pub frame_rate: Real,
+    // Animation frames between consecutive snapshots; the path is interpolated linearly.
+    pub frames_per_keyframe: u32,
+    pub stroke_width: Real,
+    pub stroke: [u8; 4],
+}
+
+impl Default for LottieStyle {
+    fn default() -> Self {
+        Self {
+            width: 512,
+            height: 512,
+            frame_rate: 30.0,
+            frames_per_keyframe: 2,
+            stroke_width: 2.0,
+            stroke: [144, 238, 144, 255],
+        }
+    }
+}
+
+// Minimal Lottie document with one shape layer holding a path per polygon, each with a
+// keyframe per snapshot. Lottie only morphs between paths of equal vertex count, so every
+// snapshot of a polygon is resampled to the largest count it reaches in the history. All
+// frames share one fit so the curves do not jump as they grow.
+pub fn export_lottie(history: &History, style: &LottieStyle) -> String {
+    let (min, max) = history
+        .iter()
+        .flat_map(|s| &s.polygons)
+        .filter_map(Polygon::bounds)
+        .reduce(|(a0, a1), (b0, b1)| (a0.min(b0), a1.max(b1)))
+        .unwrap_or((Vec2::ZERO, Vec2::ZERO));
+    let (w, h) = (style.width as Real, style.height as Real);
+    let center = (min + max) * 0.5;
+    let scale = (w / (max.x - min.x).max(1e-6)).min(h / (max.y - min.y).max(1e-6)) * 0.9;
+    // World y points up; Lottie y points down.
+    let to_canvas = |p: Vec2| {
+        Vec2::new(
+            w * 0.5 + (p.x - center.x) * scale,
+            h * 0.5 - (p.y - center.y) * scale,
+        )
+    };
+
+    let step = style.frames_per_keyframe.max(1) as u64;
+    let polygon_count = history.iter().map(|s| s.polygons.len()).max().unwrap_or(0);
+    let mut paths = String::new();
+    for p in 0..polygon_count {
+        // Snapshot index and polygon `p` in it, for every snapshot that has one.
+        let frames = || {
+            history
+                .iter()
+                .enumerate()
+                .filter_map(|(k, s)| s.polygons.get(p).map(|polygon| (k, polygon)))
+        };
+        let count = frames().map(|(_, polygon)| polygon.len()).max().unwrap_or(0);
+        let mut keyframes = Vec::new();
+        for (k, polygon) in frames() {
+            let polygon = polygon.resampled(count);
+            let mut vertices = String::new();
+            let mut tangents = String::new();
+            for (i, v) in polygon.vertices().iter().copied().map(to_canvas).enumerate() {
+                let sep = if i == 0 { "" } else { "," };
+                let _ = write!(vertices, "{sep}[{:.3},{:.3}]", v.x, v.y);
+                let _ = write!(tangents, "{sep}[0,0]");
+            }
+            keyframes.push(format!(
+                "{{\"t\":{},\"s\":[{{\"i\":[{tangents}],\"o\":[{tangents}],\"v\":[{vertices}],\
+                 \"c\":true}}],\"i\":{{\"x\":[1],\"y\":[1]}},\"o\":{{\"x\":[0],\"y\":[0]}}}}",
+                k as u64 * step
+            ));
+        }
+        let _ = write!(
+            paths,
+            "{{\"ty\":\"sh\",\"nm\":\"path {p}\",\"ks\":{{\"a\":1,\"k\":[{}]}}}},",
+            keyframes.join(",")
+        );
+    }
+    let out_point = (history.len().max(1) as u64 - 1) * step + 1;
+
+    let [r, g, b, a] = style.stroke.map(|c| c as Real / 255.0);
+    let static_transform = "\"o\":{\"a\":0,\"k\":100},\"r\":{\"a\":0,\"k\":0},\
+        \"p\":{\"a\":0,\"k\":[0,0,0]},\"a\":{\"a\":0,\"k\":[0,0,0]},\"s\":{\"a\":0,\"k\":[100,100,100]}";
+    format!(
+        "{{\"v\":\"5.7.0\",\"fr\":{fr},\"ip\":0,\"op\":{out_point},\"w\":{width},\"h\":{height},\
+         \"nm\":\"growth\",\"ddd\":0,\"assets\":[],\"layers\":[{{\"ddd\":0,\"ind\":1,\"ty\":4,\
+         \"nm\":\"curve\",\"sr\":1,\"ks\":{{{static_transform}}},\"ao\":0,\"shapes\":[{{\"ty\":\"gr\",\
+         \"nm\":\"outline\",\"it\":[{paths}{{\"ty\":\"st\",\"nm\":\"stroke\",\
+         \"c\":{{\"a\":0,\"k\":[{r},{g},{b},1]}},\"o\":{{\"a\":0,\"k\":{opacity}}},\
+         \"w\":{{\"a\":0,\"k\":{stroke_width}}},\"lc\":2,\"lj\":2}},\
+         {{\"ty\":\"tr\",{static_transform}}}]}}],\"ip\":0,\"op\":{out_point},\"st\":0,\"bm\":0}}]}}",
+        fr = style.frame_rate,
+        width = style.width,
+        height = style.height,
+        opacity = a * 100.0,
+        stroke_width = style.stroke_width,
+    )
+}
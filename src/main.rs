@@ -1,10 +1,49 @@
-use dg4::geometry::{Polygon, Vec2};
+// egui works in f32, so world-to-screen casts are no-ops under the `f32` feature.
+#![cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+
+use dg4::colormap::Colormap;
+use dg4::config_code::{decode_config, decode_params, encode_config, encode_params, SharedConfig};
+use dg4::error::GrowthError;
+use dg4::geometry::{CentroidKind, Polygon, Real, Vec2};
+use dg4::history::{Checkpoints, History};
+use dg4::integrator::{Euler, Integrator, Midpoint};
+use dg4::lottie::{export_lottie, LottieStyle};
+use dg4::param_info;
+use dg4::param_log::ParamLog;
+use dg4::render::{
+    render_polygons, render_svg_frames, Appearance, FrameRecorder, RenderStyle, VertexMarker,
+};
+use dg4::svg::{
+    displacement_svg_document, history_trail_svg, polygons_from_svg, svg_document,
+    svg_document_colored, ExportReduction,
+};
 use dg4::sim::{
-    average_edge_length, regular_ngon_edge_length, ConstraintFalloff, ConstraintShape, SimParams,
-    Simulation,
+    average_edge_length, effective_repulsion_strength, framing_bounds, regular_ngon_edge_length,
+    triangle_vertices, Attractor, ConstraintFalloff, ConstraintShape, GrowthCurvatureFilter,
+    ForceKind, SimParams, Simulation, SpatialHash, StepReport, DEFAULT_FORCE_ORDER,
+    MAX_GROWTH_INHIBITORS,
 };
+use dg4::starter::{Starter, StarterShape};
+use dg4::view::ViewTransform;
 use eframe::egui::{self, Color32, Pos2, Rect, Sense, Shape, Stroke};
 
+// Deterministic RNG seed used at startup and on reset.
+const DEFAULT_SEED: u64 = 0xD1FF_EA11_2026_0001;
+
+// Most recent generations drawn in the vertex count sparkline.
+const SPARKLINE_LENGTH: usize = 500;
+
+// Pixel size of each recorded PNG frame.
+const RECORD_FRAME_SIZE: u32 = 1024;
+
+// Snapshot history kept for animation export: every 10th generation, up to 500 snapshots.
+const HISTORY_CAPACITY: usize = 500;
+const HISTORY_INTERVAL: u64 = 10;
+
+// Farthest a flattened glyph curve may stray from the font's, relative to the glyph size.
+#[cfg(feature = "text")]
+const GLYPH_TOLERANCE: Real = 0.002;
+
 // Launch a native egui desktop window.
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
@@ -44,76 +83,451 @@ impl ViewMode {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntegratorKind {
+    Euler,
+    Midpoint,
+}
+
+impl IntegratorKind {
+    const ALL: [Self; 2] = [Self::Euler, Self::Midpoint];
+
+    fn build(self) -> Box<dyn Integrator> {
+        match self {
+            Self::Euler => Box::new(Euler),
+            Self::Midpoint => Box::<Midpoint>::default(),
+        }
+    }
+
+    // Matches `Integrator::name` of the built integrator.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Euler => "Euler",
+            Self::Midpoint => "Midpoint",
+        }
+    }
+}
+
+fn draw_vertex_marker(
+    painter: &egui::Painter,
+    marker: VertexMarker,
+    center: Pos2,
+    radius: f32,
+    color: Color32,
+) {
+    match marker {
+        VertexMarker::FilledCircle => {
+            painter.circle_filled(center, radius, color);
+        }
+        VertexMarker::HollowCircle => {
+            painter.circle_stroke(center, radius, Stroke::new(1.0, color));
+        }
+        VertexMarker::Square => {
+            let rect = Rect::from_center_size(center, egui::vec2(2.0 * radius, 2.0 * radius));
+            painter.rect_filled(rect, 0.0, color);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeColoring {
+    // Single curve color.
+    Solid,
+    // Gradient by edge length, shortest to longest.
+    Length,
+}
+
+impl EdgeColoring {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Solid => "Solid",
+            Self::Length => "Edge Length",
+        }
+    }
+}
+
+// Viewport palette used by the renderer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Theme {
+    background: Color32,
+    curve: Color32,
+    vertex: Color32,
+    constraint_fill: Color32,
+    constraint_stroke: Color32,
+}
+
+impl Theme {
+    const DARK: Self = Self {
+        background: Color32::from_gray(20),
+        curve: Color32::LIGHT_GREEN,
+        vertex: Color32::from_rgb(250, 220, 130),
+        constraint_fill: Color32::from_rgba_premultiplied(90, 120, 140, 28),
+        constraint_stroke: Color32::from_rgba_premultiplied(120, 160, 180, 80),
+    };
+
+    const LIGHT: Self = Self {
+        background: Color32::from_gray(245),
+        curve: Color32::from_rgb(30, 30, 30),
+        vertex: Color32::from_rgb(200, 70, 40),
+        constraint_fill: Color32::from_rgba_premultiplied(40, 60, 90, 20),
+        constraint_stroke: Color32::from_rgba_premultiplied(40, 60, 90, 90),
+    };
+
+    const BLUEPRINT: Self = Self {
+        background: Color32::from_rgb(18, 52, 96),
+        curve: Color32::from_rgb(230, 240, 255),
+        vertex: Color32::from_rgb(150, 200, 255),
+        constraint_fill: Color32::from_rgba_premultiplied(20, 30, 40, 30),
+        constraint_stroke: Color32::from_rgba_premultiplied(180, 210, 240, 110),
+    };
+
+    const PRESETS: [(&'static str, Self); 3] = [
+        ("Dark", Self::DARK),
+        ("Light", Self::LIGHT),
+        ("Blueprint", Self::BLUEPRINT),
+    ];
+
+    fn label(self) -> &'static str {
+        Self::PRESETS
+            .iter()
+            .find(|(_, theme)| *theme == self)
+            .map_or("Custom", |(name, _)| name)
+    }
+
+    fn from_appearance(appearance: &Appearance) -> Self {
+        let color = |[r, g, b, a]: [u8; 4]| Color32::from_rgba_premultiplied(r, g, b, a);
+        Self {
+            background: color(appearance.background),
+            curve: color(appearance.curve),
+            vertex: color(appearance.vertex),
+            constraint_fill: color(appearance.constraint_fill),
+            constraint_stroke: color(appearance.constraint_stroke),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct DgApp {
     // Starter shape controls.
-    radius: f64,
+    starter_shape: StarterShape,
+    radius: Real,
     sides: usize,
+    // Inner/outer radius ratio for stars.
+    star_inner_ratio: Real,
+    // Height/width ratio and corner radius (relative to the radius) for rounded rectangles.
+    rect_aspect: Real,
+    corner_ratio: Real,
+    starter_center: Vec2,
+    perturb_magnitude: Real,
     // Camera / view controls.
     view_mode: ViewMode,
-    zoom_px_per_unit: f64,
+    // Fit also frames the constraint area while its overlay is shown.
+    fit_constraint: bool,
+    zoom_px_per_unit: Real,
+    pan: Vec2,
+    draw_closed: bool,
+    // Curve stroke width in screen pixels, also used for recorded frames.
+    line_width: f32,
+    // Translucent interior from `Polygon::triangulate`, skipped while self-intersecting.
+    fill_interior: bool,
+    show_winding: bool,
+    // Live arrows of the growth pass at each vertex, scaled like the force preview.
+    show_growth_front: bool,
+    // Repulsion's spatial hash: grid lines plus occupied cells shaded by vertex count.
+    show_spatial_hash: bool,
+    // Clicks in the viewport place up to two world-space endpoints; a third clears them.
+    measure_mode: bool,
+    measure_points: Vec<Vec2>,
+    edge_coloring: EdgeColoring,
+    show_scale_bar: bool,
+    theme: Theme,
+    show_vertices: bool,
+    vertex_marker: VertexMarker,
+    // Marker radius (half the square's side) in pixels.
+    vertex_marker_size: f32,
+    // One-off force field from "Preview Forces", for the generation it was computed at.
+    force_preview: Option<(u64, Vec<Vec2>)>,
+    // Multiplier on each previewed displacement so the arrows are long enough to read.
+    force_preview_scale: f32,
+    // Tint each vertex dot by a hue hashed from its id, to follow vertices over time.
+    color_vertices_by_id: bool,
+    // Vertex dots are suppressed automatically above this count.
+    vertex_dot_limit: usize,
     // Edge spring force controls.
     edge_regularization_enabled: bool,
-    target_edge_length: f64,
-    edge_stiffness: f64,
+    target_edge_length: Real,
+    edge_stiffness: Real,
+    // Separate tension/compression stiffness; when off both follow `edge_stiffness`.
+    asymmetric_stiffness: bool,
+    edge_stiffness_tension: Real,
+    edge_stiffness_compression: Real,
+    edge_dead_zone: Real,
     // Non-neighbor short-range repulsion controls.
     repulsion_enabled: bool,
-    repulsion_radius: f64,
-    repulsion_strength: f64,
+    repulsion_radius: Real,
+    repulsion_strength: Real,
+    repulsion_threads: usize,
+    repulsion_max_neighbors: usize,
+    repulsion_neighbor_exclusion: usize,
+    adaptive_repulsion: bool,
+    adaptive_repulsion_max: Real,
+    edge_repulsion_enabled: bool,
+    edge_repulsion_strength: Real,
     // Normal growth controls.
     growth_enabled: bool,
-    growth_rate: f64,
+    growth_rate: Real,
+    area_goal_enabled: bool,
+    erosion_guard_enabled: bool,
+    growth_erosion_min_edge: Real,
+    growth_area_goal: Real,
+    max_area_enabled: bool,
+    max_area: Real,
+    min_area_enabled: bool,
+    min_area: Real,
+    growth_conserve_perimeter: bool,
+    growth_curvature_filter: GrowthCurvatureFilter,
+    growth_density_gain: Real,
+    growth_normal_smoothing: Real,
+    growth_tangent_window: usize,
+    // Placed by shift-clicking the viewport; new ones take the radius and strength below.
+    growth_inhibitors: Vec<Attractor>,
+    inhibitor_radius: Real,
+    inhibitor_strength: Real,
+    centering_strength: Real,
+    centroid_kind: CentroidKind,
     // Edge splitting controls.
     split_enabled: bool,
-    split_length: f64,
+    split_length: Real,
+    auto_retarget_on_split: bool,
+    split_refractory_generations: u64,
+    split_curvature_enabled: bool,
+    split_min_curvature: Real,
+    merge_enabled: bool,
+    merge_length: Real,
     // Constraint region controls.
     constraint_enabled: bool,
     constraint_shape: ConstraintShape,
-    constraint_size: f64,
-    constraint_strength: f64,
+    constraint_size: Real,
+    constraint_strength: Real,
     constraint_falloff: ConstraintFalloff,
+    constraint_ramp_generations: u64,
+    constraint_inverted: bool,
+    constraint_center: Vec2,
+    constraint_size_rate: Real,
+    constraint_center_velocity: Vec2,
     constraint_show: bool,
     // Brownian jitter controls.
     jitter_enabled: bool,
-    jitter_strength: f64,
+    jitter_strength: Real,
+    jitter_first: bool,
+    integrator: IntegratorKind,
+    // Mouse attractor controls.
+    mouse_attract_enabled: bool,
+    mouse_attract_radius: Real,
+    mouse_attract_strength: Real,
     // Simulation stepping controls.
     auto_step: bool,
     steps_per_frame: usize,
+    auto_pause_enabled: bool,
+    convergence_threshold: Real,
+    // Per-force step timing shown in the metrics.
+    profile_steps: bool,
     // Simulation state.
     sim: Simulation,
     pending_rebuild: bool,
+    // Cursor position in world units from the last drawn frame.
+    mouse_world: Option<Vec2>,
+    // Vertex id being dragged with the primary button, and the view frozen at drag start
+    // so fit-to-bounds does not rescale under the cursor.
+    dragged_vertex: Option<(u64, ViewTransform)>,
+    // SVG save/load file path and last result.
+    svg_file: String,
+    svg_status: String,
+    export_reduction: ExportReduction,
+    export_simplify_tolerance: Real,
+    export_decimate_count: usize,
+    diff_file: String,
+    // Font file, character and size for loading a glyph's contours, and the last result.
+    #[cfg(feature = "text")]
+    font_file: String,
+    #[cfg(feature = "text")]
+    glyph_char: String,
+    #[cfg(feature = "text")]
+    glyph_size: Real,
+    #[cfg(feature = "text")]
+    glyph_status: String,
+    // Text field for pasting a saved seed.
+    seed_input: String,
+    // Pasted config code and the result of the last copy or apply.
+    config_code_input: String,
+    config_code_status: String,
+    // Params-only save file, see `encode_params`.
+    params_file: String,
+    params_file_status: String,
+    // PNG frame recording state.
+    record_dir: String,
+    recorder: Option<FrameRecorder>,
+    record_last_generation: Option<u64>,
+    record_status: String,
+    // Snapshots for Lottie export, restarted whenever the polygon is rebuilt or loaded.
+    history: History,
+    // Full simulation state at each history snapshot, for Step Back.
+    checkpoints: Checkpoints,
+    lottie_file: String,
+    lottie_status: String,
+    svg_frames_dir: String,
+    svg_frames_status: String,
+    trail_svg_file: String,
+    // Parameter edit log and the params it last compared against.
+    param_log_enabled: bool,
+    param_log: ParamLog,
+    logged_params: SimParams,
+    param_log_file: String,
+    param_log_status: String,
 }
 
 impl Default for DgApp {
     fn default() -> Self {
         let mut app = Self {
+            starter_shape: StarterShape::Ngon,
             radius: 1.0,
             sides: 32,
+            star_inner_ratio: 0.5,
+            rect_aspect: 0.6,
+            corner_ratio: 0.3,
+            starter_center: Vec2::ZERO,
+            perturb_magnitude: 0.0,
             view_mode: ViewMode::Fit,
+            fit_constraint: true,
             zoom_px_per_unit: 120.0,
+            pan: Vec2::ZERO,
+            draw_closed: true,
+            line_width: 2.0,
+            fill_interior: false,
+            show_winding: false,
+            show_growth_front: false,
+            show_spatial_hash: false,
+            measure_mode: false,
+            measure_points: Vec::new(),
+            edge_coloring: EdgeColoring::Solid,
+            show_scale_bar: false,
+            theme: Theme::DARK,
+            show_vertices: true,
+            vertex_marker: VertexMarker::FilledCircle,
+            vertex_marker_size: 3.0,
+            force_preview: None,
+            force_preview_scale: 20.0,
+            color_vertices_by_id: false,
+            vertex_dot_limit: 2000,
             edge_regularization_enabled: true,
             target_edge_length: regular_ngon_edge_length(1.0, 32),
             edge_stiffness: 0.2,
+            asymmetric_stiffness: false,
+            edge_stiffness_tension: 0.2,
+            edge_stiffness_compression: 0.2,
+            edge_dead_zone: 0.0,
             repulsion_enabled: true,
             repulsion_radius: 0.15,
             repulsion_strength: 0.01,
+            repulsion_threads: 1,
+            repulsion_max_neighbors: 0,
+            repulsion_neighbor_exclusion: 1,
+            adaptive_repulsion: false,
+            adaptive_repulsion_max: 4.0,
+            edge_repulsion_enabled: false,
+            edge_repulsion_strength: 0.01,
             growth_enabled: false,
             growth_rate: 0.001,
+            area_goal_enabled: false,
+            erosion_guard_enabled: false,
+            growth_erosion_min_edge: 0.01,
+            growth_area_goal: 12.0,
+            max_area_enabled: false,
+            max_area: 50.0,
+            min_area_enabled: false,
+            min_area: 0.5,
+            growth_conserve_perimeter: false,
+            growth_curvature_filter: GrowthCurvatureFilter::All,
+            growth_density_gain: 0.0,
+            growth_normal_smoothing: 0.0,
+            growth_tangent_window: 1,
+            growth_inhibitors: Vec::new(),
+            inhibitor_radius: 0.3,
+            inhibitor_strength: 0.9,
+            centering_strength: 0.0,
+            centroid_kind: CentroidKind::Area,
             split_enabled: false,
             split_length: 0.25,
+            auto_retarget_on_split: false,
+            split_refractory_generations: 0,
+            split_curvature_enabled: false,
+            split_min_curvature: 2.0,
+            merge_enabled: false,
+            merge_length: 0.01,
             constraint_enabled: false,
             constraint_shape: ConstraintShape::Circle,
             constraint_size: 1.5,
             constraint_strength: 0.1,
             constraint_falloff: ConstraintFalloff::Linear,
+            constraint_ramp_generations: 0,
+            constraint_inverted: false,
+            constraint_center: Vec2::ZERO,
+            constraint_size_rate: 0.0,
+            constraint_center_velocity: Vec2::ZERO,
             constraint_show: true,
             jitter_enabled: true,
             jitter_strength: 0.005,
+            jitter_first: false,
+            integrator: IntegratorKind::Euler,
+            mouse_attract_enabled: false,
+            mouse_attract_radius: 0.5,
+            mouse_attract_strength: 0.01,
             auto_step: true,
             steps_per_frame: 1,
-            sim: Simulation::new(0xD1FF_EA11_2026_0001),
+            auto_pause_enabled: false,
+            convergence_threshold: 1e-5,
+            profile_steps: false,
+            sim: Simulation::new(DEFAULT_SEED),
             pending_rebuild: false,
+            mouse_world: None,
+            dragged_vertex: None,
+            svg_file: "growth.svg".to_owned(),
+            svg_status: String::new(),
+            export_reduction: ExportReduction::None,
+            export_simplify_tolerance: 0.005,
+            export_decimate_count: 200,
+            diff_file: "diff.svg".to_owned(),
+            #[cfg(feature = "text")]
+            font_file: "font.ttf".to_owned(),
+            #[cfg(feature = "text")]
+            glyph_char: "G".to_owned(),
+            #[cfg(feature = "text")]
+            glyph_size: 1.5,
+            #[cfg(feature = "text")]
+            glyph_status: String::new(),
+            seed_input: String::new(),
+            config_code_input: String::new(),
+            config_code_status: String::new(),
+            params_file: "params.dg4".to_owned(),
+            params_file_status: String::new(),
+            record_dir: "frames".to_owned(),
+            recorder: None,
+            record_last_generation: None,
+            record_status: String::new(),
+            history: History::new(HISTORY_CAPACITY, HISTORY_INTERVAL),
+            checkpoints: Checkpoints::new(HISTORY_CAPACITY),
+            lottie_file: "growth.json".to_owned(),
+            lottie_status: String::new(),
+            svg_frames_dir: "svg_frames".to_owned(),
+            svg_frames_status: String::new(),
+            trail_svg_file: "trail.svg".to_owned(),
+            param_log_enabled: false,
+            param_log: ParamLog::new(),
+            logged_params: SimParams::default(),
+            param_log_file: "params.json".to_owned(),
+            param_log_status: String::new(),
         };
+        app.logged_params = app.sim_params();
         app.rebuild_polygon();
         app
     }
@@ -122,83 +536,643 @@ impl Default for DgApp {
 impl DgApp {
     // Rebuild starter geometry from current shape parameters.
     fn rebuild_polygon(&mut self) {
-        self.sim.rebuild_polygon(self.radius, self.sides);
-        self.target_edge_length = regular_ngon_edge_length(self.radius, self.sides);
+        // Fewer than 3 sides builds nothing, so keep the slider value in range.
+        self.sides = self.sides.max(3);
+        let (radius, sides) = (self.radius, self.sides);
+        let starter = match self.starter_shape {
+            StarterShape::Ngon => None,
+            StarterShape::Star => Some(Polygon::star(sides, radius * self.star_inner_ratio, radius)),
+            StarterShape::RoundedRect => Some(Polygon::rounded_rect(
+                2.0 * radius,
+                2.0 * radius * self.rect_aspect,
+                radius * self.corner_ratio,
+                (sides / 4).max(1),
+            )),
+            StarterShape::SymmetricNgon => {
+                Some(Polygon::mirror_symmetric_ngon(radius, sides, Vec2::Y))
+            }
+        };
+        match starter {
+            Some(mut polygon) => {
+                let center = self.starter_center;
+                polygon.map_vertices(|v| v + center);
+                self.sim.set_polygon(polygon);
+                self.target_edge_length = average_edge_length(self.sim.polygon());
+            }
+            None => {
+                self.sim.rebuild_polygon_at(self.starter_center, radius, sides);
+                self.target_edge_length = regular_ngon_edge_length(radius, sides);
+            }
+        }
+        self.sim.perturb(self.perturb_magnitude);
+        self.reset_history();
+        self.force_preview = None;
+    }
+
+    // Start history and step-back checkpoints afresh from the current state.
+    fn reset_history(&mut self) {
+        self.history.clear();
+        self.checkpoints.clear();
+        self.record_history();
+    }
+
+    fn record_history(&mut self) {
+        if self.history.record(self.sim.generation(), self.sim.polygons()) {
+            self.checkpoints.push(&self.sim);
+        }
+    }
+
+    // Restore the latest checkpoint before the current generation and discard everything
+    // recorded after it. Does nothing when there is no earlier checkpoint.
+    fn step_back(&mut self) {
+        let Some(sim) = self.checkpoints.step_back(self.sim.generation()) else {
+            return;
+        };
+        self.sim = sim;
+        self.history.truncate_after(self.sim.generation());
+        if let Some(target) = self.sim.retargeted_edge_length() {
+            self.target_edge_length = target;
+        }
+        self.force_preview = None;
+    }
+
+    fn starter(&self) -> Starter {
+        Starter {
+            shape: self.starter_shape,
+            radius: self.radius,
+            sides: self.sides,
+            star_inner_ratio: self.star_inner_ratio,
+            rect_aspect: self.rect_aspect,
+            corner_ratio: self.corner_ratio,
+            center: self.starter_center,
+            perturb_magnitude: self.perturb_magnitude,
+        }
+    }
+
+    fn shared_config(&self) -> SharedConfig {
+        SharedConfig {
+            params: self.sim_params(),
+            seed: self.sim.seed(),
+            starter: self.starter(),
+            appearance: self.appearance(),
+        }
+    }
+
+    fn appearance(&self) -> Appearance {
+        let theme = self.theme;
+        Appearance {
+            background: theme.background.to_array(),
+            curve: theme.curve.to_array(),
+            vertex: theme.vertex.to_array(),
+            constraint_fill: theme.constraint_fill.to_array(),
+            constraint_stroke: theme.constraint_stroke.to_array(),
+            vertex_marker: self.vertex_marker,
+            vertex_marker_size: self.vertex_marker_size,
+            line_width: self.line_width,
+        }
+    }
+
+    // Inverse of `sim_params`, `starter` and the appearance. The mouse attractor is live
+    // input, so it is not restored; force orders other than the two the GUI offers fall back
+    // to those.
+    fn apply_config(&mut self, config: &SharedConfig) {
+        let starter = config.starter;
+        self.starter_shape = starter.shape;
+        self.radius = starter.radius;
+        self.sides = starter.sides;
+        self.star_inner_ratio = starter.star_inner_ratio;
+        self.rect_aspect = starter.rect_aspect;
+        self.corner_ratio = starter.corner_ratio;
+        self.starter_center = starter.center;
+        self.perturb_magnitude = starter.perturb_magnitude;
+        self.theme = Theme::from_appearance(&config.appearance);
+        self.vertex_marker = config.appearance.vertex_marker;
+        self.vertex_marker_size = config.appearance.vertex_marker_size;
+        self.line_width = config.appearance.line_width;
+
+        self.apply_params(&config.params);
+        self.sim.reset_seed(config.seed);
+        self.rebuild_polygon();
+        // The rebuild derives a target edge length from the starter; the code's wins.
+        self.target_edge_length = config.params.target_edge_length;
+    }
+
+    // The parameter half of `apply_config`, leaving the polygon and generation alone.
+    fn apply_params(&mut self, p: &SimParams) {
+        self.target_edge_length = p.target_edge_length;
+        self.edge_regularization_enabled = p.edge_regularization_enabled;
+        self.edge_stiffness = p.edge_stiffness;
+        self.asymmetric_stiffness =
+            p.edge_stiffness_tension.is_some() || p.edge_stiffness_compression.is_some();
+        self.edge_stiffness_tension = p.edge_stiffness_tension.unwrap_or(p.edge_stiffness);
+        self.edge_stiffness_compression = p.edge_stiffness_compression.unwrap_or(p.edge_stiffness);
+        self.edge_dead_zone = p.edge_dead_zone;
+        self.repulsion_enabled = p.repulsion_enabled;
+        self.repulsion_radius = p.repulsion_radius;
+        self.repulsion_strength = p.repulsion_strength;
+        self.repulsion_threads = p.repulsion_threads;
+        self.repulsion_max_neighbors = p.repulsion_max_neighbors;
+        self.repulsion_neighbor_exclusion = p.repulsion_neighbor_exclusion;
+        self.adaptive_repulsion = p.adaptive_repulsion;
+        self.adaptive_repulsion_max = p.adaptive_repulsion_max;
+        self.edge_repulsion_enabled = p.edge_repulsion_enabled;
+        self.edge_repulsion_strength = p.edge_repulsion_strength;
+        self.growth_enabled = p.growth_enabled;
+        self.growth_rate = p.growth_rate;
+        self.area_goal_enabled = p.growth_area_goal.is_some();
+        if let Some(goal) = p.growth_area_goal {
+            self.growth_area_goal = goal;
+        }
+        self.max_area_enabled = p.max_area.is_some();
+        if let Some(max) = p.max_area {
+            self.max_area = max;
+        }
+        self.min_area_enabled = p.min_area.is_some();
+        if let Some(min) = p.min_area {
+            self.min_area = min;
+        }
+        self.erosion_guard_enabled = p.growth_erosion_min_edge.is_some();
+        if let Some(min_edge) = p.growth_erosion_min_edge {
+            self.growth_erosion_min_edge = min_edge;
+        }
+        self.growth_conserve_perimeter = p.growth_conserve_perimeter;
+        self.growth_curvature_filter = p.growth_curvature_filter;
+        self.growth_density_gain = p.growth_density_gain;
+        self.growth_normal_smoothing = p.growth_normal_smoothing;
+        self.growth_tangent_window = p.growth_tangent_window;
+        self.growth_inhibitors = p.growth_inhibitors.iter().flatten().copied().collect();
+        self.split_enabled = p.split_enabled;
+        self.split_length = p.split_length;
+        self.auto_retarget_on_split = p.auto_retarget_on_split;
+        self.split_refractory_generations = p.split_refractory_generations;
+        self.split_curvature_enabled = p.split_min_curvature.is_some();
+        if let Some(min) = p.split_min_curvature {
+            self.split_min_curvature = min;
+        }
+        self.merge_enabled = p.merge_length.is_some();
+        if let Some(length) = p.merge_length {
+            self.merge_length = length;
+        }
+        self.constraint_enabled = p.constraint_enabled;
+        self.constraint_shape = p.constraint_shape;
+        self.constraint_size = p.constraint_size;
+        self.constraint_strength = p.constraint_strength;
+        self.constraint_falloff = p.constraint_falloff;
+        self.constraint_ramp_generations = p.constraint_ramp_generations;
+        self.constraint_inverted = p.constraint_inverted;
+        self.constraint_center = p.constraint_center;
+        self.constraint_size_rate = p.constraint_size_rate;
+        self.constraint_center_velocity = p.constraint_center_velocity;
+        self.jitter_enabled = p.jitter_enabled;
+        self.jitter_strength = p.jitter_strength;
+        self.centering_strength = p.centering_strength;
+        self.centroid_kind = p.centroid_kind;
+        self.jitter_first = p.force_order[0] == ForceKind::Jitter;
+        self.convergence_threshold = p.convergence_threshold;
+    }
+
+    fn save_params(&mut self) {
+        let code = encode_params(&self.sim_params(), self.sim.seed());
+        self.params_file_status = match std::fs::write(&self.params_file, code) {
+            Ok(()) => format!("Saved params to {}", self.params_file),
+            Err(err) => format!("Cannot save: {err}"),
+        };
+    }
+
+    // Swap in saved parameters and seed without rebuilding, so the current polygon and
+    // generation carry on under them.
+    fn load_params(&mut self) {
+        let loaded = std::fs::read_to_string(&self.params_file)
+            .map_err(GrowthError::from)
+            .and_then(|code| decode_params(&code));
+        self.params_file_status = match loaded {
+            Ok((params, seed)) => {
+                self.apply_params(&params);
+                self.sim.reset_seed(seed);
+                format!("Loaded params from {}", self.params_file)
+            }
+            Err(err) => format!("Cannot load: {err}"),
+        };
     }
 
     fn sim_params(&self) -> SimParams {
+        let mut force_order = DEFAULT_FORCE_ORDER;
+        if self.jitter_first {
+            // Jitter is last by default; rotating moves it to the front.
+            force_order.rotate_right(1);
+        }
+        let mut growth_inhibitors = [None; MAX_GROWTH_INHIBITORS];
+        for (slot, inhibitor) in growth_inhibitors.iter_mut().zip(&self.growth_inhibitors) {
+            *slot = Some(*inhibitor);
+        }
         SimParams {
             edge_regularization_enabled: self.edge_regularization_enabled,
             target_edge_length: self.target_edge_length,
             edge_stiffness: self.edge_stiffness,
+            edge_stiffness_tension: self
+                .asymmetric_stiffness
+                .then_some(self.edge_stiffness_tension),
+            edge_stiffness_compression: self
+                .asymmetric_stiffness
+                .then_some(self.edge_stiffness_compression),
+            edge_dead_zone: self.edge_dead_zone,
             repulsion_enabled: self.repulsion_enabled,
             repulsion_radius: self.repulsion_radius,
             repulsion_strength: self.repulsion_strength,
+            repulsion_threads: self.repulsion_threads,
+            repulsion_max_neighbors: self.repulsion_max_neighbors,
+            repulsion_neighbor_exclusion: self.repulsion_neighbor_exclusion,
+            adaptive_repulsion: self.adaptive_repulsion,
+            adaptive_repulsion_max: self.adaptive_repulsion_max,
+            edge_repulsion_enabled: self.edge_repulsion_enabled,
+            edge_repulsion_strength: self.edge_repulsion_strength,
             growth_enabled: self.growth_enabled,
             growth_rate: self.growth_rate,
+            growth_area_goal: self.area_goal_enabled.then_some(self.growth_area_goal),
+            max_area: self.max_area_enabled.then_some(self.max_area),
+            min_area: self.min_area_enabled.then_some(self.min_area),
+            growth_erosion_min_edge: self
+                .erosion_guard_enabled
+                .then_some(self.growth_erosion_min_edge),
+            growth_conserve_perimeter: self.growth_conserve_perimeter,
+            growth_curvature_filter: self.growth_curvature_filter,
+            growth_density_gain: self.growth_density_gain,
+            growth_normal_smoothing: self.growth_normal_smoothing,
+            growth_tangent_window: self.growth_tangent_window,
+            growth_inhibitors,
             split_enabled: self.split_enabled,
             split_length: self.split_length,
+            auto_retarget_on_split: self.auto_retarget_on_split,
+            split_refractory_generations: self.split_refractory_generations,
+            split_min_curvature: self
+                .split_curvature_enabled
+                .then_some(self.split_min_curvature),
+            merge_length: self.merge_enabled.then_some(self.merge_length),
             constraint_enabled: self.constraint_enabled,
             constraint_shape: self.constraint_shape,
             constraint_size: self.constraint_size,
             constraint_strength: self.constraint_strength,
             constraint_falloff: self.constraint_falloff,
+            constraint_ramp_generations: self.constraint_ramp_generations,
+            constraint_inverted: self.constraint_inverted,
+            constraint_center: self.constraint_center,
+            constraint_size_rate: self.constraint_size_rate,
+            constraint_center_velocity: self.constraint_center_velocity,
             jitter_enabled: self.jitter_enabled,
             jitter_strength: self.jitter_strength,
+            attractor: self.mouse_attractor(),
+            centering_strength: self.centering_strength,
+            centroid_kind: self.centroid_kind,
+            force_order,
+            convergence_threshold: self.convergence_threshold,
         }
     }
 
-    fn input_in_progress(ctx: &egui::Context) -> bool {
-        // Treat slider drags and focused numeric text-edit fields as in-progress edits.
-        ctx.input(|i| i.pointer.any_down()) || ctx.memory(|m| m.focused().is_some())
+    fn mouse_attractor(&self) -> Option<Attractor> {
+        if !self.mouse_attract_enabled || self.dragged_vertex.is_some() {
+            return None;
+        }
+        // Follow the cursor only while it hovers the viewport.
+        self.mouse_world.map(|position| Attractor {
+            position,
+            radius: self.mouse_attract_radius,
+            strength: self.mouse_attract_strength,
+        })
     }
 
-    // Draw polygon in viewport with either fit or fixed zoom mapping.
-    fn draw_polygon(
-        ui: &mut egui::Ui,
-        rect: Rect,
-        polygon: &Polygon,
-        view_mode: ViewMode,
-        fixed_zoom: f64,
-        constraint_show: bool,
-        constraint_shape: ConstraintShape,
-        constraint_size: f64,
-    ) {
-        let painter = ui.painter_at(rect);
-        painter.rect_filled(rect, 0.0, Color32::from_gray(20));
+    // Advance one step and pick up any target edge length the sim re-derived.
+    fn step_sim(&mut self) -> StepReport {
+        if self.sim.polygon().len() < 3 {
+            // Nothing meaningful to step; forces on a point or segment only produce NaNs.
+            return StepReport::default();
+        }
+        self.sim.set_profiling(self.profile_steps);
+        if self.sim.integrator().name() != self.integrator.label() {
+            // Also catches checkpoints restored from before the last change.
+            self.sim.set_integrator(self.integrator.build());
+        }
+        let report = self.sim.step(self.sim_params());
+        if let Some(target) = self.sim.retargeted_edge_length() {
+            self.target_edge_length = target;
+        }
+        self.record_history();
+        report
+    }
 
-        if polygon.is_empty() {
+    // Copies of the live outlines with the chosen export reduction applied to each.
+    fn export_polygons(&self) -> Vec<Polygon> {
+        self.sim
+            .polygons()
+            .iter()
+            .map(|polygon| {
+                self.export_reduction.apply(
+                    polygon,
+                    self.export_simplify_tolerance,
+                    self.export_decimate_count,
+                )
+            })
+            .collect()
+    }
+
+    // Saves the outlines as shown: edge-length coloring carries over into the file.
+    fn save_svg(&mut self) {
+        let polygons = &self.export_polygons();
+        let document = match self.edge_coloring {
+            EdgeColoring::Solid => svg_document(polygons, 0.01),
+            EdgeColoring::Length => {
+                let lengths: Vec<Real> = polygons
+                    .iter()
+                    .flat_map(Polygon::edges)
+                    .map(|(a, b)| a.distance(b))
+                    .collect();
+                match svg_document_colored(polygons, &lengths, Colormap::BlueOrange, 0.01) {
+                    Ok(document) => document,
+                    Err(err) => {
+                        self.svg_status = format!("Cannot color edges: {err}");
+                        return;
+                    }
+                }
+            }
+        };
+        self.svg_status = match std::fs::write(&self.svg_file, document) {
+            Ok(()) => format!("Saved {}", self.svg_file),
+            Err(err) => format!("Cannot save: {err}"),
+        };
+    }
+
+    // Arrows from the previous history snapshot to the latest one.
+    fn save_diff_svg(&mut self) {
+        let mut recent = self.history.iter().rev();
+        let (Some(to), Some(from)) = (recent.next(), recent.next()) else {
+            self.svg_status = "Need two history snapshots to diff".to_owned();
+            return;
+        };
+        let document = displacement_svg_document(&from.polygons, &to.polygons, 0.01);
+        self.svg_status = match std::fs::write(&self.diff_file, document) {
+            Ok(()) => format!(
+                "Saved generations {}..{} to {}",
+                from.generation, to.generation, self.diff_file
+            ),
+            Err(err) => format!("Cannot save: {err}"),
+        };
+    }
+
+    // Install every path of an SVG file that parses, each as its own simulation polygon.
+    fn load_svg(&mut self) {
+        let text = match std::fs::read_to_string(&self.svg_file) {
+            Ok(text) => text,
+            Err(err) => {
+                self.svg_status = format!("Cannot read: {err}");
+                return;
+            }
+        };
+        let (polygons, skipped) = polygons_from_svg(&text);
+        if polygons.is_empty() {
+            self.svg_status = match skipped.first() {
+                Some((_, err)) => format!("Cannot parse path: {err}"),
+                None => "No <path> element found".to_owned(),
+            };
+            return;
+        }
+        let vertices: usize = polygons.iter().map(Polygon::len).sum();
+        let mut status = format!("Loaded {} paths, {vertices} vertices", polygons.len());
+        if let Some((index, err)) = skipped.first() {
+            let count = skipped.len();
+            status += &format!("; skipped {count} unparsable paths (path {index}: {err})");
+        }
+        self.svg_status = status;
+        self.sim.set_polygons(polygons);
+        self.reset_history();
+    }
+
+    // Install each contour of the glyph as its own polygon, centered on the origin.
+    #[cfg(feature = "text")]
+    fn load_glyph(&mut self) {
+        let Some(ch) = self.glyph_char.chars().next() else {
+            self.glyph_status = "Enter a character".to_owned();
+            return;
+        };
+        let font = match std::fs::read(&self.font_file) {
+            Ok(font) => font,
+            Err(err) => {
+                self.glyph_status = format!("Cannot read: {err}");
+                return;
+            }
+        };
+        let tolerance = self.glyph_size * GLYPH_TOLERANCE;
+        match dg4::text::glyph_polygons(&font, ch, self.glyph_size, tolerance) {
+            Ok(polygons) if polygons.is_empty() => {
+                self.glyph_status = format!("{ch:?} has no outline");
+            }
+            Ok(polygons) => {
+                let vertices: usize = polygons.iter().map(Polygon::len).sum();
+                let contours = polygons.len();
+                self.glyph_status = format!("Loaded {contours} contours, {vertices} vertices");
+                self.sim.set_polygons(polygons);
+                self.target_edge_length = dg4::sim::average_edge_length_of(self.sim.polygons());
+                self.reset_history();
+                self.force_preview = None;
+            }
+            Err(err) => self.glyph_status = format!("Cannot load glyph: {err}"),
+        }
+    }
+
+    fn save_lottie(&mut self) {
+        let style = LottieStyle {
+            stroke: self.theme.curve.to_array(),
+            ..LottieStyle::default()
+        };
+        let json = export_lottie(&self.history, &style);
+        self.lottie_status = match std::fs::write(&self.lottie_file, json) {
+            Ok(()) => format!("Saved {} keyframes to {}", self.history.len(), self.lottie_file),
+            Err(err) => format!("Cannot save: {err}"),
+        };
+    }
+
+    fn save_svg_frames(&mut self) {
+        let frames: Vec<&[Polygon]> = self.history.iter().map(|s| &s.polygons[..]).collect();
+        self.svg_frames_status =
+            match render_svg_frames(&frames, std::path::Path::new(&self.svg_frames_dir)) {
+                Ok(paths) => format!("Saved {} frames to {}", paths.len(), self.svg_frames_dir),
+                Err(err) => format!("Cannot save: {err}"),
+            };
+    }
+
+    fn save_trail_svg(&mut self) {
+        let document = history_trail_svg(&self.history);
+        self.svg_frames_status = match std::fs::write(&self.trail_svg_file, document) {
+            Ok(()) => format!("Saved {} snapshots to {}", self.history.len(), self.trail_svg_file),
+            Err(err) => format!("Cannot save: {err}"),
+        };
+    }
+
+    fn save_param_log(&mut self) {
+        let json = self.param_log.to_json();
+        self.param_log_status = match std::fs::write(&self.param_log_file, json) {
+            Ok(()) => format!(
+                "Saved {} changes to {}",
+                self.param_log.entries().len(),
+                self.param_log_file
+            ),
+            Err(err) => format!("Cannot save: {err}"),
+        };
+    }
+
+    fn start_recording(&mut self) {
+        match FrameRecorder::new(&self.record_dir) {
+            Ok(recorder) => {
+                self.recorder = Some(recorder);
+                self.record_last_generation = None;
+                self.record_status.clear();
+            }
+            Err(err) => self.record_status = format!("Cannot record: {err}"),
+        }
+    }
+
+    fn stop_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            self.record_status = format!(
+                "Wrote {} frames to {}",
+                recorder.frames_written(),
+                recorder.dir().display()
+            );
+        }
+    }
+
+    // Write one PNG per new generation while recording.
+    fn capture_frame(&mut self) {
+        let generation = self.sim.generation();
+        if self.record_last_generation == Some(generation) {
+            return;
+        }
+        let Some(recorder) = self.recorder.as_mut() else {
             return;
+        };
+
+        let style = RenderStyle {
+            background: self.theme.background.to_array(),
+            curve: self.theme.curve.to_array(),
+            closed: self.draw_closed,
+            line_width: self.line_width,
+            fill: self
+                .fill_interior
+                .then(|| interior_color(self.theme.curve).to_srgba_unmultiplied()),
+        };
+        let size = RECORD_FRAME_SIZE;
+        let image = render_polygons(self.sim.polygons(), size, size, &style);
+        match recorder.write_frame(&image) {
+            Ok(_) => self.record_last_generation = Some(generation),
+            Err(err) => {
+                self.record_status = format!("Recording stopped: {err}");
+                self.recorder = None;
+            }
         }
+    }
 
-        let (min, max) = bounds(polygon.vertices()).unwrap_or((Vec2::ZERO, Vec2::ZERO));
+    fn input_in_progress(ctx: &egui::Context) -> bool {
+        // Treat slider drags and focused numeric text-edit fields as in-progress edits.
+        ctx.input(|i| i.pointer.any_down()) || ctx.memory(|m| m.focused().is_some())
+    }
+
+    // Build this frame's world/screen mapping with either fit or fixed zoom.
+    fn view_transform(&self, rect: Rect) -> ViewTransform {
+        let region = self.sim.constraint_region(&self.sim_params());
+        let constraint = (self.view_mode == ViewMode::Fit
+            && self.fit_constraint
+            && self.constraint_show
+            && region.1 > 0.0)
+            .then_some(region);
+        let (min, max) = self
+            .sim
+            .polygons()
+            .iter()
+            .filter_map(|polygon| framing_bounds(polygon, constraint))
+            .reduce(|(min, max), (p_min, p_max)| (min.min(p_min), max.max(p_max)))
+            .unwrap_or((Vec2::ZERO, Vec2::ZERO));
         let center = (min + max) * 0.5;
         let width = (max.x - min.x).max(1e-6);
         let height = (max.y - min.y).max(1e-6);
 
-        let scale = match view_mode {
+        let scale = match self.view_mode {
             ViewMode::Fit => {
                 // Preserve aspect ratio while fitting bounds with a small margin.
-                let scale_x = rect.width() as f64 / width;
-                let scale_y = rect.height() as f64 / height;
+                let scale_x = rect.width() as Real / width;
+                let scale_y = rect.height() as Real / height;
                 scale_x.min(scale_y) * 0.9
             }
-            ViewMode::FixedZoom => fixed_zoom.max(1.0),
+            ViewMode::FixedZoom => self.zoom_px_per_unit.max(1.0),
         };
 
-        // World-to-screen transform centered on polygon bounds.
-        let to_screen = |p: Vec2| -> Pos2 {
-            let local = (p - center) * scale;
-            Pos2::new(
-                rect.center().x + local.x as f32,
-                rect.center().y - local.y as f32,
-            )
-        };
+        ViewTransform {
+            screen_center: rect.center(),
+            center,
+            scale,
+            pan: self.pan,
+        }
+    }
+
+    // Occupied cells shaded by vertex count, under grid lines when cells are large enough
+    // on screen to tell apart.
+    fn draw_spatial_hash(&self, painter: &egui::Painter, rect: Rect, view: ViewTransform) {
+        let vertices: Vec<Vec2> =
+            self.sim.polygons().iter().flat_map(|p| p.vertices()).copied().collect();
+        let hash = SpatialHash::build(&vertices, self.repulsion_radius);
+        let size = hash.cell_size();
+        let cells = hash.occupied_cells();
+        let max_count = cells.iter().map(|&(_, count)| count).max().unwrap_or(1);
+        for (cell, count) in cells {
+            let (min, max) = hash.cell_bounds(cell);
+            let alpha = 20 + (80 * count / max_count) as u8;
+            let color = Color32::from_rgba_unmultiplied(230, 160, 60, alpha);
+            let screen = Rect::from_two_pos(view.to_screen(min), view.to_screen(max));
+            painter.rect_filled(screen, 0.0, color);
+        }
+
+        if size * view.scale < 6.0 {
+            return;
+        }
+        let stroke = Stroke::new(1.0, Color32::from_rgba_unmultiplied(230, 160, 60, 40));
+        let (a, b) = (view.to_world(rect.left_top()), view.to_world(rect.right_bottom()));
+        let (lo, hi) = (a.min(b), a.max(b));
+        let mut x = (lo.x / size).floor() * size;
+        while x <= hi.x {
+            let sx = view.to_screen(Vec2::new(x, 0.0)).x;
+            painter.vline(sx, rect.y_range(), stroke);
+            x += size;
+        }
+        let mut y = (lo.y / size).floor() * size;
+        while y <= hi.y {
+            let sy = view.to_screen(Vec2::new(0.0, y)).y;
+            painter.hline(rect.x_range(), sy, stroke);
+            y += size;
+        }
+    }
+
+    // Draw polygon in viewport using the frame's view transform.
+    fn draw_polygon(&self, ui: &mut egui::Ui, response: &egui::Response, view: ViewTransform) {
+        let rect = response.rect;
+        let (polygons, polygon) = (self.sim.polygons(), self.sim.polygon());
+        let painter = ui.painter_at(rect);
+        let theme = self.theme;
+        painter.rect_filled(rect, 0.0, theme.background);
+
+        if self.sim.vertex_count() == 0 {
+            return;
+        }
+        // Vertices of every polygon in order, as the per-vertex simulation state runs.
+        let vertices = || polygons.iter().flat_map(|p| p.vertices());
+
+        let scale = view.scale;
+        let to_screen = |p: Vec2| view.to_screen(p);
 
-        if constraint_show && constraint_size > 0.0 {
-            let fill = Color32::from_rgba_premultiplied(90, 120, 140, 28);
-            let stroke = Stroke::new(1.0, Color32::from_rgba_premultiplied(120, 160, 180, 80));
-            let constraint_center = to_screen(Vec2::ZERO);
+        // The area as the next step applies it, after any animation.
+        let (constraint_shape, constraint_size, center) =
+            self.sim.constraint_region(&self.sim_params());
+        if self.constraint_show && constraint_size > 0.0 {
+            let fill = theme.constraint_fill;
+            let stroke = Stroke::new(1.0, theme.constraint_stroke);
+            let constraint_center = to_screen(center);
+            let to_screen = |p: Vec2| to_screen(p + center);
 
             match constraint_shape {
                 ConstraintShape::Circle => {
@@ -206,12 +1180,19 @@ impl DgApp {
                     painter.circle_filled(constraint_center, radius, fill);
                     painter.circle_stroke(constraint_center, radius, stroke);
                 }
-                ConstraintShape::Square => {
+                ConstraintShape::Square | ConstraintShape::Rectangle { .. } => {
+                    let (hw, hh) = match constraint_shape {
+                        ConstraintShape::Rectangle {
+                            half_width,
+                            half_height,
+                        } => (half_width, half_height),
+                        _ => (constraint_size, constraint_size),
+                    };
                     let vertices = [
-                        Vec2::new(-constraint_size, -constraint_size),
-                        Vec2::new(constraint_size, -constraint_size),
-                        Vec2::new(constraint_size, constraint_size),
-                        Vec2::new(-constraint_size, constraint_size),
+                        Vec2::new(-hw, -hh),
+                        Vec2::new(hw, -hh),
+                        Vec2::new(hw, hh),
+                        Vec2::new(-hw, hh),
                     ];
                     let mut points: Vec<Pos2> = vertices.iter().copied().map(to_screen).collect();
                     painter.add(Shape::convex_polygon(points.clone(), fill, Stroke::NONE));
@@ -219,25 +1200,165 @@ impl DgApp {
                     painter.add(Shape::line(points, stroke));
                 }
                 ConstraintShape::Triangle => {
-                    let vertices = [
-                        Vec2::new(0.0, constraint_size),
-                        Vec2::new(-0.866_025_403_784, -0.5) * constraint_size,
-                        Vec2::new(0.866_025_403_784, -0.5) * constraint_size,
-                    ];
+                    let vertices = triangle_vertices(constraint_size);
                     let points: Vec<Pos2> = vertices.iter().copied().map(to_screen).collect();
                     painter.add(Shape::convex_polygon(points.clone(), fill, stroke));
                 }
             }
         }
 
-        let mut points: Vec<Pos2> = polygon.vertices().iter().copied().map(to_screen).collect();
-        if points.len() > 1 {
-            points.push(points[0]);
-            painter.add(Shape::line(points, Stroke::new(2.0, Color32::LIGHT_GREEN)));
+        if self.show_spatial_hash && self.repulsion_radius > 0.0 {
+            self.draw_spatial_hash(&painter, rect, view);
+        }
+
+        if self.fill_interior {
+            let mut mesh = egui::Mesh::default();
+            let color = interior_color(theme.curve);
+            for triangle in polygons.iter().flat_map(Polygon::triangulate) {
+                let base = mesh.vertices.len() as u32;
+                for v in triangle {
+                    mesh.colored_vertex(to_screen(v), color);
+                }
+                mesh.add_triangle(base, base + 1, base + 2);
+            }
+            painter.add(Shape::mesh(mesh));
+        }
+
+        let lengths: Vec<Vec<Real>> = match self.edge_coloring {
+            EdgeColoring::Solid => Vec::new(),
+            EdgeColoring::Length => polygons
+                .iter()
+                .map(|p| p.edges().map(|(a, b)| a.distance(b)).collect())
+                .collect(),
+        };
+        // One color scale across all polygons.
+        let all_lengths = || lengths.iter().flatten().copied();
+        let min = all_lengths().fold(Real::INFINITY, Real::min);
+        let max = all_lengths().fold(Real::NEG_INFINITY, Real::max);
+        let mut length_range = None;
+        for (k, polygon) in polygons.iter().enumerate() {
+            let mut points: Vec<Pos2> = polygon.vertices().iter().copied().map(to_screen).collect();
+            if points.len() <= 1 {
+                continue;
+            }
+            if self.draw_closed {
+                points.push(points[0]);
+            }
+            match self.edge_coloring {
+                EdgeColoring::Solid => {
+                    let stroke = Stroke::new(self.line_width, theme.curve);
+                    round_joins(&painter, &points, stroke);
+                    painter.add(Shape::line(points, stroke));
+                }
+                EdgeColoring::Length => {
+                    let span = (max - min).max(1e-12);
+                    for (segment, len) in points.windows(2).zip(&lengths[k]) {
+                        let t = ((len - min) / span) as f32;
+                        let stroke = Stroke::new(self.line_width, gradient_color(t));
+                        round_joins(&painter, segment, stroke);
+                        painter.line_segment([segment[0], segment[1]], stroke);
+                    }
+                    length_range = Some((min, max));
+                }
+            }
+        }
+
+        if self.show_vertices && self.sim.vertex_count() <= self.vertex_dot_limit {
+            let ids = self.sim.vertex_ids();
+            for (i, v) in vertices().enumerate() {
+                let color = match ids.get(i) {
+                    Some(&id) if self.color_vertices_by_id => id_color(id),
+                    _ => theme.vertex,
+                };
+                let (marker, size) = (self.vertex_marker, self.vertex_marker_size);
+                draw_vertex_marker(&painter, marker, to_screen(*v), size, color);
+            }
+        }
+
+        if let Some((generation, delta)) = &self.force_preview
+            && *generation == self.sim.generation()
+            && delta.len() == self.sim.vertex_count()
+        {
+            let stroke = Stroke::new(1.5, Color32::from_rgb(240, 200, 80));
+            for (v, d) in vertices().zip(delta) {
+                let arrow = view.to_screen(*v + *d * self.force_preview_scale as Real) - to_screen(*v);
+                painter.arrow(to_screen(*v), arrow, stroke);
+            }
+        }
+
+        if self.show_growth_front {
+            let growth = self.sim.growth_delta(self.sim_params());
+            let stroke = Stroke::new(1.5, Color32::from_rgb(80, 200, 240));
+            for (v, d) in vertices().zip(&growth) {
+                if *d != Vec2::ZERO {
+                    let tip = view.to_screen(*v + *d * self.force_preview_scale as Real);
+                    painter.arrow(to_screen(*v), tip - to_screen(*v), stroke);
+                }
+            }
+        }
+
+        if self.show_winding && !polygon.is_empty() {
+            // Mark vertex 0 and point an arrow along the first edge to show traversal order.
+            let first = to_screen(polygon.vertices()[0]);
+            painter.circle_filled(first, 5.0, Color32::from_rgb(240, 90, 90));
+            if polygon.len() > 1 {
+                let second = to_screen(polygon.vertices()[1]);
+                let stroke = Stroke::new(2.0, Color32::from_rgb(240, 90, 90));
+                painter.arrow(first, (second - first) * 0.5, stroke);
+            }
+        }
+
+        if self.repulsion_enabled
+            && self.repulsion_radius > 0.0
+            && let Some(hover) = response.hover_pos()
+            && let Some(index) = pick_vertex(polygon, view, hover)
+        {
+            let radius = (self.repulsion_radius * scale) as f32;
+            let stroke = Stroke::new(1.0, Color32::from_rgba_premultiplied(200, 120, 120, 120));
+            painter.circle_stroke(to_screen(polygon.vertices()[index]), radius, stroke);
+        }
+
+        let stroke = Stroke::new(1.0, Color32::from_rgba_premultiplied(120, 200, 160, 100));
+        for inhibitor in &self.growth_inhibitors {
+            let radius = (inhibitor.radius * scale) as f32;
+            painter.circle_stroke(to_screen(inhibitor.position), radius, stroke);
         }
 
-        for v in polygon.vertices() {
-            painter.circle_filled(to_screen(*v), 3.0, Color32::from_rgb(250, 220, 130));
+        if let Some(attractor) = self.mouse_attractor() {
+            let radius = (attractor.radius * scale) as f32;
+            let stroke = Stroke::new(1.0, Color32::from_rgba_premultiplied(140, 140, 220, 100));
+            painter.circle_stroke(to_screen(attractor.position), radius, stroke);
+        }
+
+        if self.measure_mode {
+            // With one endpoint placed, follow the cursor until the second click.
+            let segment = match *self.measure_points.as_slice() {
+                [start] => self.mouse_world.map(|end| (start, end)),
+                [start, end] => Some((start, end)),
+                _ => None,
+            };
+            let color = Color32::from_rgb(240, 220, 120);
+            for &p in &self.measure_points {
+                painter.circle_filled(to_screen(p), 3.0, color);
+            }
+            if let Some((start, end)) = segment {
+                let (a, b) = (to_screen(start), to_screen(end));
+                painter.line_segment([a, b], Stroke::new(1.5, color));
+                painter.text(
+                    a + (b - a) * 0.5 + egui::vec2(6.0, -6.0),
+                    egui::Align2::LEFT_BOTTOM,
+                    format!("{:.4}", start.distance(end)),
+                    egui::FontId::proportional(12.0),
+                    color,
+                );
+            }
+        }
+
+        if let Some((min, max)) = length_range {
+            draw_color_legend(&painter, rect, min, max);
+        }
+        if self.show_scale_bar {
+            draw_scale_bar(&painter, rect, scale);
         }
     }
 }
@@ -258,12 +1379,52 @@ impl eframe::App for DgApp {
 
                     left.heading("Starter Polygon");
                     left.separator();
+                    let previous_shape = self.starter_shape;
+                    egui::ComboBox::from_label("Shape")
+                        .selected_text(self.starter_shape.label())
+                        .show_ui(left, |ui| {
+                            for shape in StarterShape::ALL {
+                                ui.selectable_value(&mut self.starter_shape, shape, shape.label());
+                            }
+                        });
+                    changed |= self.starter_shape != previous_shape;
                     changed |= left
                         .add(egui::Slider::new(&mut self.radius, 0.05..=10.0).text("Radius"))
                         .changed();
                     changed |= left
                         .add(egui::Slider::new(&mut self.sides, 3..=512).text("Sides"))
                         .changed();
+                    match self.starter_shape {
+                        StarterShape::Ngon | StarterShape::SymmetricNgon => {}
+                        StarterShape::Star => {
+                            changed |= left
+                                .add(
+                                    egui::Slider::new(&mut self.star_inner_ratio, 0.05..=1.0)
+                                        .text("Inner Ratio"),
+                                )
+                                .changed();
+                        }
+                        StarterShape::RoundedRect => {
+                            changed |= left
+                                .add(egui::Slider::new(&mut self.rect_aspect, 0.1..=1.0).text("Aspect"))
+                                .changed();
+                            changed |= left
+                                .add(
+                                    egui::Slider::new(&mut self.corner_ratio, 0.0..=1.0)
+                                        .text("Corner Radius"),
+                                )
+                                .changed();
+                        }
+                    }
+                    changed |= left
+                        .add(egui::Slider::new(&mut self.starter_center.x, -5.0..=5.0).text("Center X"))
+                        .changed();
+                    changed |= left
+                        .add(egui::Slider::new(&mut self.starter_center.y, -5.0..=5.0).text("Center Y"))
+                        .changed();
+                    changed |= left
+                        .add(egui::Slider::new(&mut self.perturb_magnitude, 0.0..=0.2).text("Perturb Start"))
+                        .changed();
 
                     left.separator();
                     left.heading("View");
@@ -274,26 +1435,290 @@ impl eframe::App for DgApp {
                             ui.selectable_value(&mut self.view_mode, ViewMode::Fit, "Fit");
                             ui.selectable_value(&mut self.view_mode, ViewMode::FixedZoom, "Fixed Zoom");
                         });
+                    if self.view_mode == ViewMode::Fit {
+                        left.checkbox(&mut self.fit_constraint, "Fit Constraint Area")
+                            .on_hover_text("Include the constraint overlay in the fitted bounds.");
+                    }
                     if self.view_mode == ViewMode::FixedZoom {
                         left.add(
                             egui::Slider::new(&mut self.zoom_px_per_unit, 10.0..=400.0)
                                 .text("Zoom (px/unit)"),
                         );
                     }
+                    left.checkbox(&mut self.draw_closed, "Draw Closed");
+                    left.add(egui::Slider::new(&mut self.line_width, 0.5..=8.0).text("Line Width"));
+                    left.checkbox(&mut self.fill_interior, "Fill Interior")
+                        .on_hover_text(
+                            "Shade the enclosed area. Self-intersecting outlines stay unfilled.",
+                        );
+                    left.checkbox(&mut self.show_winding, "Show First Vertex / Winding");
+                    left.checkbox(&mut self.show_growth_front, "Show Growth Front")
+                        .on_hover_text(
+                            "Arrows of the growth each vertex gets next step, scaled by the \
+                             Arrow Scale slider.",
+                        );
+                    left.checkbox(&mut self.show_spatial_hash, "Show Spatial Hash")
+                        .on_hover_text(
+                            "Grid used to find repulsion neighbors, one cell per Repulsion \
+                             Radius. Brighter cells hold more vertices.",
+                        );
+                    if left
+                        .checkbox(&mut self.measure_mode, "Measure")
+                        .on_hover_text(
+                            "Click two points in the view to show their distance in world \
+                             units; a third click starts over.",
+                        )
+                        .changed()
+                    {
+                        self.measure_points.clear();
+                    }
+                    egui::ComboBox::from_label("Edge Color")
+                        .selected_text(self.edge_coloring.label())
+                        .show_ui(left, |ui| {
+                            for coloring in [EdgeColoring::Solid, EdgeColoring::Length] {
+                                ui.selectable_value(&mut self.edge_coloring, coloring, coloring.label());
+                            }
+                        });
+                    left.checkbox(&mut self.show_scale_bar, "Show Scale Bar");
+                    left.checkbox(&mut self.show_vertices, "Show Vertices");
+                    left.checkbox(&mut self.color_vertices_by_id, "Color Vertices By Id");
+                    egui::ComboBox::from_label("Marker")
+                        .selected_text(self.vertex_marker.label())
+                        .show_ui(left, |ui| {
+                            for marker in VertexMarker::ALL {
+                                ui.selectable_value(&mut self.vertex_marker, marker, marker.label());
+                            }
+                        });
+                    left.add(
+                        egui::Slider::new(&mut self.vertex_marker_size, 0.5..=12.0)
+                            .text("Marker Size"),
+                    );
+                    left.add(
+                        egui::Slider::new(&mut self.vertex_dot_limit, 100..=20000)
+                            .logarithmic(true)
+                            .text("Hide Dots Above"),
+                    );
+                    egui::ComboBox::from_label("Theme")
+                        .selected_text(self.theme.label())
+                        .show_ui(left, |ui| {
+                            for (name, theme) in Theme::PRESETS {
+                                ui.selectable_value(&mut self.theme, theme, name);
+                            }
+                        });
+
+                    left.separator();
+                    left.heading("SVG");
+                    left.separator();
+                    left.horizontal(|ui| {
+                        ui.label("File");
+                        ui.text_edit_singleline(&mut self.svg_file);
+                    });
+                    egui::ComboBox::from_label("Export")
+                        .selected_text(self.export_reduction.label())
+                        .show_ui(left, |ui| {
+                            for reduction in ExportReduction::ALL {
+                                ui.selectable_value(
+                                    &mut self.export_reduction,
+                                    reduction,
+                                    reduction.label(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("Applied to a copy on save; the simulation is untouched.");
+                    match self.export_reduction {
+                        ExportReduction::Simplify => {
+                            left.add(
+                                egui::Slider::new(&mut self.export_simplify_tolerance, 1e-4..=0.1)
+                                    .logarithmic(true)
+                                    .text("Tolerance"),
+                            )
+                            .on_hover_text("Farthest a dropped vertex may lie from the outline.");
+                        }
+                        ExportReduction::Decimate => {
+                            left.add(
+                                egui::Slider::new(&mut self.export_decimate_count, 3..=2000)
+                                    .logarithmic(true)
+                                    .text("Vertex Count"),
+                            );
+                        }
+                        ExportReduction::None | ExportReduction::ConvexHull => {}
+                    }
+                    left.horizontal(|ui| {
+                        if ui.button("Save SVG").clicked() {
+                            self.save_svg();
+                        }
+                        if ui.button("Load SVG").clicked() {
+                            self.load_svg();
+                        }
+                    });
+                    left.horizontal(|ui| {
+                        ui.label("Diff File");
+                        ui.text_edit_singleline(&mut self.diff_file);
+                    });
+                    if left
+                        .button("Save Diff SVG")
+                        .on_hover_text("Displacement arrows between the two latest history snapshots.")
+                        .clicked()
+                    {
+                        self.save_diff_svg();
+                    }
+                    if !self.svg_status.is_empty() {
+                        left.label(&self.svg_status);
+                    }
+
+                    #[cfg(feature = "text")]
+                    {
+                        left.separator();
+                        left.heading("Glyph");
+                        left.separator();
+                        left.horizontal(|ui| {
+                            ui.label("Font File");
+                            ui.text_edit_singleline(&mut self.font_file);
+                        });
+                        left.horizontal(|ui| {
+                            ui.label("Character");
+                            ui.add(egui::TextEdit::singleline(&mut self.glyph_char).char_limit(1));
+                        });
+                        left.add(egui::Slider::new(&mut self.glyph_size, 0.1..=5.0).text("Size"));
+                        if left
+                            .button("Load Glyph")
+                            .on_hover_text("One polygon per contour, holes included.")
+                            .clicked()
+                        {
+                            self.load_glyph();
+                        }
+                        if !self.glyph_status.is_empty() {
+                            left.label(&self.glyph_status);
+                        }
+                    }
+
+                    left.separator();
+                    left.heading("Record");
+                    left.separator();
+                    left.horizontal(|ui| {
+                        ui.label("Directory");
+                        ui.text_edit_singleline(&mut self.record_dir);
+                    });
+                    let mut recording = self.recorder.is_some();
+                    if left
+                        .checkbox(&mut recording, "Record Frames")
+                        .on_hover_text(
+                            "Writes frame_00000.png, frame_00001.png, ... once per generation.\n\
+                             Assemble with: ffmpeg -framerate 30 -i frame_%05d.png -pix_fmt yuv420p growth.mp4",
+                        )
+                        .changed()
+                    {
+                        if recording {
+                            self.start_recording();
+                        } else {
+                            self.stop_recording();
+                        }
+                    }
+                    if let Some(recorder) = &self.recorder {
+                        left.label(format!("Frames: {}", recorder.frames_written()));
+                    }
+                    if !self.record_status.is_empty() {
+                        left.label(&self.record_status);
+                    }
+                    left.horizontal(|ui| {
+                        ui.label("Lottie File");
+                        ui.text_edit_singleline(&mut self.lottie_file);
+                    });
+                    if left
+                        .button("Export Lottie")
+                        .on_hover_text(format!(
+                            "Vector animation of the last {} snapshots, one every {} generations.",
+                            self.history.capacity(),
+                            self.history.interval()
+                        ))
+                        .clicked()
+                    {
+                        self.save_lottie();
+                    }
+                    if !self.lottie_status.is_empty() {
+                        left.label(&self.lottie_status);
+                    }
+                    left.horizontal(|ui| {
+                        ui.label("SVG Frames Dir");
+                        ui.text_edit_singleline(&mut self.svg_frames_dir);
+                    });
+                    if left
+                        .button("Export SVG Frames")
+                        .on_hover_text("One SVG per history snapshot, all framed alike.")
+                        .clicked()
+                    {
+                        self.save_svg_frames();
+                    }
+                    left.horizontal(|ui| {
+                        ui.label("Trail SVG File");
+                        ui.text_edit_singleline(&mut self.trail_svg_file);
+                    });
+                    if left
+                        .button("Export Trail SVG")
+                        .on_hover_text("Every history snapshot in one SVG, older ones fainter.")
+                        .clicked()
+                    {
+                        self.save_trail_svg();
+                    }
+                    if !self.svg_frames_status.is_empty() {
+                        left.label(&self.svg_frames_status);
+                    }
+                    left.checkbox(&mut self.param_log_enabled, "Log Parameter Changes")
+                        .on_hover_text("Records generation, parameter, old and new value per edit.");
+                    left.horizontal(|ui| {
+                        ui.label("Log File");
+                        ui.text_edit_singleline(&mut self.param_log_file);
+                    });
+                    left.horizontal(|ui| {
+                        if ui.button("Save Log").clicked() {
+                            self.save_param_log();
+                        }
+                        if ui.button("Clear Log").clicked() {
+                            self.param_log.clear();
+                            self.param_log_status.clear();
+                        }
+                        ui.label(format!("{} entries", self.param_log.entries().len()));
+                    });
+                    if !self.param_log_status.is_empty() {
+                        left.label(&self.param_log_status);
+                    }
 
                     left.separator();
                     left.heading("Spacing");
                     left.separator();
-                    left.checkbox(&mut self.edge_regularization_enabled, "Edge Regularization");
+                    left
+                        .checkbox(&mut self.edge_regularization_enabled, "Edge Regularization")
+                        .on_hover_text(param_info::tooltip("edge_regularization_enabled"));
                     left.add(
                         egui::Slider::new(&mut self.target_edge_length, 0.0001..=2.0)
                             .logarithmic(true)
                             .text("Target Edge Length"),
-                    );
+                    )
+                    .on_hover_text(param_info::tooltip("target_edge_length"));
                     left.add(
                         egui::Slider::new(&mut self.edge_stiffness, 0.0..=1.0)
                             .text("Edge Stiffness"),
-                    );
+                    )
+                    .on_hover_text(param_info::tooltip("edge_stiffness"));
+                    left.checkbox(&mut self.asymmetric_stiffness, "Separate Tension/Compression");
+                    if self.asymmetric_stiffness {
+                        left.add(
+                            egui::Slider::new(&mut self.edge_stiffness_tension, 0.0..=1.0)
+                                .text("Tension Stiffness"),
+                        )
+                        .on_hover_text(param_info::tooltip("edge_stiffness_tension"));
+                        left.add(
+                            egui::Slider::new(&mut self.edge_stiffness_compression, 0.0..=1.0)
+                                .text("Compression Stiffness"),
+                        )
+                        .on_hover_text(param_info::tooltip("edge_stiffness_compression"));
+                    }
+                    left.add(
+                        egui::Slider::new(&mut self.edge_dead_zone, 0.0..=0.05)
+                            .text("Edge Dead Zone"),
+                    )
+                    .on_hover_text(param_info::tooltip("edge_dead_zone"));
                     if left.button("Set Target From Current Shape").clicked() {
                         // Re-anchor target edge length to current geometry.
                         self.target_edge_length = average_edge_length(self.sim.polygon());
@@ -302,44 +1727,252 @@ impl eframe::App for DgApp {
                     left.separator();
                     left.heading("Repulsion");
                     left.separator();
-                    left.checkbox(&mut self.repulsion_enabled, "Self Repulsion");
+                    left
+                        .checkbox(&mut self.repulsion_enabled, "Self Repulsion")
+                        .on_hover_text(param_info::tooltip("repulsion_enabled"));
                     left.add(
                         egui::Slider::new(&mut self.repulsion_radius, 0.0001..=2.0)
                             .logarithmic(true)
                             .text("Repulsion Radius"),
-                    );
+                    )
+                    .on_hover_text(param_info::tooltip("repulsion_radius"));
                     left.add(
                         egui::Slider::new(&mut self.repulsion_strength, 0.0..=0.1)
                             .text("Repulsion Strength"),
-                    );
+                    )
+                    .on_hover_text(param_info::tooltip("repulsion_strength"));
+                    left.add(
+                        egui::Slider::new(&mut self.repulsion_threads, 1..=16)
+                            .text("Repulsion Threads"),
+                    )
+                    .on_hover_text(param_info::tooltip("repulsion_threads"));
+                    #[cfg(feature = "gpu")]
+                    {
+                        let mut gpu = self.sim.gpu_repulsion();
+                        left.checkbox(&mut gpu, "GPU Repulsion").on_hover_text(
+                            "Run vertex repulsion in a compute shader. Falls back to the CPU \
+                             without a GPU or with a neighbor cap.",
+                        );
+                        self.sim.set_gpu_repulsion(gpu);
+                        if gpu && dg4::gpu::shared().is_none() {
+                            left.label("No GPU adapter found; repulsion runs on the CPU");
+                        }
+                    }
+                    left.add(
+                        egui::Slider::new(&mut self.repulsion_max_neighbors, 0..=64)
+                            .text("Max Repulsion Neighbors"),
+                    )
+                    .on_hover_text(param_info::tooltip("repulsion_max_neighbors"));
+                    left.add(
+                        egui::Slider::new(&mut self.repulsion_neighbor_exclusion, 0..=8)
+                            .text("Repulsion Exclusion"),
+                    )
+                    .on_hover_text(param_info::tooltip("repulsion_neighbor_exclusion"));
+                    left
+                        .checkbox(&mut self.adaptive_repulsion, "Adaptive Repulsion")
+                        .on_hover_text(param_info::tooltip("adaptive_repulsion"));
+                    left.add(
+                        egui::Slider::new(&mut self.adaptive_repulsion_max, 1.0..=16.0)
+                            .text("Max Repulsion Boost"),
+                    )
+                    .on_hover_text(param_info::tooltip("adaptive_repulsion_max"));
+                    left
+                        .checkbox(&mut self.edge_repulsion_enabled, "Edge Repulsion")
+                        .on_hover_text(param_info::tooltip("edge_repulsion_enabled"));
+                    left.add(
+                        egui::Slider::new(&mut self.edge_repulsion_strength, 0.0..=0.1)
+                            .text("Edge Repulsion Strength"),
+                    )
+                    .on_hover_text(param_info::tooltip("edge_repulsion_strength"));
 
                     right.heading("Growth");
                     right.separator();
-                    right.checkbox(&mut self.growth_enabled, "Normal Growth");
+                    right
+                        .checkbox(&mut self.growth_enabled, "Normal Growth")
+                        .on_hover_text(param_info::tooltip("growth_enabled"));
                     right.add(
                         egui::Slider::new(&mut self.growth_rate, -0.01..=0.01)
                             .text("Growth Rate"),
+                    )
+                    .on_hover_text(param_info::tooltip("growth_rate"));
+                    right
+                        .checkbox(&mut self.area_goal_enabled, "Grow To Area")
+                        .on_hover_text(param_info::tooltip("growth_area_goal"));
+                    if self.area_goal_enabled {
+                        right.add(
+                            egui::Slider::new(&mut self.growth_area_goal, 0.1..=100.0)
+                                .logarithmic(true)
+                                .text("Area Goal"),
+                        )
+                        .on_hover_text(param_info::tooltip("growth_area_goal"));
+                    }
+                    right
+                        .checkbox(&mut self.max_area_enabled, "Stop At Area")
+                        .on_hover_text(param_info::tooltip("max_area"));
+                    if self.max_area_enabled {
+                        right.add(
+                            egui::Slider::new(&mut self.max_area, 0.1..=100.0)
+                                .logarithmic(true)
+                                .text("Max Area"),
+                        )
+                        .on_hover_text(param_info::tooltip("max_area"));
+                        if self.sim.area_capped(&self.sim_params()) {
+                            right.label("Area cap reached: growth and splitting paused");
+                        }
+                    }
+                    right
+                        .checkbox(&mut self.min_area_enabled, "Keep Min Area")
+                        .on_hover_text(param_info::tooltip("min_area"));
+                    if self.min_area_enabled {
+                        right.add(
+                            egui::Slider::new(&mut self.min_area, 0.01..=10.0)
+                                .logarithmic(true)
+                                .text("Min Area"),
+                        )
+                        .on_hover_text(param_info::tooltip("min_area"));
+                    }
+                    right
+                        .checkbox(&mut self.erosion_guard_enabled, "Guard Erosion")
+                        .on_hover_text(param_info::tooltip("growth_erosion_min_edge"));
+                    if self.erosion_guard_enabled {
+                        right.add(
+                            egui::Slider::new(&mut self.growth_erosion_min_edge, 0.001..=0.2)
+                                .logarithmic(true)
+                                .text("Min Edge"),
+                        )
+                        .on_hover_text(param_info::tooltip("growth_erosion_min_edge"));
+                    }
+                    right
+                        .checkbox(&mut self.growth_conserve_perimeter, "Conserve Perimeter")
+                        .on_hover_text(param_info::tooltip("growth_conserve_perimeter"));
+                    let mut inverted = !self.sim.growth_outward();
+                    right
+                        .checkbox(&mut inverted, "Invert Growth Direction")
+                        .on_hover_text(
+                            "Grow against the normals the winding implies, for loaded shapes \
+                             that grow inward.",
+                        );
+                    self.sim.set_growth_outward(!inverted);
+                    right.add(
+                        egui::Slider::new(&mut self.growth_density_gain, 0.0..=2.0)
+                            .text("Density Gain"),
+                    )
+                    .on_hover_text(param_info::tooltip("growth_density_gain"));
+                    right.add(
+                        egui::Slider::new(&mut self.growth_normal_smoothing, 0.0..=1.0)
+                            .text("Normal Smoothing"),
+                    )
+                    .on_hover_text(param_info::tooltip("growth_normal_smoothing"));
+                    right.add(
+                        egui::Slider::new(&mut self.growth_tangent_window, 1..=16)
+                            .text("Tangent Window"),
+                    )
+                    .on_hover_text(param_info::tooltip("growth_tangent_window"));
+                    right.horizontal(|ui| {
+                        ui.label(format!(
+                            "Inhibitors: {}/{MAX_GROWTH_INHIBITORS}",
+                            self.growth_inhibitors.len()
+                        ))
+                        .on_hover_text(format!(
+                            "{}\nShift-click the view to place one.",
+                            param_info::tooltip("growth_inhibitors")
+                        ));
+                        if ui.button("Clear").clicked() {
+                            self.growth_inhibitors.clear();
+                        }
+                    });
+                    right.add(
+                        egui::Slider::new(&mut self.inhibitor_radius, 0.01..=2.0)
+                            .text("Inhibitor Radius"),
                     );
+                    right.add(
+                        egui::Slider::new(&mut self.inhibitor_strength, 0.0..=1.0)
+                            .text("Inhibitor Strength"),
+                    );
+                    right.add(
+                        egui::Slider::new(&mut self.centering_strength, -0.05..=0.05)
+                            .text("Centering Strength"),
+                    )
+                    .on_hover_text(param_info::tooltip("centering_strength"));
+                    egui::ComboBox::from_label("Grow Where")
+                        .selected_text(match self.growth_curvature_filter {
+                            GrowthCurvatureFilter::All => "All",
+                            GrowthCurvatureFilter::ConvexOnly => "Convex Only",
+                            GrowthCurvatureFilter::ConcaveOnly => "Concave Only",
+                        })
+                        .show_ui(right, |ui| {
+                            ui.selectable_value(
+                                &mut self.growth_curvature_filter,
+                                GrowthCurvatureFilter::All,
+                                "All",
+                            );
+                            ui.selectable_value(
+                                &mut self.growth_curvature_filter,
+                                GrowthCurvatureFilter::ConvexOnly,
+                                "Convex Only",
+                            );
+                            ui.selectable_value(
+                                &mut self.growth_curvature_filter,
+                                GrowthCurvatureFilter::ConcaveOnly,
+                                "Concave Only",
+                            );
+                        });
 
                     right.separator();
                     right.heading("Split");
                     right.separator();
-                    right.checkbox(&mut self.split_enabled, "Split Long Edges");
+                    right
+                        .checkbox(&mut self.split_enabled, "Split Long Edges")
+                        .on_hover_text(param_info::tooltip("split_enabled"));
                     right.add(
                         egui::Slider::new(&mut self.split_length, 0.005..=1.0)
                             .logarithmic(true)
                             .text("Split Length"),
-                    );
+                    )
+                    .on_hover_text(param_info::tooltip("split_length"));
+                    right
+                        .checkbox(&mut self.auto_retarget_on_split, "Retarget Edges After Split")
+                        .on_hover_text(param_info::tooltip("auto_retarget_on_split"));
+                    right.add(
+                        egui::Slider::new(&mut self.split_refractory_generations, 0..=100)
+                            .text("Split Refractory Period"),
+                    )
+                    .on_hover_text(param_info::tooltip("split_refractory_generations"));
+                    right
+                        .checkbox(&mut self.split_curvature_enabled, "Split Only Where Curved")
+                        .on_hover_text(param_info::tooltip("split_min_curvature"));
+                    if self.split_curvature_enabled {
+                        right.add(
+                            egui::Slider::new(&mut self.split_min_curvature, 0.1..=50.0)
+                                .logarithmic(true)
+                                .text("Split Min Curvature"),
+                        )
+                        .on_hover_text(param_info::tooltip("split_min_curvature"));
+                    }
+                    right
+                        .checkbox(&mut self.merge_enabled, "Merge Short Edges")
+                        .on_hover_text(param_info::tooltip("merge_length"));
+                    if self.merge_enabled {
+                        right.add(
+                            egui::Slider::new(&mut self.merge_length, 0.001..=0.2)
+                                .logarithmic(true)
+                                .text("Merge Length"),
+                        )
+                        .on_hover_text(param_info::tooltip("merge_length"));
+                    }
 
                     right.separator();
                     right.heading("Constraint");
                     right.separator();
-                    right.checkbox(&mut self.constraint_enabled, "Constrain To Area");
+                    right
+                        .checkbox(&mut self.constraint_enabled, "Constrain To Area")
+                        .on_hover_text(param_info::tooltip("constraint_enabled"));
                     egui::ComboBox::from_label("Area Shape")
                         .selected_text(match self.constraint_shape {
                             ConstraintShape::Circle => "Circle",
                             ConstraintShape::Square => "Square",
                             ConstraintShape::Triangle => "Triangle",
+                            ConstraintShape::Rectangle { .. } => "Rectangle",
                         })
                         .show_ui(right, |ui| {
                             ui.selectable_value(
@@ -357,7 +1990,19 @@ impl eframe::App for DgApp {
                                 ConstraintShape::Triangle,
                                 "Triangle",
                             );
+                            let is_rectangle =
+                                matches!(self.constraint_shape, ConstraintShape::Rectangle { .. });
+                            if ui.selectable_label(is_rectangle, "Rectangle").clicked() && !is_rectangle {
+                                // Start from a landscape box around the current area size.
+                                self.constraint_shape = ConstraintShape::Rectangle {
+                                    half_width: self.constraint_size * 1.5,
+                                    half_height: self.constraint_size * 0.75,
+                                };
+                            }
                         });
+                    right
+                        .checkbox(&mut self.constraint_inverted, "Invert Area")
+                        .on_hover_text(param_info::tooltip("constraint_inverted"));
                     egui::ComboBox::from_label("Constraint Falloff")
                         .selected_text(match self.constraint_falloff {
                             ConstraintFalloff::Linear => "Linear",
@@ -375,24 +2020,97 @@ impl eframe::App for DgApp {
                                 "Quadratic",
                             );
                         });
-                    right.add(
-                        egui::Slider::new(&mut self.constraint_size, 0.1..=5.0)
-                            .logarithmic(true)
-                            .text("Area Size"),
-                    );
+                    if let ConstraintShape::Rectangle {
+                        half_width,
+                        half_height,
+                    } = &mut self.constraint_shape
+                    {
+                        right.add(
+                            egui::Slider::new(half_width, 0.1..=5.0)
+                                .logarithmic(true)
+                                .text("Half Width"),
+                        );
+                        right.add(
+                            egui::Slider::new(half_height, 0.1..=5.0)
+                                .logarithmic(true)
+                                .text("Half Height"),
+                        );
+                    } else {
+                        right.add(
+                            egui::Slider::new(&mut self.constraint_size, 0.1..=5.0)
+                                .logarithmic(true)
+                                .text("Area Size"),
+                        )
+                        .on_hover_text(param_info::tooltip("constraint_size"));
+                    }
                     right.add(
                         egui::Slider::new(&mut self.constraint_strength, 0.0..=1.0)
                             .text("Constraint Strength"),
-                    );
+                    )
+                    .on_hover_text(param_info::tooltip("constraint_strength"));
+                    right.add(
+                        egui::Slider::new(&mut self.constraint_ramp_generations, 0..=500)
+                            .text("Constraint Ramp"),
+                    )
+                    .on_hover_text(param_info::tooltip("constraint_ramp_generations"));
+                    right
+                        .horizontal(|ui| {
+                            ui.label("Area Center");
+                            ui.add(egui::DragValue::new(&mut self.constraint_center.x).speed(0.01));
+                            ui.add(egui::DragValue::new(&mut self.constraint_center.y).speed(0.01));
+                        })
+                        .response
+                        .on_hover_text(param_info::tooltip("constraint_center"));
+                    right.add(
+                        egui::Slider::new(&mut self.constraint_size_rate, -0.01..=0.01)
+                            .text("Area Size Rate"),
+                    )
+                    .on_hover_text(param_info::tooltip("constraint_size_rate"));
+                    right
+                        .horizontal(|ui| {
+                            ui.label("Area Velocity");
+                            let velocity = &mut self.constraint_center_velocity;
+                            ui.add(egui::DragValue::new(&mut velocity.x).speed(0.0001));
+                            ui.add(egui::DragValue::new(&mut velocity.y).speed(0.0001));
+                        })
+                        .response
+                        .on_hover_text(param_info::tooltip("constraint_center_velocity"));
                     right.checkbox(&mut self.constraint_show, "Show Area Overlay");
 
                     right.separator();
                     right.heading("Noise");
                     right.separator();
-                    right.checkbox(&mut self.jitter_enabled, "Brownian Jitter");
+                    right
+                        .checkbox(&mut self.jitter_enabled, "Brownian Jitter")
+                        .on_hover_text(param_info::tooltip("jitter_enabled"));
                     right.add(
                         egui::Slider::new(&mut self.jitter_strength, 0.0..=0.05)
                             .text("Jitter Strength"),
+                    )
+                    .on_hover_text(param_info::tooltip("jitter_strength"));
+                    right.checkbox(&mut self.jitter_first, "Jitter Before Other Forces");
+                    egui::ComboBox::from_label("Integrator")
+                        .selected_text(self.integrator.label())
+                        .show_ui(right, |ui| {
+                            for kind in IntegratorKind::ALL {
+                                ui.selectable_value(&mut self.integrator, kind, kind.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text("Update rule for the forces; Midpoint evaluates them twice.");
+
+                    right.separator();
+                    right.heading("Mouse");
+                    right.separator();
+                    right.checkbox(&mut self.mouse_attract_enabled, "Mouse Attract");
+                    right.add(
+                        egui::Slider::new(&mut self.mouse_attract_radius, 0.01..=5.0)
+                            .logarithmic(true)
+                            .text("Attract Radius"),
+                    );
+                    right.add(
+                        egui::Slider::new(&mut self.mouse_attract_strength, -0.1..=0.1)
+                            .text("Attract Strength"),
                     );
 
                     right.separator();
@@ -402,47 +2120,241 @@ impl eframe::App for DgApp {
                         egui::Slider::new(&mut self.steps_per_frame, 1..=32).text("Steps/Frame"),
                     );
                     right.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!self.checkpoints.is_empty(), egui::Button::new("Step Back"))
+                            .on_hover_text(
+                                "Restore the previous history snapshot and continue from there, \
+                                 discarding later history.",
+                            )
+                            .clicked()
+                        {
+                            self.step_back();
+                        }
                         if ui.button("Step").clicked() {
-                            self.sim.step(self.sim_params());
+                            self.step_sim();
                         }
                         ui.checkbox(&mut self.auto_step, "Run");
                     });
+                    right.horizontal(|ui| {
+                        if ui
+                            .button("Preview Forces")
+                            .on_hover_text("Draw the displacement the next step would apply.")
+                            .clicked()
+                        {
+                            let delta = self.sim.preview_delta(self.sim_params());
+                            self.force_preview = Some((self.sim.generation(), delta));
+                        }
+                        if self.force_preview.is_some() && ui.button("Clear").clicked() {
+                            self.force_preview = None;
+                        }
+                    });
+                    right.add(
+                        egui::Slider::new(&mut self.force_preview_scale, 1.0..=1000.0)
+                            .logarithmic(true)
+                            .text("Arrow Scale"),
+                    );
+                    right.checkbox(&mut self.auto_pause_enabled, "Pause When Converged");
+                    right.add(
+                        egui::Slider::new(&mut self.convergence_threshold, 1e-8..=1e-2)
+                            .logarithmic(true)
+                            .text("Convergence Threshold"),
+                    )
+                    .on_hover_text(param_info::tooltip("convergence_threshold"));
+
+                    right.horizontal(|ui| {
+                        let seed_text = format!("0x{:016X}", self.sim.seed());
+                        ui.label(format!("Seed: {seed_text}"));
+                        if ui.button("Copy").clicked() {
+                            ui.ctx().copy_text(seed_text);
+                        }
+                    });
+                    right.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.seed_input)
+                                .hint_text("0x... or decimal")
+                                .desired_width(140.0),
+                        );
+                        if ui.button("Apply Seed").clicked() {
+                            if let Some(seed) = parse_seed(&self.seed_input) {
+                                // Restart from the starter shape so the seed fully determines the run.
+                                self.sim.reset_seed(seed);
+                                changed = true;
+                            } else {
+                                self.seed_input.clear();
+                            }
+                        }
+                    });
+
+                    right.horizontal(|ui| {
+                        if ui
+                            .button("Copy Config Code")
+                            .on_hover_text(
+                                "Parameters, seed, starter and appearance as one pasteable string.",
+                            )
+                            .clicked()
+                        {
+                            let code = encode_config(&self.shared_config());
+                            self.config_code_status = format!("Copied {} characters", code.len());
+                            ui.ctx().copy_text(code);
+                        }
+                    });
+                    right.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.config_code_input)
+                                .hint_text("Paste config code")
+                                .desired_width(140.0),
+                        );
+                        if ui.button("Apply Code").clicked() {
+                            match decode_config(&self.config_code_input) {
+                                Ok(config) => {
+                                    self.apply_config(&config);
+                                    self.config_code_status = "Applied config code".to_owned();
+                                }
+                                Err(err) => self.config_code_status = format!("Invalid code: {err}"),
+                            }
+                        }
+                    });
+                    if !self.config_code_status.is_empty() {
+                        right.label(&self.config_code_status);
+                    }
+                    right.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.params_file).desired_width(140.0),
+                        );
+                        if ui
+                            .button("Save Params")
+                            .on_hover_text("Parameters and seed only, without the starter shape.")
+                            .clicked()
+                        {
+                            self.save_params();
+                        }
+                        if ui
+                            .button("Load Params")
+                            .on_hover_text("Apply saved parameters and seed to the current shape.")
+                            .clicked()
+                        {
+                            self.load_params();
+                        }
+                    });
+                    if !self.params_file_status.is_empty() {
+                        right.label(&self.params_file_status);
+                    }
 
                     right.horizontal(|ui| {
                         if ui.button("Reset Geometry").clicked() {
                             // Reset only the starter shape and RNG; preserve all other settings.
+                            self.starter_shape = StarterShape::Ngon;
                             self.radius = 1.0;
                             self.sides = 32;
-                            self.sim.reset_seed(0xD1FF_EA11_2026_0001);
+                            self.star_inner_ratio = 0.5;
+                            self.rect_aspect = 0.6;
+                            self.corner_ratio = 0.3;
+                            self.starter_center = Vec2::ZERO;
+                            self.perturb_magnitude = 0.0;
+                            self.sim.reset_seed(DEFAULT_SEED);
                             changed = true;
                         }
 
                         if ui.button("Reset All").clicked() {
                             // Reset controls and RNG seed to deterministic defaults.
+                            self.starter_shape = StarterShape::Ngon;
                             self.radius = 1.0;
                             self.sides = 32;
+                            self.star_inner_ratio = 0.5;
+                            self.rect_aspect = 0.6;
+                            self.corner_ratio = 0.3;
+                            self.starter_center = Vec2::ZERO;
+                            self.perturb_magnitude = 0.0;
                             self.view_mode = ViewMode::Fit;
+                            self.fit_constraint = true;
                             self.zoom_px_per_unit = 120.0;
+                            self.pan = Vec2::ZERO;
+                            self.draw_closed = true;
+                            self.line_width = 2.0;
+                            self.fill_interior = false;
+                            self.show_winding = false;
+                            self.show_growth_front = false;
+                            self.show_spatial_hash = false;
+                            self.measure_mode = false;
+                            self.measure_points.clear();
+                            self.edge_coloring = EdgeColoring::Solid;
+                            self.show_scale_bar = false;
+                            self.theme = Theme::DARK;
+                            self.show_vertices = true;
+                            self.vertex_marker = VertexMarker::FilledCircle;
+                            self.vertex_marker_size = 3.0;
+                            self.force_preview = None;
+                            self.force_preview_scale = 20.0;
+                            self.color_vertices_by_id = false;
+                            self.vertex_dot_limit = 2000;
                             self.edge_regularization_enabled = true;
                             self.edge_stiffness = 0.2;
+                            self.asymmetric_stiffness = false;
+                            self.edge_stiffness_tension = 0.2;
+                            self.edge_stiffness_compression = 0.2;
+                            self.edge_dead_zone = 0.0;
                             self.repulsion_enabled = true;
                             self.repulsion_radius = 0.15;
                             self.repulsion_strength = 0.01;
+                            self.repulsion_threads = 1;
+                            self.repulsion_max_neighbors = 0;
+                            self.repulsion_neighbor_exclusion = 1;
+                            self.adaptive_repulsion = false;
+                            self.adaptive_repulsion_max = 4.0;
+                            self.edge_repulsion_enabled = false;
+                            self.edge_repulsion_strength = 0.01;
                             self.growth_enabled = false;
                             self.growth_rate = 0.001;
+                            self.area_goal_enabled = false;
+                            self.erosion_guard_enabled = false;
+                            self.growth_erosion_min_edge = 0.01;
+                            self.growth_area_goal = 12.0;
+                            self.max_area_enabled = false;
+                            self.max_area = 50.0;
+                            self.min_area_enabled = false;
+                            self.min_area = 0.5;
+                            self.growth_conserve_perimeter = false;
+                            self.sim.set_growth_outward(true);
+                            self.growth_curvature_filter = GrowthCurvatureFilter::All;
+                            self.growth_density_gain = 0.0;
+                            self.growth_normal_smoothing = 0.0;
+                            self.growth_tangent_window = 1;
+                            self.growth_inhibitors.clear();
+                            self.inhibitor_radius = 0.3;
+                            self.inhibitor_strength = 0.9;
+                            self.centering_strength = 0.0;
+                            self.centroid_kind = CentroidKind::Area;
                             self.split_enabled = false;
                             self.split_length = 0.25;
+                            self.auto_retarget_on_split = false;
+                            self.split_refractory_generations = 0;
+                            self.split_curvature_enabled = false;
+                            self.split_min_curvature = 2.0;
+                            self.merge_enabled = false;
+                            self.merge_length = 0.01;
                             self.constraint_enabled = false;
                             self.constraint_shape = ConstraintShape::Circle;
                             self.constraint_size = 1.5;
                             self.constraint_strength = 0.1;
                             self.constraint_falloff = ConstraintFalloff::Linear;
+                            self.constraint_ramp_generations = 0;
+                            self.constraint_inverted = false;
+                            self.constraint_center = Vec2::ZERO;
+                            self.constraint_size_rate = 0.0;
+                            self.constraint_center_velocity = Vec2::ZERO;
                             self.constraint_show = true;
                             self.jitter_enabled = true;
                             self.jitter_strength = 0.005;
+                            self.jitter_first = false;
+                            self.integrator = IntegratorKind::Euler;
+                            self.mouse_attract_enabled = false;
+                            self.mouse_attract_radius = 0.5;
+                            self.mouse_attract_strength = 0.01;
                             self.auto_step = false;
                             self.steps_per_frame = 1;
-                            self.sim.reset_seed(0xD1FF_EA11_2026_0001);
+                            self.auto_pause_enabled = false;
+                            self.convergence_threshold = 1e-5;
+                            self.sim.reset_seed(DEFAULT_SEED);
                             changed = true;
                         }
                     });
@@ -458,58 +2370,351 @@ impl eframe::App for DgApp {
                 }
 
                 ui.separator();
-                ui.label(format!("Vertices: {}", self.sim.polygon().len()));
+                match self.sim.polygons().len() {
+                    1 => ui.label(format!("Vertices: {}", self.sim.vertex_count())),
+                    count => ui.label(format!(
+                        "Vertices: {} in {count} polygons; stats below are for the first",
+                        self.sim.vertex_count()
+                    )),
+                };
+                let counts = self.sim.vertex_count_log();
+                if counts.len() > 1 {
+                    let start = counts.len().saturating_sub(SPARKLINE_LENGTH);
+                    let recent: Vec<(u64, usize)> = counts.range(start..).copied().collect();
+                    draw_sparkline(ui, &recent).on_hover_text(format!(
+                        "Vertex count over generations {}..{}",
+                        recent[0].0,
+                        recent[recent.len() - 1].0
+                    ));
+                    if ui.button("Copy Vertex Counts").clicked() {
+                        let mut csv = "generation,vertices\n".to_owned();
+                        for (generation, count) in counts {
+                            csv += &format!("{generation},{count}\n");
+                        }
+                        ui.ctx().copy_text(csv);
+                    }
+                }
+                if self.sim.polygon().len() < 3 {
+                    ui.colored_label(
+                        Color32::LIGHT_RED,
+                        "Degenerate polygon: needs at least 3 vertices",
+                    );
+                }
                 ui.label(format!("Perimeter: {:.6}", self.sim.polygon().perimeter()));
+                if let Some(compactness) = self.sim.polygon().compactness() {
+                    ui.label(format!("Compactness: {compactness:.4}")).on_hover_text(
+                        "4π·area / perimeter²: 1 for a circle, lower for crinkly outlines.",
+                    );
+                }
                 ui.label(format!(
                     "Avg Edge Length: {:.6}",
                     average_edge_length(self.sim.polygon())
                 ));
                 ui.label(format!("Generation: {}", self.sim.generation()));
-                if let Some(c) = self.sim.polygon().centroid() {
+                ui.checkbox(&mut self.profile_steps, "Profile Steps")
+                    .on_hover_text("Time each force pass and the split pass of every step.");
+                if self.profile_steps
+                    && let Some(profile) = self.sim.last_profile()
+                {
+                    let micros = |d: std::time::Duration| d.as_secs_f64() * 1e6;
+                    let params = self.sim_params();
+                    ui.label(format!("Step Time: {:.1} µs", micros(profile.total)));
+                    for kind in DEFAULT_FORCE_ORDER {
+                        if kind.is_enabled(&params) {
+                            let time = micros(profile.force(kind));
+                            ui.label(format!("  {}: {time:.1} µs", kind.label()));
+                        }
+                    }
+                    if self.split_enabled {
+                        ui.label(format!("  Split: {:.1} µs", micros(profile.split)));
+                    }
+                }
+                if let Some((min, max)) = self.sim.polygon().bounds() {
+                    // Cell sizes from 1/4 down to 1/128 of the larger extent.
+                    let extent = (max - min).max_element();
+                    let scales: Vec<Real> =
+                        (2..=7).map(|k| extent / (1u32 << k) as Real).collect();
+                    let dimension = self.sim.polygon().box_counting_dimension(&scales);
+                    if dimension.is_finite() {
+                        ui.label(format!("Box Dimension: {dimension:.3}"));
+                    }
+                }
+                if self.sim.is_converged(self.convergence_threshold) {
+                    ui.colored_label(Color32::LIGHT_BLUE, "Converged");
+                }
+                let warnings = self.sim.polygon().validate();
+                ui.horizontal(|ui| {
+                    if warnings.is_empty() {
+                        ui.label("Health: OK");
+                        return;
+                    }
+                    let details: Vec<String> =
+                        warnings.iter().take(10).map(|w| w.describe()).collect();
+                    ui.colored_label(Color32::YELLOW, format!("Warnings: {}", warnings.len()))
+                        .on_hover_text(details.join("\n"));
+                    if ui
+                        .button("Repair")
+                        .on_hover_text("Remove zero-length edges and wind counter-clockwise.")
+                        .clicked()
+                    {
+                        self.sim.repair_polygon();
+                        self.force_preview = None;
+                    }
+                });
+                if self.adaptive_repulsion && self.repulsion_strength > 0.0 {
+                    let vertices = self.sim.polygon().vertices();
+                    let strength = effective_repulsion_strength(vertices, &self.sim_params());
+                    let boost = strength / self.repulsion_strength;
+                    ui.label(format!("Repulsion Boost: {boost:.2}x"));
+                }
+                egui::ComboBox::from_label("Centroid")
+                    .selected_text(centroid_label(self.centroid_kind))
+                    .show_ui(ui, |ui| {
+                        for kind in [CentroidKind::Area, CentroidKind::VertexMean] {
+                            ui.selectable_value(&mut self.centroid_kind, kind, centroid_label(kind));
+                        }
+                    })
+                    .response
+                    .on_hover_text(param_info::tooltip("centroid_kind"));
+                if let Some(c) = self.sim.polygon().centroid_of_kind(self.centroid_kind) {
                     ui.label(format!("Centroid: ({:.4}, {:.4})", c.x, c.y));
                 }
             });
 
-        if self.auto_step && !Self::input_in_progress(ctx) {
+        if !Self::input_in_progress(ctx) {
+            // Compare only once an edit is finished so a slider drag logs a single change.
+            let params = self.sim_params();
+            if self.param_log_enabled {
+                self.param_log.record(self.sim.generation(), &self.logged_params, &params);
+            }
+            self.logged_params = params;
+        }
+
+        // A vertex drag holds the pointer down but should not pause the other vertices.
+        if self.auto_step && (self.dragged_vertex.is_some() || !Self::input_in_progress(ctx)) {
             // Advance multiple steps per frame for faster evolution.
+            let mut converged = false;
             for _ in 0..self.steps_per_frame {
-                self.sim.step(self.sim_params());
+                converged = self.step_sim().converged;
+            }
+            if self.auto_pause_enabled && converged {
+                // Stop burning CPU once the shape has settled.
+                self.auto_step = false;
             }
             ctx.request_repaint();
         }
 
+        if self.recorder.is_some() {
+            self.capture_frame();
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             let available = ui.available_size();
-            let (response, _painter) = ui.allocate_painter(available, Sense::hover());
-            Self::draw_polygon(
-                ui,
-                response.rect,
-                self.sim.polygon(),
-                self.view_mode,
-                self.zoom_px_per_unit,
-                self.constraint_show,
-                self.constraint_shape,
-                self.constraint_size,
-            );
+            let (response, _painter) = ui.allocate_painter(available, Sense::click_and_drag());
+            let view = self.view_transform(response.rect);
+            if response.drag_started_by(egui::PointerButton::Primary)
+                && let Some(pos) = response.interact_pointer_pos()
+                && let Some(index) = pick_vertex(self.sim.polygon(), view, pos)
+            {
+                let id = self.sim.vertex_ids()[index];
+                self.sim.set_pinned_vertex(Some(id));
+                self.dragged_vertex = Some((id, view));
+            }
+            if let Some((id, drag_view)) = self.dragged_vertex {
+                let index = self.sim.vertex_ids().iter().position(|&v| v == id);
+                if response.dragged_by(egui::PointerButton::Primary)
+                    && let Some(index) = index
+                    && let Some(pos) = response.interact_pointer_pos()
+                {
+                    self.sim.move_vertex(index, drag_view.to_world(pos));
+                } else {
+                    // Released, or the vertex vanished in a rebuild: resume normal dynamics.
+                    self.sim.set_pinned_vertex(None);
+                    self.dragged_vertex = None;
+                }
+            } else if response.dragged_by(egui::PointerButton::Secondary) {
+                // Right-drag pans the view by the cursor motion in world units.
+                self.pan -= view.to_world_delta(response.drag_delta());
+            }
+            if self.measure_mode
+                && response.clicked()
+                && !ui.input(|i| i.modifiers.shift)
+                && let Some(pos) = response.interact_pointer_pos()
+            {
+                if self.measure_points.len() == 2 {
+                    self.measure_points.clear();
+                } else {
+                    self.measure_points.push(view.to_world(pos));
+                }
+            }
+            if response.clicked()
+                && ui.input(|i| i.modifiers.shift)
+                && self.growth_inhibitors.len() < MAX_GROWTH_INHIBITORS
+                && let Some(pos) = response.interact_pointer_pos()
+            {
+                self.growth_inhibitors.push(Attractor {
+                    position: view.to_world(pos),
+                    radius: self.inhibitor_radius,
+                    strength: self.inhibitor_strength,
+                });
+            }
+            let view = match self.dragged_vertex {
+                Some((_, drag_view)) => drag_view,
+                None => self.view_transform(response.rect),
+            };
+            self.mouse_world = response.hover_pos().map(|p| view.to_world(p));
+            self.draw_polygon(ui, &response, view);
         });
     }
 }
 
-fn bounds(points: &[Vec2]) -> Option<(Vec2, Vec2)> {
-    if points.is_empty() {
-        return None;
+// Nearest vertex within a small pixel radius of a screen position.
+fn pick_vertex(polygon: &Polygon, view: ViewTransform, pos: Pos2) -> Option<usize> {
+    polygon
+        .vertices()
+        .iter()
+        .map(|&v| view.to_screen(v).distance(pos))
+        .enumerate()
+        .filter(|(_, d)| *d <= 8.0)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+// Curve color at low opacity, so the outline stays visible on top of the fill.
+fn interior_color(curve: Color32) -> Color32 {
+    let [r, g, b, _] = curve.to_srgba_unmultiplied();
+    Color32::from_rgba_unmultiplied(r, g, b, 60)
+}
+
+// Line chart of `(generation, count)` pairs scaled to their own range, with the range
+// printed at the ends so flat and exploding counts are told apart at a glance.
+fn draw_sparkline(ui: &mut egui::Ui, counts: &[(u64, usize)]) -> egui::Response {
+    let (response, painter) = ui.allocate_painter(egui::vec2(200.0, 36.0), Sense::hover());
+    let rect = response.rect;
+    let min = counts.iter().map(|&(_, c)| c).min().unwrap_or(0);
+    let max = counts.iter().map(|&(_, c)| c).max().unwrap_or(0);
+    let span = (max - min).max(1) as f32;
+    let last = counts.len().saturating_sub(1).max(1) as f32;
+    let points: Vec<Pos2> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &(_, c))| {
+            let x = rect.left() + rect.width() * i as f32 / last;
+            let y = rect.bottom() - rect.height() * (c - min) as f32 / span;
+            Pos2::new(x, y)
+        })
+        .collect();
+    let frame = Stroke::new(1.0, Color32::from_gray(60));
+    painter.rect_stroke(rect, 0.0, frame, egui::StrokeKind::Inside);
+    painter.add(Shape::line(points, Stroke::new(1.5, Color32::LIGHT_GREEN)));
+    let font = egui::FontId::monospace(10.0);
+    let color = Color32::from_gray(160);
+    painter.text(rect.left_top(), egui::Align2::LEFT_TOP, max.to_string(), font.clone(), color);
+    painter.text(rect.left_bottom(), egui::Align2::LEFT_BOTTOM, min.to_string(), font, color);
+    response
+}
+
+// Discs at every point give a stroked polyline round joins and caps, which egui's
+// mitered path strokes lack. Below a pixel wide the corners are not visible anyway.
+fn round_joins(painter: &egui::Painter, points: &[Pos2], stroke: Stroke) {
+    let radius = stroke.width * 0.5;
+    if radius >= 0.5 {
+        for &p in points {
+            painter.circle_filled(p, radius, stroke.color);
+        }
+    }
+}
+
+// Blue-to-orange gradient for normalized scalar values in [0, 1].
+fn gradient_color(t: f32) -> Color32 {
+    let [r, g, b] = Colormap::BlueOrange.sample(t as Real);
+    Color32::from_rgb(r, g, b)
+}
+
+// Hue from a mixed hash of the id so consecutive ids get clearly distinct colors.
+fn id_color(id: u64) -> Color32 {
+    let mut h = id.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    let hue = (h >> 40) as f32 / (1u64 << 24) as f32;
+    egui::ecolor::Hsva::new(hue, 0.75, 0.95, 1.0).into()
+}
+
+// Gradient strip with min/max labels in the bottom-left corner.
+fn draw_color_legend(painter: &egui::Painter, rect: Rect, min: Real, max: Real) {
+    let size = egui::vec2(140.0, 10.0);
+    let origin = rect.left_bottom() + egui::vec2(12.0, -28.0);
+    let slices = 32;
+    let slice_width = size.x / slices as f32;
+    for k in 0..slices {
+        let t = k as f32 / (slices - 1) as f32;
+        let min_corner = origin + egui::vec2(k as f32 * slice_width, 0.0);
+        let slice = Rect::from_min_size(min_corner, egui::vec2(slice_width + 0.5, size.y));
+        painter.rect_filled(slice, 0.0, gradient_color(t));
+    }
+
+    let font = egui::FontId::proportional(11.0);
+    let label_y = origin.y + size.y + 2.0;
+    painter.text(
+        Pos2::new(origin.x, label_y),
+        egui::Align2::LEFT_TOP,
+        format!("{min:.4}"),
+        font.clone(),
+        Color32::GRAY,
+    );
+    painter.text(
+        Pos2::new(origin.x + size.x, label_y),
+        egui::Align2::RIGHT_TOP,
+        format!("{max:.4}"),
+        font,
+        Color32::GRAY,
+    );
+}
+
+// Horizontal bar spanning a power-of-ten world length in the bottom-right corner.
+fn draw_scale_bar(painter: &egui::Painter, rect: Rect, scale: Real) {
+    if scale <= 0.0 {
+        return;
     }
 
-    // Axis-aligned bounding box over all vertices.
-    let mut min = points[0];
-    let mut max = points[0];
+    // Start from one world unit and rescale by decades until the bar has a readable length.
+    let mut unit = 1.0;
+    while unit * scale > 200.0 {
+        unit /= 10.0;
+    }
+    while unit * scale < 20.0 {
+        unit *= 10.0;
+    }
 
-    for p in points.iter().copied().skip(1) {
-        min.x = min.x.min(p.x);
-        min.y = min.y.min(p.y);
-        max.x = max.x.max(p.x);
-        max.y = max.y.max(p.y);
+    let length = (unit * scale) as f32;
+    let right = rect.right_bottom() + egui::vec2(-12.0, -20.0);
+    let left = right - egui::vec2(length, 0.0);
+    let stroke = Stroke::new(2.0, Color32::GRAY);
+    painter.line_segment([left, right], stroke);
+    painter.line_segment([left, left - egui::vec2(0.0, 5.0)], stroke);
+    painter.line_segment([right, right - egui::vec2(0.0, 5.0)], stroke);
+    painter.text(
+        Pos2::new((left.x + right.x) * 0.5, left.y - 6.0),
+        egui::Align2::CENTER_BOTTOM,
+        format!("{unit} unit"),
+        egui::FontId::proportional(11.0),
+        Color32::GRAY,
+    );
+}
+
+fn centroid_label(kind: CentroidKind) -> &'static str {
+    match kind {
+        CentroidKind::VertexMean => "Vertex Mean",
+        CentroidKind::Area => "Area Centroid",
     }
+}
 
-    Some((min, max))
+// Accept seeds as `0x`-prefixed hex (underscores allowed) or plain decimal.
+fn parse_seed(text: &str) -> Option<u64> {
+    let text = text.trim().replace('_', "");
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
 }
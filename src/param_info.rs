@@ -0,0 +1,223 @@
+use crate::config_code::CodeValue;
+use crate::error::GrowthError;
+use crate::sim::SimParams;
+
+// Human-facing documentation for one `SimParams` field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamInfo {
+    // Field name in `SimParams`.
+    pub name: &'static str,
+    pub label: &'static str,
+    pub description: &'static str,
+    // Recommended range for numeric parameters.
+    pub range: Option<(f64, f64)>,
+}
+
+macro_rules! param_registry {
+    ($($field:ident => $label:expr, $description:expr, $range:expr;)*) => {
+        pub const PARAMS: &[ParamInfo] = &[
+            $(ParamInfo {
+                name: stringify!($field),
+                label: $label,
+                description: $description,
+                range: $range,
+            },)*
+        ];
+
+        // Exhaustive destructuring: adding a `SimParams` field without an entry here
+        // fails to compile, keeping the registry complete.
+        const _: fn(SimParams) = |params| {
+            let SimParams { $($field: _,)* } = params;
+        };
+
+        // `(name, old, new)` for every field that differs, values in `Debug` form, in
+        // registry order.
+        pub fn changed_params(
+            old: &SimParams,
+            new: &SimParams,
+        ) -> Vec<(&'static str, String, String)> {
+            let mut changes = Vec::new();
+            $(if old.$field != new.$field {
+                changes.push((
+                    stringify!($field),
+                    format!("{:?}", old.$field),
+                    format!("{:?}", new.$field),
+                ));
+            })*
+            changes
+        }
+
+        // Config code layout: every field in registry order.
+        pub(crate) fn write_params(params: &SimParams, out: &mut Vec<u8>) {
+            $(CodeValue::write(&params.$field, out);)*
+        }
+
+        pub(crate) fn read_params(input: &mut &[u8]) -> Result<SimParams, GrowthError> {
+            Ok(SimParams {
+                $($field: CodeValue::read(input)?,)*
+            })
+        }
+    };
+}
+
+param_registry! {
+    edge_regularization_enabled => "Edge Regularization",
+        "Springs pull each edge toward the target length.", None;
+    target_edge_length => "Target Edge Length",
+        "Rest length of the edge springs in world units.", Some((0.0001, 2.0));
+    edge_stiffness => "Edge Stiffness",
+        "Fraction of the length error corrected per step.", Some((0.0, 1.0));
+    edge_stiffness_tension => "Tension Stiffness",
+        "Stiffness for edges longer than the target; unset uses Edge Stiffness.",
+        Some((0.0, 1.0));
+    edge_stiffness_compression => "Compression Stiffness",
+        "Stiffness for edges shorter than the target; unset uses Edge Stiffness. \
+         0 lets edges buckle freely like rope.",
+        Some((0.0, 1.0));
+    edge_dead_zone => "Edge Dead Zone",
+        "Length errors up to this size are left uncorrected, so springs stop fighting \
+         jitter over tiny deviations. 0 always corrects.",
+        Some((0.0, 0.05));
+    repulsion_enabled => "Self Repulsion",
+        "Non-adjacent vertices push apart when closer than the repulsion radius.", None;
+    repulsion_radius => "Repulsion Radius",
+        "Interaction distance for vertex and edge repulsion.", Some((0.0001, 2.0));
+    repulsion_strength => "Repulsion Strength",
+        "Peak push between two coincident vertices; fades linearly to zero at the radius.",
+        Some((0.0, 0.1));
+    repulsion_threads => "Repulsion Threads",
+        "Worker threads for vertex repulsion. Results are identical for any count.",
+        Some((1.0, 16.0));
+    repulsion_max_neighbors => "Max Repulsion Neighbors",
+        "Approximation: each vertex is pushed only by its K nearest neighbors within the \
+         radius, bounding cost in dense regions. 0 disables the cap.",
+        Some((0.0, 64.0));
+    repulsion_neighbor_exclusion => "Repulsion Exclusion",
+        "Vertices up to this many steps apart along the curve do not repel each other. \
+         1 skips only immediate neighbors.",
+        Some((0.0, 8.0));
+    adaptive_repulsion => "Adaptive Repulsion",
+        "Strengthens repulsion as separate parts of the curve approach each other.", None;
+    adaptive_repulsion_max => "Max Repulsion Boost",
+        "Upper bound on the adaptive repulsion multiplier.", Some((1.0, 16.0));
+    edge_repulsion_enabled => "Edge Repulsion",
+        "Vertices also push away from nearby non-incident edges.", None;
+    edge_repulsion_strength => "Edge Repulsion Strength",
+        "Peak push between a vertex and an edge it touches.", Some((0.0, 0.1));
+    growth_enabled => "Normal Growth",
+        "Moves every vertex along its outward normal each step.", None;
+    growth_rate => "Growth Rate",
+        "Distance moved along the normal per step; negative values shrink.",
+        Some((-0.01, 0.01));
+    growth_area_goal => "Area Goal",
+        "Grows or shrinks toward this enclosed area, slowing as it gets close. \
+         Growth Rate becomes the top speed.",
+        Some((0.1, 100.0));
+    max_area => "Max Area",
+        "Growth and splitting stop while the enclosed area is above this, a hard ceiling \
+         for unattended runs.",
+        Some((0.1, 100.0));
+    min_area => "Min Area",
+        "After a step leaves the enclosed area below this, the shape is scaled up about its \
+         centroid back to it, so runs cannot collapse to a point.",
+        Some((0.01, 10.0));
+    growth_erosion_min_edge => "Erosion Guard",
+        "With negative growth, each vertex stops shrinking once an adjacent edge would drop \
+         below this length or it would reach the centroid, preventing inversion.",
+        Some((0.001, 0.2));
+    growth_conserve_perimeter => "Conserve Perimeter",
+        "Rescales after growth so the perimeter stays constant.", None;
+    growth_curvature_filter => "Grow Where",
+        "Restricts growth to convex or concave vertices.", None;
+    growth_density_gain => "Density Gain",
+        "Suppresses growth where many vertices lie within the repulsion radius.",
+        Some((0.0, 2.0));
+    growth_normal_smoothing => "Normal Smoothing",
+        "Averages each growth direction with its neighbors' to damp noise; 0 is off.",
+        Some((0.0, 1.0));
+    growth_tangent_window => "Tangent Window",
+        "Growth directions come from the chord spanning this many neighbors on each side; \
+         larger windows ignore small wiggles.",
+        Some((1.0, 16.0));
+    growth_inhibitors => "Growth Inhibitors",
+        "Points that suppress growth within their radius without moving the curve. \
+         Strength 1 stops growth at the center.",
+        None;
+    split_enabled => "Split Long Edges",
+        "Inserts vertices into edges longer than the split length.", None;
+    split_length => "Split Length",
+        "Edges longer than this are subdivided.", Some((0.005, 1.0));
+    auto_retarget_on_split => "Retarget Edges After Split",
+        "Sets the target edge length to the new average after each split.", None;
+    split_refractory_generations => "Split Refractory Period",
+        "Steps after a split during which the split edge's vertices neither grow nor \
+         split again, spacing out branches. 0 disables it.",
+        Some((0.0, 100.0));
+    split_min_curvature => "Split Min Curvature",
+        "Long edges only split when the curvature at an endpoint is above this, keeping \
+         flat stretches coarse and adding detail where the curve bends.",
+        Some((0.1, 50.0));
+    merge_length => "Merge Length",
+        "Edges shorter than this collapse into one vertex, shortest first, so dense \
+         stretches thin out. Keep it well below the split length.",
+        Some((0.001, 0.2));
+    constraint_enabled => "Constrain To Area",
+        "Pushes vertices outside the area back toward its boundary.", None;
+    constraint_shape => "Area Shape",
+        "Shape of the constraint area, centered on the area center.", None;
+    constraint_size => "Area Size",
+        "Radius or half-size of the constraint area.", Some((0.1, 5.0));
+    constraint_strength => "Constraint Strength",
+        "Fraction of the overshoot corrected per step, never past the boundary.",
+        Some((0.0, 1.0));
+    constraint_falloff => "Constraint Falloff",
+        "Linear or quadratic response to how far a vertex overshoots.", None;
+    constraint_ramp_generations => "Constraint Ramp",
+        "Generations over which the constraint eases in after being enabled; 0 is instant.",
+        Some((0.0, 500.0));
+    constraint_inverted => "Invert Area",
+        "Keeps the curve outside the area instead, pushing interior vertices out.", None;
+    constraint_center => "Area Center",
+        "Center of the constraint area at generation 0.", None;
+    constraint_size_rate => "Area Size Rate",
+        "Change in the area's size per generation; negative values shrink it.",
+        Some((-0.01, 0.01));
+    constraint_center_velocity => "Area Velocity",
+        "Distance the area's center moves per generation.", None;
+    jitter_enabled => "Brownian Jitter",
+        "Adds a seeded random offset to every vertex each step.", None;
+    jitter_strength => "Jitter Strength",
+        "Maximum random offset per axis per step.", Some((0.0, 0.05));
+    attractor => "Mouse Attract",
+        "Point that pulls vertices within its radius toward itself.", None;
+    centering_strength => "Centering Strength",
+        "Pulls every vertex toward the centroid; negative values push outward.",
+        Some((-0.05, 0.05));
+    centroid_kind => "Centroid",
+        "Center used by metrics, centering and rescaling. The vertex mean is biased toward \
+         densely sampled regions; the area centroid is not.",
+        None;
+    force_order => "Force Order",
+        "Sequence in which force passes accumulate; affects only summation and RNG order.",
+        None;
+    convergence_threshold => "Convergence Threshold",
+        "A step counts as converged when no vertex moves further than this.",
+        Some((1e-8, 1e-2));
+}
+
+pub fn lookup(name: &str) -> Option<&'static ParamInfo> {
+    PARAMS.iter().find(|info| info.name == name)
+}
+
+// Description plus recommended range, suitable for hover text or `--help` output.
+pub fn tooltip(name: &str) -> String {
+    match lookup(name) {
+        Some(ParamInfo {
+            description,
+            range: Some((min, max)),
+            ..
+        }) => format!("{description}\nRecommended: {min} to {max}"),
+        Some(info) => info.description.to_owned(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,74 @@
+use crate::param_info::changed_params;
+use crate::sim::SimParams;
+use std::fmt::Write;
+
+// Fields left out of the log: the attractor follows the cursor and changes every frame.
+const UNLOGGED: &[&str] = &["attractor"];
+
+// One parameter edit, with values in `Debug` form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamChange {
+    pub generation: u64,
+    pub name: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+// Passive record of parameter edits over a session, in the order they happened.
+#[derive(Debug, Clone, Default)]
+pub struct ParamLog {
+    entries: Vec<ParamChange>,
+}
+
+impl ParamLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[ParamChange] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    // Append one entry per field that differs between `old` and `new`. Returns how many
+    // were added.
+    pub fn record(&mut self, generation: u64, old: &SimParams, new: &SimParams) -> usize {
+        let before = self.entries.len();
+        for (name, old, new) in changed_params(old, new) {
+            if !UNLOGGED.contains(&name) {
+                self.entries.push(ParamChange {
+                    generation,
+                    name,
+                    old,
+                    new,
+                });
+            }
+        }
+        self.entries.len() - before
+    }
+
+    // `[{"generation": 12, "param": "growth_rate", "old": "0.001", "new": "0.002"}, ...]`
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, entry) in self.entries.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            let _ = write!(
+                json,
+                "{sep}\n  {{\"generation\": {}, \"param\": \"{}\", \"old\": \"{}\", \"new\": \"{}\"}}",
+                entry.generation,
+                entry.name,
+                escape_json(&entry.old),
+                escape_json(&entry.new)
+            );
+        }
+        json.push_str(if self.entries.is_empty() { "]\n" } else { "\n]\n" });
+        json
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,339 @@
+use crate::error::GrowthError;
+use crate::geometry::{closest_point_on_segment, edges, Polygon, Real, Vec2};
+use crate::sim::{SimParams, Simulation};
+use crate::svg::shared_view_box;
+use image::{Rgba, RgbaImage};
+use std::path::{Path, PathBuf};
+
+// Colors and stroke settings for offscreen rendering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderStyle {
+    pub background: [u8; 4],
+    pub curve: [u8; 4],
+    pub line_width: f32,
+    pub closed: bool,
+    // Interior color under the outline, from `Polygon::triangulate`; `None` leaves it
+    // unfilled, as do self-intersecting outlines.
+    pub fill: Option<[u8; 4]>,
+}
+
+impl Default for RenderStyle {
+    // Matches the GUI's dark theme.
+    fn default() -> Self {
+        Self {
+            background: [20, 20, 20, 255],
+            curve: [144, 238, 144, 255],
+            line_width: 2.0,
+            closed: true,
+            fill: None,
+        }
+    }
+}
+
+// Shape drawn at each vertex in the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexMarker {
+    FilledCircle,
+    HollowCircle,
+    Square,
+}
+
+impl VertexMarker {
+    pub const ALL: [Self; 3] = [Self::FilledCircle, Self::HollowCircle, Self::Square];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FilledCircle => "Filled Circle",
+            Self::HollowCircle => "Hollow Circle",
+            Self::Square => "Square",
+        }
+    }
+}
+
+// How the viewport draws a run: the palette as premultiplied RGBA, the vertex markers and
+// the curve width. Config codes carry it so a shared run looks the same when opened
+// elsewhere; only the GUI draws with it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Appearance {
+    pub background: [u8; 4],
+    pub curve: [u8; 4],
+    pub vertex: [u8; 4],
+    pub constraint_fill: [u8; 4],
+    pub constraint_stroke: [u8; 4],
+    pub vertex_marker: VertexMarker,
+    // Marker radius and curve stroke width in pixels.
+    pub vertex_marker_size: f32,
+    pub line_width: f32,
+}
+
+impl Default for Appearance {
+    // The GUI's dark theme.
+    fn default() -> Self {
+        Self {
+            background: [20, 20, 20, 255],
+            curve: [144, 238, 144, 255],
+            vertex: [250, 220, 130, 255],
+            constraint_fill: [90, 120, 140, 28],
+            constraint_stroke: [120, 160, 180, 80],
+            vertex_marker: VertexMarker::FilledCircle,
+            vertex_marker_size: 3.0,
+            line_width: 2.0,
+        }
+    }
+}
+
+// Rasterize the polygon outline into a new image, fitting its bounds with a small margin.
+pub fn render_polygon(polygon: &Polygon, width: u32, height: u32, style: &RenderStyle) -> RgbaImage {
+    render_polygons(std::slice::from_ref(polygon), width, height, style)
+}
+
+// Rasterize every outline into one image, fitting their combined bounds with a small margin.
+pub fn render_polygons(
+    polygons: &[Polygon],
+    width: u32,
+    height: u32,
+    style: &RenderStyle,
+) -> RgbaImage {
+    let mut image = RgbaImage::from_pixel(width, height, Rgba(style.background));
+    let Some((min, max)) = polygons
+        .iter()
+        .filter_map(Polygon::bounds)
+        .reduce(|(a0, a1), (b0, b1)| (a0.min(b0), a1.max(b1)))
+    else {
+        return image;
+    };
+
+    let center = (min + max) * 0.5;
+    let extent_x = (max.x - min.x).max(1e-6);
+    let extent_y = (max.y - min.y).max(1e-6);
+    let scale = (width as Real / extent_x).min(height as Real / extent_y) * 0.9;
+    // World-to-pixel transform with y pointing up, as in the viewport.
+    let to_pixel = |p: Vec2| {
+        Vec2::new(
+            width as Real * 0.5 + (p.x - center.x) * scale,
+            height as Real * 0.5 - (p.y - center.y) * scale,
+        )
+    };
+
+    // Fill everything first so no interior covers a neighbour's outline.
+    if let Some(fill) = style.fill {
+        for [a, b, c] in polygons.iter().flat_map(Polygon::triangulate) {
+            fill_triangle(&mut image, to_pixel(a), to_pixel(b), to_pixel(c), fill);
+        }
+    }
+    for polygon in polygons {
+        let closed = style.closed && polygon.is_closed();
+        for (a, b) in edges(polygon.vertices(), closed) {
+            draw_segment(&mut image, to_pixel(a), to_pixel(b), style.line_width, style.curve);
+        }
+    }
+    image
+}
+
+// Antialiased thick segment: pixel coverage falls off over one pixel at the stroke edge.
+fn draw_segment(image: &mut RgbaImage, a: Vec2, b: Vec2, width: f32, color: [u8; 4]) {
+    let half = (width as Real * 0.5).max(0.5);
+    let pad = half + 1.0;
+    let max_x = image.width() as Real - 1.0;
+    let max_y = image.height() as Real - 1.0;
+    let x0 = (a.x.min(b.x) - pad).floor().max(0.0);
+    let x1 = (a.x.max(b.x) + pad).ceil().min(max_x);
+    let y0 = (a.y.min(b.y) - pad).floor().max(0.0);
+    let y1 = (a.y.max(b.y) + pad).ceil().min(max_y);
+    if x0 > x1 || y0 > y1 {
+        return;
+    }
+
+    for y in y0 as u32..=y1 as u32 {
+        for x in x0 as u32..=x1 as u32 {
+            let center = Vec2::new(x as Real + 0.5, y as Real + 0.5);
+            let dist = center.distance(closest_point_on_segment(center, a, b));
+            let coverage = (half + 0.5 - dist).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                blend(image.get_pixel_mut(x, y), color, coverage);
+            }
+        }
+    }
+}
+
+// Fills the pixels whose centers lie in the triangle, in either winding. No antialiasing,
+// so adjacent triangles meet without seams; the stroke covers the rim.
+fn fill_triangle(image: &mut RgbaImage, a: Vec2, b: Vec2, c: Vec2, color: [u8; 4]) {
+    let max_x = image.width() as Real - 1.0;
+    let max_y = image.height() as Real - 1.0;
+    let x0 = a.x.min(b.x).min(c.x).floor().max(0.0);
+    let x1 = a.x.max(b.x).max(c.x).ceil().min(max_x);
+    let y0 = a.y.min(b.y).min(c.y).floor().max(0.0);
+    let y1 = a.y.max(b.y).max(c.y).ceil().min(max_y);
+    if x0 > x1 || y0 > y1 {
+        return;
+    }
+
+    let edge = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p);
+    let sign = edge(a, b, c).signum();
+    for y in y0 as u32..=y1 as u32 {
+        for x in x0 as u32..=x1 as u32 {
+            let center = Vec2::new(x as Real + 0.5, y as Real + 0.5);
+            // Half-open on one side so pixels on a shared edge are filled once.
+            let covered = [(a, b), (b, c), (c, a)].iter().all(|&(p, q)| {
+                let e = edge(p, q, center) * sign;
+                e > 0.0 || (e == 0.0 && (q.y > p.y || (q.y == p.y && q.x < p.x)))
+            });
+            if covered {
+                blend(image.get_pixel_mut(x, y), color, 1.0);
+            }
+        }
+    }
+}
+
+// Side of one contact sheet cell in pixels.
+pub const CONTACT_SHEET_CELL: u32 = 256;
+
+// Grid of `cols` columns, filled row by row with one cell per seed. Each cell runs
+// `steps` steps of `base` from the GUI's default unit 32-gon, renders the result in the
+// default style and labels it with its seed in the top-left corner.
+pub fn render_contact_sheet(
+    base: SimParams,
+    seeds: &[u64],
+    steps: usize,
+    cols: usize,
+) -> RgbaImage {
+    let cols = cols.max(1);
+    let rows = seeds.len().div_ceil(cols);
+    let style = RenderStyle::default();
+    let cell = CONTACT_SHEET_CELL;
+    let mut sheet =
+        RgbaImage::from_pixel(cols as u32 * cell, rows as u32 * cell, Rgba(style.background));
+    for (k, &seed) in seeds.iter().enumerate() {
+        let mut sim = Simulation::new(seed);
+        sim.rebuild_polygon(1.0, 32);
+        for _ in 0..steps {
+            sim.step(base);
+        }
+        let mut image = render_polygon(sim.polygon(), cell, cell, &style);
+        draw_digits(&mut image, &seed.to_string(), style.curve);
+        let (x, y) = ((k % cols) as u32 * cell, (k / cols) as u32 * cell);
+        image::imageops::replace(&mut sheet, &image, x.into(), y.into());
+    }
+    sheet
+}
+
+// 3x5 bitmap glyphs for 0-9, one row per byte with the leftmost pixel in bit 2.
+const DIGIT_GLYPHS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+// Writes the digits of `text` at twice the glyph size from the top-left corner; other
+// characters leave a gap.
+fn draw_digits(image: &mut RgbaImage, text: &str, color: [u8; 4]) {
+    const SCALE: u32 = 2;
+    const MARGIN: u32 = 4;
+    for (k, c) in text.chars().enumerate() {
+        let Some(glyph) = c.to_digit(10).map(|d| DIGIT_GLYPHS[d as usize]) else {
+            continue;
+        };
+        let left = MARGIN + k as u32 * 4 * SCALE;
+        for (row, bits) in glyph.iter().enumerate() {
+            for col in 0..3 {
+                if bits >> (2 - col) & 1 == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        let (x, y) = (left + col * SCALE + dx, MARGIN + row as u32 * SCALE + dy);
+                        if x < image.width() && y < image.height() {
+                            blend(image.get_pixel_mut(x, y), color, 1.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn blend(pixel: &mut Rgba<u8>, color: [u8; 4], coverage: Real) {
+    let alpha = coverage * color[3] as Real / 255.0;
+    for (dst, src) in pixel.0.iter_mut().zip(color).take(3) {
+        let d = *dst as Real;
+        *dst = (d + (src as Real - d) * alpha).round() as u8;
+    }
+    pixel.0[3] = pixel.0[3].max((alpha * 255.0).round() as u8);
+}
+
+// Writes numbered PNG frames (`frame_00000.png`, ...) into a directory.
+#[derive(Debug)]
+pub struct FrameRecorder {
+    dir: PathBuf,
+    frames_written: usize,
+}
+
+impl FrameRecorder {
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self, GrowthError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            frames_written: 0,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.frames_written
+    }
+
+    pub fn write_frame(&mut self, image: &RgbaImage) -> Result<PathBuf, GrowthError> {
+        let path = self.dir.join(format!("frame_{:05}.png", self.frames_written));
+        image.save(&path)?;
+        self.frames_written += 1;
+        Ok(path)
+    }
+}
+
+// Writes one SVG per frame (`frame_00000.svg`, ...) into `dir` and returns the paths; a
+// frame is the set of polygons at one moment, each written as its own path. Every frame
+// shares the viewBox of the combined bounds so playback does not jump, and strokes keep a
+// fixed on-screen width whatever the scale. Independent of the GUI.
+pub fn render_svg_frames<F: AsRef<[Polygon]>>(
+    history: &[F],
+    dir: &Path,
+) -> Result<Vec<PathBuf>, GrowthError> {
+    std::fs::create_dir_all(dir)?;
+    // World y points up, so frames flip it as `to_svg_document` does.
+    let (x, y, w, h) = shared_view_box(history.iter().flat_map(|f| f.as_ref()));
+
+    let mut paths = Vec::with_capacity(history.len());
+    for (i, frame) in history.iter().enumerate() {
+        let mut outlines = String::new();
+        for polygon in frame.as_ref() {
+            outlines.push_str(&format!(
+                "<path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"1\" \
+                 vector-effect=\"non-scaling-stroke\"/>\n",
+                polygon.to_svg_path()
+            ));
+        }
+        let svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {w} {h}\">\n\
+             <g transform=\"scale(1 -1)\">\n\
+             {outlines}\
+             </g>\n\
+             </svg>\n"
+        );
+        let path = dir.join(format!("frame_{i:05}.svg"));
+        std::fs::write(&path, svg)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
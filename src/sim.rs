@@ -1,34 +1,441 @@
-use crate::geometry::{Polygon, Vec2};
+use crate::error::GrowthError;
+use crate::force::{Force, ForceContext, PolygonMeta};
+use crate::geometry::consts::{PI, TAU};
+use crate::geometry::{
+    centroid_of_kind, closest_point_on_segment, edges, next_index, outward_sign, prev_index,
+    signed_area, vertex_curvature, CentroidKind, Polygon, Real, Vec2,
+};
+use crate::integrator::{Euler, Integrator};
+use crate::split::{CurvatureSplit, EdgeContext, LengthSplit, SplitPredicate};
 use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
-use std::f64::consts::PI;
+use rand::{Rng, RngCore, SeedableRng};
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SimParams {
     pub edge_regularization_enabled: bool,
-    pub target_edge_length: f64,
-    pub edge_stiffness: f64,
+    pub target_edge_length: Real,
+    pub edge_stiffness: Real,
+    // Stiffness for stretched (`len > target`) and compressed edges; `None` falls back to
+    // `edge_stiffness`. A compression stiffness of 0 gives rope-like edges.
+    pub edge_stiffness_tension: Option<Real>,
+    pub edge_stiffness_compression: Option<Real>,
+    // Springs leave an edge alone while `|len - target|` is at most this; beyond it the
+    // full error is corrected. 0 always corrects.
+    pub edge_dead_zone: Real,
     pub repulsion_enabled: bool,
-    pub repulsion_radius: f64,
-    pub repulsion_strength: f64,
+    pub repulsion_radius: Real,
+    pub repulsion_strength: Real,
+    // Worker threads for repulsion; results are bit-identical for any count.
+    pub repulsion_threads: usize,
+    // Approximation: each vertex is pushed only by its K nearest non-adjacent neighbors
+    // within the radius, bounding per-vertex cost. 0 means no cap.
+    pub repulsion_max_neighbors: usize,
+    // Vertices this many or fewer steps apart around the loop never repel each other;
+    // 1 skips only immediate neighbors.
+    pub repulsion_neighbor_exclusion: usize,
+    // Scale `repulsion_strength` by `radius / min_self_distance`, up to
+    // `adaptive_repulsion_max`, as separate parts of the curve approach each other.
+    pub adaptive_repulsion: bool,
+    pub adaptive_repulsion_max: Real,
+    // Vertex-to-edge repulsion over `repulsion_radius`, accelerated by `EdgeQuadtree`.
+    pub edge_repulsion_enabled: bool,
+    pub edge_repulsion_strength: Real,
     pub growth_enabled: bool,
-    pub growth_rate: f64,
+    pub growth_rate: Real,
+    // Enclosed area to grow (or shrink) toward. When set, `growth_rate` is the top speed
+    // and growth slows in proportion to the relative area error, stopping at the goal.
+    pub growth_area_goal: Option<Real>,
+    // Hard ceiling: growth and splitting switch off while the enclosed area exceeds it.
+    pub max_area: Option<Real>,
+    // Floor: a step that leaves the enclosed area below this scales the polygon up about
+    // its centroid to exactly this area.
+    pub min_area: Option<Real>,
+    // Guarded erosion: with negative growth, each vertex moves only as far as keeps its
+    // adjacent edges above this length and itself this far from the centroid.
+    pub growth_erosion_min_edge: Option<Real>,
+    pub growth_conserve_perimeter: bool,
+    pub growth_curvature_filter: GrowthCurvatureFilter,
+    // Scales growth by 1 / (1 + gain * neighbors within the repulsion radius).
+    pub growth_density_gain: Real,
+    // Blend of each growth normal with its neighbors' mean normal, 0 (off) to 1.
+    pub growth_normal_smoothing: Real,
+    // Growth tangents span vertices `i - k` to `i + k`, i.e. the sum of the 2k edges
+    // around each vertex. 1 uses only the adjacent vertices.
+    pub growth_tangent_window: usize,
+    // Invisible points that only damp growth: within `radius`, growth is scaled by
+    // `1 - strength * (1 - d / radius)`, compounding where inhibitors overlap.
+    pub growth_inhibitors: [Option<Attractor>; MAX_GROWTH_INHIBITORS],
     pub split_enabled: bool,
-    pub split_length: f64,
+    pub split_length: Real,
+    pub auto_retarget_on_split: bool,
+    // Steps after a split during which the vertices of the split edge, old and new,
+    // neither grow nor split again. 0 disables the refractory period.
+    pub split_refractory_generations: u64,
+    // Curvature-aware splitting: a long edge only splits when the absolute curvature at
+    // one of its endpoints exceeds this, so flat stretches keep few vertices.
+    pub split_min_curvature: Option<Real>,
+    // Edges shorter than this collapse into one vertex each step, shortest first with
+    // ties going to the lower index, so the result does not depend on the platform.
+    pub merge_length: Option<Real>,
     pub constraint_enabled: bool,
     pub constraint_shape: ConstraintShape,
-    pub constraint_size: f64,
-    pub constraint_strength: f64,
+    pub constraint_size: Real,
+    pub constraint_strength: Real,
     pub constraint_falloff: ConstraintFalloff,
+    // Generations over which strength ramps linearly from zero after the constraint is
+    // enabled, avoiding a snap; 0 applies full strength at once.
+    pub constraint_ramp_generations: u64,
+    // Push interior vertices out instead, turning the area into an obstacle.
+    pub constraint_inverted: bool,
+    // Center of the area at generation 0.
+    pub constraint_center: Vec2,
+    // Change in size (or rectangle half extents) per generation, never shrinking below
+    // zero; 0 keeps the area fixed.
+    pub constraint_size_rate: Real,
+    // Distance the center moves per generation.
+    pub constraint_center_velocity: Vec2,
     pub jitter_enabled: bool,
-    pub jitter_strength: f64,
+    pub jitter_strength: Real,
+    pub attractor: Option<Attractor>,
+    // Pull toward the reference centroid; negative pushes away from it.
+    pub centering_strength: Real,
+    // Center used by centering and perimeter-conserving rescale.
+    pub centroid_kind: CentroidKind,
+    pub force_order: [ForceKind; FORCE_KIND_COUNT],
+    pub convergence_threshold: Real,
 }
 
+// Structural summary of one `Simulation::step`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StepReport {
+    pub vertices_added: usize,
+    pub vertices_removed: usize,
+    pub max_displacement: Real,
+    // Max displacement was at or below `SimParams::convergence_threshold`.
+    pub converged: bool,
+    // The step started above `SimParams::max_area`, so it neither grew nor split.
+    pub area_capped: bool,
+    // `grow_to_vertex_count` stopped because the target was reached, not the step cap.
+    pub target_reached: bool,
+}
+
+// Force passes that accumulate into the per-step displacement buffer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForceKind {
+    Spring,
+    Repulsion,
+    EdgeRepulsion,
+    Growth,
+    Constraint,
+    Attractor,
+    Centering,
+    Jitter,
+}
+
+pub const FORCE_KIND_COUNT: usize = 8;
+
+impl ForceKind {
+    // Position in `DEFAULT_FORCE_ORDER`, used to index per-force tables.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    // Whether the pass is switched on, i.e. does more than return immediately.
+    pub fn is_enabled(self, params: &SimParams) -> bool {
+        match self {
+            Self::Spring => params.edge_regularization_enabled,
+            Self::Repulsion => params.repulsion_enabled,
+            Self::EdgeRepulsion => params.edge_repulsion_enabled,
+            Self::Growth => params.growth_enabled,
+            Self::Constraint => params.constraint_enabled,
+            Self::Attractor => params.attractor.is_some(),
+            Self::Centering => params.centering_strength != 0.0,
+            Self::Jitter => params.jitter_enabled,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Spring => "Springs",
+            Self::Repulsion => "Repulsion",
+            Self::EdgeRepulsion => "Edge Repulsion",
+            Self::Growth => "Growth",
+            Self::Constraint => "Constraint",
+            Self::Attractor => "Attractor",
+            Self::Centering => "Centering",
+            Self::Jitter => "Jitter",
+        }
+    }
+}
+
+// Wall-clock time spent in the parts of one step. Disabled force passes stay zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StepProfile {
+    // Indexed by `ForceKind::index`; a pass listed twice in the order sums both runs.
+    pub forces: [Duration; FORCE_KIND_COUNT],
+    pub split: Duration,
+    pub total: Duration,
+}
+
+impl StepProfile {
+    pub fn force(&self, kind: ForceKind) -> Duration {
+        self.forces[kind.index()]
+    }
+}
+
+// Fixed capacity keeps `SimParams` `Copy`.
+pub const MAX_GROWTH_INHIBITORS: usize = 8;
+
+// Entries kept by `Simulation::vertex_count_log`; older generations are dropped.
+pub const VERTEX_COUNT_LOG_CAPACITY: usize = 4096;
+
+// Seed and edge length of `Simulation::benchmark`. Half the default repulsion radius, so
+// each vertex repels a few neighbors beyond the excluded ones.
+pub const BENCHMARK_SEED: u64 = 1;
+pub const BENCHMARK_EDGE_LENGTH: Real = 0.075;
+
+// Default pass order: springs, vertex repulsion, edge repulsion, growth, constraint,
+// attractor, centering, then jitter.
+// All passes read the same position snapshot and sum into one buffer, so the order
+// only changes floating-point summation order and when jitter draws from the RNG.
+// Each kind should appear exactly once; duplicates apply that force twice.
+pub const DEFAULT_FORCE_ORDER: [ForceKind; FORCE_KIND_COUNT] = [
+    ForceKind::Spring,
+    ForceKind::Repulsion,
+    ForceKind::EdgeRepulsion,
+    ForceKind::Growth,
+    ForceKind::Constraint,
+    ForceKind::Attractor,
+    ForceKind::Centering,
+    ForceKind::Jitter,
+];
+
+impl Default for SimParams {
+    // Defaults mirror the GUI's initial control values.
+    fn default() -> Self {
+        Self {
+            edge_regularization_enabled: true,
+            target_edge_length: regular_ngon_edge_length(1.0, 32),
+            edge_stiffness: 0.2,
+            edge_stiffness_tension: None,
+            edge_stiffness_compression: None,
+            edge_dead_zone: 0.0,
+            repulsion_enabled: true,
+            repulsion_radius: 0.15,
+            repulsion_strength: 0.01,
+            repulsion_threads: 1,
+            repulsion_max_neighbors: 0,
+            repulsion_neighbor_exclusion: 1,
+            adaptive_repulsion: false,
+            adaptive_repulsion_max: 4.0,
+            edge_repulsion_enabled: false,
+            edge_repulsion_strength: 0.01,
+            growth_enabled: false,
+            growth_rate: 0.001,
+            growth_area_goal: None,
+            max_area: None,
+            min_area: None,
+            growth_erosion_min_edge: None,
+            growth_conserve_perimeter: false,
+            growth_curvature_filter: GrowthCurvatureFilter::All,
+            growth_density_gain: 0.0,
+            growth_normal_smoothing: 0.0,
+            growth_tangent_window: 1,
+            growth_inhibitors: [None; MAX_GROWTH_INHIBITORS],
+            split_enabled: false,
+            split_length: 0.25,
+            auto_retarget_on_split: false,
+            split_refractory_generations: 0,
+            split_min_curvature: None,
+            merge_length: None,
+            constraint_enabled: false,
+            constraint_shape: ConstraintShape::Circle,
+            constraint_size: 1.5,
+            constraint_strength: 0.1,
+            constraint_falloff: ConstraintFalloff::Linear,
+            constraint_ramp_generations: 0,
+            constraint_inverted: false,
+            constraint_center: Vec2::ZERO,
+            constraint_size_rate: 0.0,
+            constraint_center_velocity: Vec2::ZERO,
+            jitter_enabled: true,
+            jitter_strength: 0.005,
+            attractor: None,
+            centering_strength: 0.0,
+            centroid_kind: CentroidKind::Area,
+            force_order: DEFAULT_FORCE_ORDER,
+            convergence_threshold: 1e-5,
+        }
+    }
+}
+
+macro_rules! builder_setters {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        $(
+            pub fn $field(mut self, value: $ty) -> Self {
+                self.params.$field = value;
+                self
+            }
+        )*
+    };
+}
+
+// Chainable construction of `SimParams` starting from the GUI defaults.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimParamsBuilder {
+    params: SimParams,
+}
+
+impl SimParamsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    builder_setters! {
+        edge_regularization_enabled: bool,
+        target_edge_length: Real,
+        edge_stiffness: Real,
+        edge_stiffness_tension: Option<Real>,
+        edge_stiffness_compression: Option<Real>,
+        edge_dead_zone: Real,
+        repulsion_enabled: bool,
+        repulsion_radius: Real,
+        repulsion_strength: Real,
+        repulsion_threads: usize,
+        repulsion_max_neighbors: usize,
+        repulsion_neighbor_exclusion: usize,
+        adaptive_repulsion: bool,
+        adaptive_repulsion_max: Real,
+        edge_repulsion_enabled: bool,
+        edge_repulsion_strength: Real,
+        growth_enabled: bool,
+        growth_rate: Real,
+        growth_area_goal: Option<Real>,
+        max_area: Option<Real>,
+        min_area: Option<Real>,
+        growth_erosion_min_edge: Option<Real>,
+        growth_conserve_perimeter: bool,
+        growth_curvature_filter: GrowthCurvatureFilter,
+        growth_density_gain: Real,
+        growth_normal_smoothing: Real,
+        growth_tangent_window: usize,
+        growth_inhibitors: [Option<Attractor>; MAX_GROWTH_INHIBITORS],
+        split_enabled: bool,
+        split_length: Real,
+        auto_retarget_on_split: bool,
+        split_refractory_generations: u64,
+        split_min_curvature: Option<Real>,
+        merge_length: Option<Real>,
+        constraint_enabled: bool,
+        constraint_shape: ConstraintShape,
+        constraint_size: Real,
+        constraint_strength: Real,
+        constraint_falloff: ConstraintFalloff,
+        constraint_ramp_generations: u64,
+        constraint_inverted: bool,
+        constraint_center: Vec2,
+        constraint_size_rate: Real,
+        constraint_center_velocity: Vec2,
+        jitter_enabled: bool,
+        jitter_strength: Real,
+        attractor: Option<Attractor>,
+        centering_strength: Real,
+        centroid_kind: CentroidKind,
+        force_order: [ForceKind; FORCE_KIND_COUNT],
+        convergence_threshold: Real,
+    }
+
+    pub fn build(self) -> SimParams {
+        self.params
+    }
+}
+
+// Point that pulls nearby vertices toward itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attractor {
+    pub position: Vec2,
+    pub radius: Real,
+    pub strength: Real,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConstraintShape {
     Circle,
     Square,
     Triangle,
+    // Axis-aligned box with explicit half extents; ignores `constraint_size`.
+    Rectangle { half_width: Real, half_height: Real },
+}
+
+impl ConstraintShape {
+    // Axis-aligned `(min, max)` of the area for the given `constraint_size`.
+    pub fn bounds(self, size: Real) -> (Vec2, Vec2) {
+        match self {
+            Self::Circle | Self::Square => (Vec2::splat(-size), Vec2::splat(size)),
+            Self::Rectangle {
+                half_width,
+                half_height,
+            } => {
+                let half = Vec2::new(half_width, half_height);
+                (-half, half)
+            }
+            Self::Triangle => {
+                let [a, b, c] = triangle_vertices(size);
+                (a.min(b).min(c), a.max(b).max(c))
+            }
+        }
+    }
+
+    // Rectangles with both half extents changed by `amount`, clamped at zero; the other
+    // shapes take their size from `constraint_size` and are returned as is.
+    pub fn grown(self, amount: Real) -> Self {
+        match self {
+            Self::Rectangle {
+                half_width,
+                half_height,
+            } => Self::Rectangle {
+                half_width: (half_width + amount).max(0.0),
+                half_height: (half_height + amount).max(0.0),
+            },
+            shape => shape,
+        }
+    }
+
+    // Whether `point` lies in the area, boundary included, for the given size, center
+    // and counterclockwise rotation in radians. The same test the constraint force uses,
+    // which never rotates the area.
+    pub fn contains(&self, point: Vec2, size: Real, center: Vec2, rotation: Real) -> bool {
+        let local = Vec2::from_angle(-rotation).rotate(point - center);
+        constraint_push(local, *self, size) == Vec2::ZERO
+    }
+}
+
+// Box covering the polygon and, when given, a constraint area of `(shape, size, center)`.
+pub fn framing_bounds(
+    polygon: &Polygon,
+    constraint: Option<(ConstraintShape, Real, Vec2)>,
+) -> Option<(Vec2, Vec2)> {
+    let area = constraint.map(|(shape, size, center)| {
+        let (min, max) = shape.bounds(size);
+        (min + center, max + center)
+    });
+    match (polygon.bounds(), area) {
+        (Some((min, max)), Some((area_min, area_max))) => {
+            Some((min.min(area_min), max.max(area_max)))
+        }
+        (bounds, area) => bounds.or(area),
+    }
+}
+
+// Restricts growth to vertices by the sign of their curvature relative to the winding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthCurvatureFilter {
+    All,
+    ConvexOnly,
+    ConcaveOnly,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -37,197 +444,2002 @@ pub enum ConstraintFalloff {
     Quadratic,
 }
 
-#[derive(Debug)]
+// Uniform grid bucketing point indices for fixed-radius neighbor queries.
+#[derive(Debug, Clone, Default)]
+pub struct SpatialHash {
+    cell_size: Real,
+    // `(row, column, index)` of every point, sorted, so each row of cells is one run
+    // and `rebuild` can reuse the allocation.
+    entries: Vec<(i64, i64, usize)>,
+}
+
+impl SpatialHash {
+    pub fn build(points: &[Vec2], cell_size: Real) -> Self {
+        let mut hash = Self::default();
+        hash.rebuild(points, cell_size);
+        hash
+    }
+
+    // `build` in place, keeping the previous allocation when it is large enough.
+    pub fn rebuild(&mut self, points: &[Vec2], cell_size: Real) {
+        self.cell_size = cell_size.max(1e-9);
+        self.entries.clear();
+        for (i, p) in points.iter().copied().enumerate() {
+            let (cx, cy) = self.cell_of(p);
+            self.entries.push((cy, cx, i));
+        }
+        self.entries.sort_unstable();
+    }
+
+    pub fn cell_size(&self) -> Real {
+        self.cell_size
+    }
+
+    // Debug view: every non-empty cell with its point count, sorted by row then column.
+    pub fn occupied_cells(&self) -> Vec<((i64, i64), usize)> {
+        self.entries
+            .chunk_by(|a, b| (a.0, a.1) == (b.0, b.1))
+            .map(|run| ((run[0].1, run[0].0), run.len()))
+            .collect()
+    }
+
+    // World-space (min, max) corners of a cell.
+    pub fn cell_bounds(&self, (cx, cy): (i64, i64)) -> (Vec2, Vec2) {
+        let min = Vec2::new(cx as Real, cy as Real) * self.cell_size;
+        (min, min + Vec2::splat(self.cell_size))
+    }
+
+    fn cell_of(&self, p: Vec2) -> (i64, i64) {
+        (
+            (p.x / self.cell_size).floor() as i64,
+            (p.y / self.cell_size).floor() as i64,
+        )
+    }
+
+    // Visit indices of `points` within `radius` of `p`. Cells are scanned row by row
+    // and indices ascend within a cell, so the visiting order is deterministic.
+    pub fn for_each_within(&self, points: &[Vec2], p: Vec2, radius: Real, mut f: impl FnMut(usize)) {
+        let (min_x, min_y) = self.cell_of(p - Vec2::splat(radius));
+        let (max_x, max_y) = self.cell_of(p + Vec2::splat(radius));
+        let radius_sq = radius * radius;
+        for cy in min_y..=max_y {
+            let first = self.entries.partition_point(|&(y, x, _)| (y, x) < (cy, min_x));
+            let row = self.entries[first..].iter().take_while(|&&(y, x, _)| y == cy && x <= max_x);
+            for &(_, _, i) in row {
+                if points[i].distance_squared(p) <= radius_sq {
+                    f(i);
+                }
+            }
+        }
+    }
+}
+
+// Quadtree over edge bounding boxes for closed polygons; edge `e` joins vertex `e`
+// to vertex `(e + 1) % n`.
+#[derive(Debug, Clone)]
+pub struct EdgeQuadtree {
+    nodes: Vec<QuadNode>,
+    edge_bounds: Vec<(Vec2, Vec2)>,
+}
+
+#[derive(Debug, Clone)]
+struct QuadNode {
+    min: Vec2,
+    max: Vec2,
+    depth: usize,
+    children: Option<[usize; 4]>,
+    // Edges stored here: all edges of a leaf, or those straddling a split line.
+    edges: Vec<usize>,
+}
+
+const QUADTREE_NODE_CAPACITY: usize = 8;
+const QUADTREE_MAX_DEPTH: usize = 10;
+
+impl EdgeQuadtree {
+    pub fn build(positions: &[Vec2]) -> Self {
+        let n = positions.len();
+        let edge_bounds: Vec<(Vec2, Vec2)> = (0..n)
+            .map(|e| {
+                let a = positions[e];
+                let b = positions[next_index(e, n)];
+                (a.min(b), a.max(b))
+            })
+            .collect();
+
+        let (min, max) = edge_bounds
+            .iter()
+            .fold((Vec2::splat(Real::INFINITY), Vec2::splat(Real::NEG_INFINITY)), |acc, b| {
+                (acc.0.min(b.0), acc.1.max(b.1))
+            });
+        let mut tree = Self {
+            nodes: vec![QuadNode {
+                min,
+                max,
+                depth: 0,
+                children: None,
+                edges: Vec::new(),
+            }],
+            edge_bounds,
+        };
+        for e in 0..n {
+            tree.insert(0, e);
+        }
+        tree
+    }
+
+    fn insert(&mut self, node: usize, edge: usize) {
+        if let Some(children) = self.nodes[node].children {
+            match self.child_containing(children, edge) {
+                Some(child) => self.insert(child, edge),
+                None => self.nodes[node].edges.push(edge),
+            }
+            return;
+        }
+
+        self.nodes[node].edges.push(edge);
+        if self.nodes[node].edges.len() > QUADTREE_NODE_CAPACITY
+            && self.nodes[node].depth < QUADTREE_MAX_DEPTH
+        {
+            self.subdivide(node);
+        }
+    }
+
+    fn subdivide(&mut self, node: usize) {
+        let QuadNode { min, max, depth, .. } = self.nodes[node];
+        let mid = (min + max) * 0.5;
+        let quadrants = [
+            (min, mid),
+            (Vec2::new(mid.x, min.y), Vec2::new(max.x, mid.y)),
+            (Vec2::new(min.x, mid.y), Vec2::new(mid.x, max.y)),
+            (mid, max),
+        ];
+        let first = self.nodes.len();
+        for (qmin, qmax) in quadrants {
+            self.nodes.push(QuadNode {
+                min: qmin,
+                max: qmax,
+                depth: depth + 1,
+                children: None,
+                edges: Vec::new(),
+            });
+        }
+        let children = [first, first + 1, first + 2, first + 3];
+        self.nodes[node].children = Some(children);
+
+        // Push down edges that fit entirely inside one quadrant.
+        let edges = std::mem::take(&mut self.nodes[node].edges);
+        for edge in edges {
+            match self.child_containing(children, edge) {
+                Some(child) => self.nodes[child].edges.push(edge),
+                None => self.nodes[node].edges.push(edge),
+            }
+        }
+    }
+
+    fn child_containing(&self, children: [usize; 4], edge: usize) -> Option<usize> {
+        let (emin, emax) = self.edge_bounds[edge];
+        children.into_iter().find(|&c| {
+            let n = &self.nodes[c];
+            emin.x >= n.min.x && emin.y >= n.min.y && emax.x <= n.max.x && emax.y <= n.max.y
+        })
+    }
+
+    // Edges whose bounding boxes overlap the square of half-size `radius` around `point`,
+    // in ascending index order so callers accumulate deterministically.
+    pub fn query_near(&self, point: Vec2, radius: Real) -> Vec<usize> {
+        let qmin = point - Vec2::splat(radius);
+        let qmax = point + Vec2::splat(radius);
+        let overlaps = |min: Vec2, max: Vec2| {
+            min.x <= qmax.x && max.x >= qmin.x && min.y <= qmax.y && max.y >= qmin.y
+        };
+
+        let mut found = Vec::new();
+        if self.edge_bounds.is_empty() {
+            return found;
+        }
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let n = &self.nodes[node];
+            if !overlaps(n.min, n.max) {
+                continue;
+            }
+            for &e in &n.edges {
+                let (emin, emax) = self.edge_bounds[e];
+                if overlaps(emin, emax) {
+                    found.push(e);
+                }
+            }
+            if let Some(children) = n.children {
+                stack.extend(children);
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+}
+
+// Cloning captures the complete state, RNG included, so a clone stepped with the same
+// parameters reproduces the original exactly.
+#[derive(Debug, Clone)]
 pub struct Simulation {
-    polygon: Polygon,
+    // Outlines stepped side by side, each with its own force passes and splits; they do
+    // not interact. Single-polygon accessors such as `polygon` describe the first. Never
+    // empty. The per-vertex state below runs over the vertices of every polygon in order.
+    polygons: Vec<Polygon>,
     generation: u64,
+    seed: u64,
     rng: StdRng,
+    // Average edge length after the last split, when auto-retargeting is on.
+    retargeted_edge_length: Option<Real>,
+    // Per-vertex displacement applied by the last step.
+    last_delta: Vec<Vec2>,
+    // Stable identity and birth generation per vertex.
+    vertex_ids: Vec<u64>,
+    vertex_births: Vec<u64>,
+    next_vertex_id: u64,
+    // Per-vertex mass dividing the applied displacement.
+    masses: Vec<Real>,
+    // Remaining refractory steps per vertex.
+    refractory: Vec<u64>,
+    // (generation, vertex count) after each step, oldest first.
+    vertex_count_log: VecDeque<(u64, usize)>,
+    // Vertex id held in place: steps leave it wherever `move_vertex` put it.
+    pinned_vertex: Option<u64>,
+    // Off by default so unprofiled steps never read the clock.
+    profiling: bool,
+    last_profile: Option<StepProfile>,
+    integrator: Box<dyn Integrator>,
+    // Custom passes run after the built-in ones.
+    forces: Vec<Box<dyn Force>>,
+    // Replaces the built-in split rule when set.
+    split_predicate: Option<Box<dyn SplitPredicate>>,
+    // Run vertex repulsion on the GPU when one is available; needs the `gpu` feature.
+    gpu_repulsion: bool,
+    // Grow along the detected outward normals; false flips them.
+    growth_outward: bool,
+    // Generation at which the constraint was last switched on, for its ramp-in.
+    constraint_enabled_at: Option<u64>,
+    scratch: StepBuffers,
+}
+
+// Buffers `step` writes into and then swaps with the live ones, so steady-state steps
+// reuse the previous step's allocations instead of making new ones. Contents between
+// steps are stale and never read.
+#[derive(Debug, Clone, Default)]
+struct StepBuffers {
+    positions: Vec<Vec2>,
+    ids: Vec<u64>,
+    births: Vec<u64>,
+    masses: Vec<Real>,
+    refractory: Vec<u64>,
+    forces: ForceBuffers,
+    // Merge pass: candidate edges, then per-vertex flags.
+    merge_candidates: Vec<(Real, usize)>,
+    merge_touched: Vec<bool>,
+    merge_removed: Vec<bool>,
+    // Per polygon: above `max_area` when the step started.
+    capped: Vec<bool>,
+    // Every polygon's vertices when the step started, hashed for repulsion between them.
+    all_positions: Vec<Vec2>,
+    all_hash: SpatialHash,
+}
+
+// Scratch memory for the built-in force passes, handed to them through `ForceContext`.
+// `step` keeps one set in `StepBuffers` and reuses it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ForceBuffers {
+    hash: SpatialHash,
+    // Capped repulsion's neighbor candidates as `(distance squared, index)`.
+    nearest: Vec<(Real, usize)>,
+    // Arc length from vertex 0, for `min_self_distance`.
+    arc: Vec<Real>,
+    raw_normals: Vec<Option<Vec2>>,
+    normals: Vec<Option<Vec2>>,
+    growth: Vec<Vec2>,
+    // Fraction of each growth step the erosion guard lets through.
+    growth_scales: Vec<Real>,
 }
 
 impl Simulation {
     pub fn new(seed: u64) -> Self {
         Self {
-            polygon: Polygon::new(),
+            polygons: vec![Polygon::new()],
             generation: 0,
+            seed,
             rng: StdRng::seed_from_u64(seed),
+            retargeted_edge_length: None,
+            last_delta: Vec::new(),
+            vertex_ids: Vec::new(),
+            vertex_births: Vec::new(),
+            next_vertex_id: 0,
+            masses: Vec::new(),
+            refractory: Vec::new(),
+            vertex_count_log: VecDeque::with_capacity(VERTEX_COUNT_LOG_CAPACITY),
+            pinned_vertex: None,
+            profiling: false,
+            last_profile: None,
+            integrator: Box::new(Euler),
+            forces: Vec::new(),
+            split_predicate: None,
+            gpu_repulsion: false,
+            growth_outward: true,
+            constraint_enabled_at: None,
+            scratch: StepBuffers::default(),
         }
     }
 
+    // `new` with a regular `sides`-gon of `radius` around the origin in place.
+    pub fn with_ngon(seed: u64, radius: Real, sides: usize) -> Self {
+        let mut sim = Self::new(seed);
+        sim.rebuild_polygon(radius, sides);
+        sim
+    }
+
+    // Fixed starting state for benchmarks and before/after comparisons: a `vertices`-gon
+    // with edges of `BENCHMARK_EDGE_LENGTH`, perturbed by a tenth of that with
+    // `BENCHMARK_SEED`. Identical across runs and platforms of the same precision.
+    pub fn benchmark(vertices: usize) -> Self {
+        let radius = BENCHMARK_EDGE_LENGTH / (2.0 * (PI / vertices as Real).sin());
+        let mut sim = Self::with_ngon(BENCHMARK_SEED, radius, vertices);
+        sim.perturb(BENCHMARK_EDGE_LENGTH * 0.1);
+        sim
+    }
+
+    // `new` with each of `polygons` in place, e.g. every path of an SVG file.
+    pub fn with_polygons(seed: u64, polygons: Vec<Polygon>) -> Self {
+        let mut sim = Self::new(seed);
+        sim.set_polygons(polygons);
+        sim
+    }
+
+    // The first polygon.
     pub fn polygon(&self) -> &Polygon {
-        &self.polygon
+        &self.polygons[0]
+    }
+
+    pub fn polygons(&self) -> &[Polygon] {
+        &self.polygons
+    }
+
+    // Vertices over all polygons; the per-vertex accessors have this many entries.
+    pub fn vertex_count(&self) -> usize {
+        self.polygons.iter().map(Polygon::len).sum()
     }
 
     pub fn generation(&self) -> u64 {
         self.generation
     }
 
+    // New target edge length computed by the last step, if it split edges with
+    // `auto_retarget_on_split` set. Callers own `SimParams`, so copy it back there.
+    pub fn retargeted_edge_length(&self) -> Option<Real> {
+        self.retargeted_edge_length
+    }
+
+    // True when no vertex moved more than `threshold` during the last step.
+    pub fn is_converged(&self, threshold: Real) -> bool {
+        if self.last_delta.is_empty() {
+            return false;
+        }
+        self.last_delta.iter().all(|d| d.length() <= threshold)
+    }
+
+    // Unique id per vertex, over all polygons in order. Ids survive steps; vertices
+    // inserted by splits get fresh ones.
+    pub fn vertex_ids(&self) -> &[u64] {
+        &self.vertex_ids
+    }
+
+    // Steps since each vertex was created.
+    pub fn vertex_ages(&self) -> Vec<u64> {
+        self.vertex_births
+            .iter()
+            .map(|birth| self.generation - birth)
+            .collect()
+    }
+
+    // Mass per vertex; each step's displacement is divided by it. Defaults to 1.0.
+    pub fn masses(&self) -> &[Real] {
+        &self.masses
+    }
+
+    // Replace the per-vertex masses. Split vertices interpolate the masses of the edge
+    // they were inserted into.
+    pub fn set_masses(&mut self, masses: Vec<Real>) -> Result<(), GrowthError> {
+        if masses.len() != self.vertex_count() {
+            return Err(GrowthError::InvalidParameter(format!(
+                "got {} masses for {} vertices",
+                masses.len(),
+                self.vertex_count()
+            )));
+        }
+        if let Some(bad) = masses.iter().find(|m| !(m.is_finite() && **m > 0.0)) {
+            return Err(GrowthError::InvalidParameter(format!(
+                "mass must be positive and finite, got {bad}"
+            )));
+        }
+        self.masses = masses;
+        Ok(())
+    }
+
+    // Steps each vertex has left before it may grow or split again; see
+    // `SimParams::split_refractory_generations`.
+    pub fn refractory(&self) -> &[u64] {
+        &self.refractory
+    }
+
+    // Total vertex count at the start and after each step since the polygons were
+    // installed, up to the last `VERTEX_COUNT_LOG_CAPACITY` entries: rising without bound
+    // means splits are running away, flat means the outline has settled.
+    pub fn vertex_count_log(&self) -> &VecDeque<(u64, usize)> {
+        &self.vertex_count_log
+    }
+
+    // Number all current vertices afresh, born at the current generation, with unit mass,
+    // no refractory period and no pin, and restart the vertex count log.
+    fn reset_vertex_tracking(&mut self) {
+        let n = self.vertex_count() as u64;
+        self.vertex_ids = (0..n).collect();
+        self.vertex_births = vec![self.generation; n as usize];
+        self.next_vertex_id = n;
+        self.masses = vec![1.0; n as usize];
+        self.refractory = vec![0; n as usize];
+        self.pinned_vertex = None;
+        self.vertex_count_log.clear();
+        self.log_vertex_count();
+    }
+
+    fn log_vertex_count(&mut self) {
+        if self.vertex_count_log.len() >= VERTEX_COUNT_LOG_CAPACITY {
+            self.vertex_count_log.pop_front();
+        }
+        self.vertex_count_log.push_back((self.generation, self.vertex_count()));
+    }
+
+    // Record a `StepProfile` for each subsequent step.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiling = enabled;
+        if !enabled {
+            self.last_profile = None;
+        }
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self.profiling
+    }
+
+    // Timings of the last step taken with profiling on.
+    pub fn last_profile(&self) -> Option<&StepProfile> {
+        self.last_profile.as_ref()
+    }
+
+    // Pin the vertex with this id (see `vertex_ids`) so forces no longer move it, or
+    // release the pin with `None`. The pin follows the id through splits.
+    pub fn set_pinned_vertex(&mut self, id: Option<u64>) {
+        self.pinned_vertex = id;
+    }
+
+    pub fn pinned_vertex(&self) -> Option<u64> {
+        self.pinned_vertex
+    }
+
+    // Place one vertex directly, e.g. while the user drags it. Indices run over all
+    // polygons in order, as `vertex_ids` does; out-of-range ones are ignored.
+    pub fn move_vertex(&mut self, index: usize, position: Vec2) {
+        let mut vertices = self.polygons.iter_mut().flat_map(|p| p.vertices_mut());
+        if let Some(v) = vertices.nth(index) {
+            *v = position;
+        }
+    }
+
+    pub fn integrator(&self) -> &dyn Integrator {
+        self.integrator.as_ref()
+    }
+
+    // Swap the update rule used by `step`; `Euler` by default.
+    pub fn set_integrator(&mut self, integrator: Box<dyn Integrator>) {
+        self.integrator = integrator;
+    }
+
+    // Register a custom pass. Every force evaluation runs `force_stack`: the built-in
+    // passes in `params.force_order`, then these in the order added, before mass scaling.
+    pub fn add_force(&mut self, force: Box<dyn Force>) {
+        self.forces.push(force);
+    }
+
+    pub fn forces(&self) -> &[Box<dyn Force>] {
+        &self.forces
+    }
+
+    pub fn clear_forces(&mut self) {
+        self.forces.clear();
+    }
+
+    // Decide splits with `predicate` instead of `split_length` and `split_min_curvature`.
+    // `split_enabled` still switches the pass, `split_length` still sets the piece count
+    // and refractory edges are still skipped.
+    pub fn set_split_predicate(&mut self, predicate: Box<dyn SplitPredicate>) {
+        self.split_predicate = Some(predicate);
+    }
+
+    pub fn split_predicate(&self) -> Option<&dyn SplitPredicate> {
+        self.split_predicate.as_deref()
+    }
+
+    pub fn clear_split_predicate(&mut self) {
+        self.split_predicate = None;
+    }
+
+    // Offload vertex repulsion to the GPU. Without an adapter, and for neighbor caps the
+    // kernel does not cover, the CPU pass runs as before. Results differ from the CPU
+    // within `gpu::GPU_REPULSION_TOLERANCE`, so runs are no longer bit-reproducible.
+    #[cfg(feature = "gpu")]
+    pub fn set_gpu_repulsion(&mut self, enabled: bool) {
+        self.gpu_repulsion = enabled;
+    }
+
+    pub fn gpu_repulsion(&self) -> bool {
+        self.gpu_repulsion
+    }
+
+    // Growth follows the outward normal the winding implies; `false` reverses it, for
+    // loaded shapes whose winding or self-overlap fools that detection. Curvature
+    // filters still judge convexity by the winding.
+    pub fn set_growth_outward(&mut self, outward: bool) {
+        self.growth_outward = outward;
+    }
+
+    pub fn growth_outward(&self) -> bool {
+        self.growth_outward
+    }
+
+    // Constraint strength after the ramp-in: zero at the generation the constraint was
+    // enabled, reaching `constraint_strength` after `constraint_ramp_generations`.
+    pub fn effective_constraint_strength(&self, params: &SimParams) -> Real {
+        if params.constraint_ramp_generations == 0 {
+            return params.constraint_strength;
+        }
+        // Not stepped with the constraint on yet: the next step is its first.
+        let enabled_at = self.constraint_enabled_at.unwrap_or(self.generation);
+        let elapsed = self.generation.saturating_sub(enabled_at) as Real;
+        let ramp = (elapsed / params.constraint_ramp_generations as Real).min(1.0);
+        params.constraint_strength * ramp
+    }
+
+    // Shape, size and center of the constraint area at the current generation, after
+    // `constraint_size_rate` and `constraint_center_velocity` have acted since generation 0.
+    pub fn constraint_region(&self, params: &SimParams) -> (ConstraintShape, Real, Vec2) {
+        let elapsed = self.generation as Real;
+        let growth = params.constraint_size_rate * elapsed;
+        (
+            params.constraint_shape.grown(growth),
+            (params.constraint_size + growth).max(0.0),
+            params.constraint_center + params.constraint_center_velocity * elapsed,
+        )
+    }
+
+    // Whether any polygon's area is above `params.max_area`. Those neither grow nor split;
+    // the others carry on.
+    pub fn area_capped(&self, params: &SimParams) -> bool {
+        self.polygons.iter().any(|p| polygon_capped(p, params))
+    }
+
+    // `params` as applied this generation to a polygon: ramped-in constraint strength, the
+    // animated constraint area, and growth halted when it is `capped`.
+    fn effective_params(&self, params: SimParams, capped: bool) -> SimParams {
+        let (constraint_shape, constraint_size, constraint_center) =
+            self.constraint_region(&params);
+        SimParams {
+            constraint_strength: self.effective_constraint_strength(&params),
+            constraint_shape,
+            constraint_size,
+            constraint_center,
+            growth_enabled: params.growth_enabled && !capped,
+            split_enabled: params.split_enabled && !capped,
+            ..params
+        }
+    }
+
+    fn pinned_index(&self) -> Option<usize> {
+        let id = self.pinned_vertex?;
+        self.vertex_ids.iter().position(|&v| v == id)
+    }
+
+    // FNV-1a over the generation, vertex positions rounded to multiples of
+    // `STATE_HASH_QUANTUM`, and the next value the RNG would produce. Stable across runs
+    // for the same seed, parameters and step count, so golden tests can pin the dynamics.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = Fnv1a::new();
+        hash.write(&self.generation.to_le_bytes());
+        for v in self.polygons.iter().flat_map(|p| p.vertices()) {
+            for c in [v.x, v.y] {
+                hash.write(&((c / STATE_HASH_QUANTUM).round() as i64).to_le_bytes());
+            }
+        }
+        // StdRng hides its state; its next output is a deterministic function of it.
+        hash.write(&self.rng.clone().next_u64().to_le_bytes());
+        hash.finish()
+    }
+
+    // Seed the RNG was last reset to.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     pub fn reset_seed(&mut self, seed: u64) {
+        self.seed = seed;
         self.rng = StdRng::seed_from_u64(seed);
     }
 
-    pub fn rebuild_polygon(&mut self, radius: f64, sides: usize) {
-        self.polygon = Polygon::regular_ngon(radius, sides);
-        self.generation = 0;
+    // Draw and discard `n` random values, giving a different jitter realization from the
+    // same state while every deterministic force stays the same.
+    pub fn skip_rng(&mut self, n: usize) {
+        for _ in 0..n {
+            self.rng.next_u64();
+        }
+    }
+
+    pub fn rebuild_polygon(&mut self, radius: Real, sides: usize) {
+        self.rebuild_polygon_at(Vec2::ZERO, radius, sides);
+    }
+
+    pub fn rebuild_polygon_at(&mut self, center: Vec2, radius: Real, sides: usize) {
+        self.set_polygon(Polygon::regular_ngon_at(center, radius, sides));
     }
 
-    pub fn step(&mut self, params: SimParams) {
-        let n = self.polygon.len();
-        if n == 0 {
+    // `Polygon::repair` on every polygon in place, keeping per-vertex tracking with its
+    // vertices. Returns whether anything changed.
+    pub fn repair_polygon(&mut self) -> bool {
+        let mut kept = Vec::with_capacity(self.vertex_ids.len());
+        let mut start = 0;
+        for polygon in &mut self.polygons {
+            let n = polygon.len();
+            kept.extend(polygon.repair().into_iter().map(|k| start + k));
+            start += n;
+        }
+        if kept.iter().copied().eq(0..self.vertex_ids.len()) {
+            return false;
+        }
+        self.vertex_ids = kept.iter().map(|&k| self.vertex_ids[k]).collect();
+        self.vertex_births = kept.iter().map(|&k| self.vertex_births[k]).collect();
+        self.masses = kept.iter().map(|&k| self.masses[k]).collect();
+        self.refractory = kept.iter().map(|&k| self.refractory[k]).collect();
+        // The last step's displacements no longer line up with the vertices.
+        self.last_delta.clear();
+        true
+    }
+
+    // Offset each vertex by a seeded random vector no longer than `magnitude`, to give
+    // growth some asymmetry to amplify. A non-positive magnitude draws nothing.
+    pub fn perturb(&mut self, magnitude: Real) {
+        if magnitude <= 0.0 {
             return;
         }
+        for v in self.polygons.iter_mut().flat_map(|p| p.vertices_mut()) {
+            let angle = self.rng.gen_range(0.0..TAU);
+            let radius = self.rng.gen_range(0.0..=magnitude);
+            *v += Vec2::new(angle.cos(), angle.sin()) * radius;
+        }
+    }
 
-        // Snapshot positions so all forces are computed from the same state.
-        let positions = self.polygon.vertices().to_vec();
-        let mut delta = vec![Vec2::ZERO; n];
+    // Install an arbitrary polygon (e.g. loaded from a file) and restart the generation count.
+    pub fn set_polygon(&mut self, polygon: Polygon) {
+        self.set_polygons(vec![polygon]);
+    }
 
-        if params.edge_regularization_enabled
-            && params.edge_stiffness > 0.0
-            && params.target_edge_length > 0.0
-        {
-            // Edge springs keep local spacing near target length.
-            for i in 0..n {
-                let j = (i + 1) % n;
-                let d = positions[j] - positions[i];
-                let len = d.length();
-                if len > 1e-12 {
-                    let dir = d / len;
-                    let error = len - params.target_edge_length;
-                    // Apply equal/opposite correction to edge endpoints.
-                    let correction = dir * (error * params.edge_stiffness * 0.5);
-                    delta[i] += correction;
-                    delta[j] -= correction;
-                }
+    // `set_polygon` for several outlines at once. An empty list installs one empty polygon.
+    pub fn set_polygons(&mut self, polygons: Vec<Polygon>) {
+        self.polygons = polygons;
+        if self.polygons.is_empty() {
+            self.polygons.push(Polygon::new());
+        }
+        self.generation = 0;
+        self.constraint_enabled_at = None;
+        self.retargeted_edge_length = None;
+        self.last_delta.clear();
+        self.reset_vertex_tracking();
+    }
+
+    // Displacement the growth pass alone would add next step, before mass scaling: each
+    // vertex's growth normal times its effective rate, exactly as `step` computes it.
+    // Vertices of all polygons in order.
+    pub fn growth_delta(&self, params: SimParams) -> Vec<Vec2> {
+        let mut delta = vec![Vec2::ZERO; self.vertex_count()];
+        let mut start = 0;
+        for polygon in &self.polygons {
+            let positions = polygon.vertices();
+            let range = start..start + positions.len();
+            start = range.end;
+            let params = self.effective_params(params, polygon_capped(polygon, &params));
+            let meta = PolygonMeta {
+                params: &params,
+                generation: self.generation,
+                refractory: &self.refractory[range.clone()],
+                growth_outward: self.growth_outward,
+            };
+            let delta = &mut delta[range];
+            accumulate_growth(positions, &meta, &mut ForceBuffers::default(), delta);
+        }
+        delta
+    }
+
+    // Displacement the next `step` would apply before rescaling and splitting, computed
+    // on a copy of the RNG so neither the polygons nor the random sequence advance.
+    // Vertices of all polygons in order.
+    pub fn preview_delta(&self, params: SimParams) -> Vec<Vec2> {
+        let pinned = self.pinned_index();
+        let mut integrator = self.integrator.clone();
+        let mut rng = self.rng.clone();
+        let mut buffers = ForceBuffers::default();
+        let (mut delta, mut next) = (Vec::with_capacity(self.vertex_count()), Vec::new());
+        let (mut all_positions, mut all_hash) = (Vec::new(), SpatialHash::default());
+        let between = hash_all_vertices(&self.polygons, &params, &mut all_positions, &mut all_hash);
+        let mut start = 0;
+        for polygon in &self.polygons {
+            let positions = polygon.vertices();
+            let range = start..start + positions.len();
+            start = range.end;
+            if positions.is_empty() {
+                continue;
             }
+            let params = self.effective_params(params, polygon_capped(polygon, &params));
+            let (masses, pinned) = (&self.masses[range.clone()], local_index(pinned, &range));
+            let meta = PolygonMeta {
+                params: &params,
+                generation: self.generation,
+                refractory: &self.refractory[range.clone()],
+                growth_outward: self.growth_outward,
+            };
+            let others = between.then(|| OtherPolygons {
+                positions: &all_positions,
+                hash: &all_hash,
+                own: range.clone(),
+            });
+            let field = ForceField {
+                meta,
+                custom: &self.forces,
+                gpu: self.gpu_repulsion,
+                others,
+            };
+            let mut forces = |at: &[Vec2], delta: &mut Vec<Vec2>| {
+                compute_delta_into(at, &field, &mut rng, &mut buffers, None, delta);
+                apply_vertex_response(delta, masses, pinned);
+            };
+            integrator.integrate(positions, &mut forces, &mut next);
+            delta.extend(next.iter().zip(positions).map(|(n, p)| *n - *p));
         }
+        delta
+    }
 
-        if params.repulsion_enabled && params.repulsion_strength > 0.0 && params.repulsion_radius > 0.0 {
-            let radius_sq = params.repulsion_radius * params.repulsion_radius;
-            // Pairwise repulsion excludes immediate polygon neighbors.
-            for i in 0..n {
-                for j in (i + 1)..n {
-                    if are_neighbors(i, j, n) {
-                        continue;
+    // Step until the polygons have at least `target` vertices between them or `max_steps`
+    // steps have run. The report sums the vertices added and removed over all steps,
+    // takes the other fields from the last one, and sets `target_reached` when the count
+    // got there; polygons already at the target take no steps.
+    pub fn grow_to_vertex_count(
+        &mut self,
+        params: SimParams,
+        target: usize,
+        max_steps: usize,
+    ) -> StepReport {
+        let mut total = StepReport::default();
+        for _ in 0..max_steps {
+            if self.vertex_count() >= target {
+                break;
+            }
+            let report = self.step(params);
+            total = StepReport {
+                vertices_added: total.vertices_added + report.vertices_added,
+                vertices_removed: total.vertices_removed + report.vertices_removed,
+                ..report
+            };
+        }
+        total.target_reached = self.vertex_count() >= target;
+        total
+    }
+
+    // `for polygon in sim.iter_steps(params).take(100) { ... }`
+    pub fn iter_steps(&mut self, params: SimParams) -> StepIter<'_> {
+        StepIter { sim: self, params }
+    }
+
+    // Advance every polygon by one generation. Each runs the force passes, rescaling,
+    // splits and merges on its own outline only; they share the RNG, which they draw from
+    // in order.
+    pub fn step(&mut self, params: SimParams) -> StepReport {
+        self.retargeted_edge_length = None;
+        if self.vertex_count() == 0 {
+            return StepReport::default();
+        }
+        let mut report = StepReport::default();
+        let step_start = self.profiling.then(Instant::now);
+        self.constraint_enabled_at = match self.constraint_enabled_at {
+            _ if !params.constraint_enabled => None,
+            None => Some(self.generation),
+            since => since,
+        };
+        // Caps are judged on the areas the step starts from, for its splits as well.
+        let capped = &mut self.scratch.capped;
+        capped.clear();
+        capped.extend(self.polygons.iter().map(|p| polygon_capped(p, &params)));
+        report.area_capped = capped.contains(&true);
+        let effective = [false, true].map(|capped| self.effective_params(params, capped));
+        let pinned = self.pinned_index();
+        let mut profile = StepProfile::default();
+        let StepBuffers {
+            all_positions,
+            all_hash,
+            ..
+        } = &mut self.scratch;
+        let between = hash_all_vertices(&self.polygons, &params, all_positions, all_hash);
+
+        self.last_delta.clear();
+        let mut start = 0;
+        for (k, polygon) in self.polygons.iter_mut().enumerate() {
+            let n = polygon.len();
+            let range = start..start + n;
+            start = range.end;
+            if n == 0 {
+                continue;
+            }
+            let params = &effective[usize::from(self.scratch.capped[k])];
+            let positions = polygon.vertices();
+            let (masses, pinned) = (&self.masses[range.clone()], local_index(pinned, &range));
+            let meta = PolygonMeta {
+                params,
+                generation: self.generation,
+                refractory: &self.refractory[range.clone()],
+                growth_outward: self.growth_outward,
+            };
+            let others = between.then(|| OtherPolygons {
+                positions: &self.scratch.all_positions,
+                hash: &self.scratch.all_hash,
+                own: range,
+            });
+            let field = ForceField {
+                meta,
+                custom: &self.forces,
+                gpu: self.gpu_repulsion,
+                others,
+            };
+            let (rng, buffers) = (&mut self.rng, &mut self.scratch.forces);
+            let mut profile_slot = self.profiling.then_some(&mut profile);
+            let mut forces = |at: &[Vec2], delta: &mut Vec<Vec2>| {
+                let profile = profile_slot.as_deref_mut();
+                compute_delta_into(at, &field, rng, buffers, profile, delta);
+                apply_vertex_response(delta, masses, pinned);
+            };
+            let next = &mut self.scratch.positions;
+            self.integrator.integrate(positions, &mut forces, next);
+            self.last_delta.extend(next.iter().zip(positions).map(|(n, p)| *n - *p));
+
+            let perimeter_before = polygon.perimeter();
+
+            // Move to the integrated positions; the old ones become next step's buffer.
+            polygon.swap_vertices(&mut self.scratch.positions);
+
+            if params.growth_enabled
+                && params.growth_rate != 0.0
+                && params.growth_conserve_perimeter
+            {
+                // Rescale uniformly so growth redistributes material instead of adding it.
+                let perimeter_after = polygon.perimeter();
+                if let Some(c) = polygon.centroid_of_kind(params.centroid_kind)
+                    && perimeter_before > 1e-12
+                    && perimeter_after > 1e-12
+                {
+                    let factor = perimeter_before / perimeter_after;
+                    for v in polygon.vertices_mut() {
+                        *v = c + (*v - c) * factor;
                     }
+                }
+            }
 
-                    let d = positions[j] - positions[i];
-                    let dist_sq = d.length_squared();
-                    if dist_sq <= 1e-18 || dist_sq >= radius_sq {
-                        continue;
+            if let Some(min_area) = params.min_area {
+                // Outlines with next to no area have no meaningful size to restore.
+                let area = polygon.area();
+                if area < min_area
+                    && area > 1e-12
+                    && let Some(c) = polygon.centroid_of_kind(params.centroid_kind)
+                {
+                    let factor = (min_area / area).sqrt();
+                    for v in polygon.vertices_mut() {
+                        *v = c + (*v - c) * factor;
                     }
+                }
+            }
+        }
+        report.max_displacement = self.last_delta.iter().map(|d| d.length()).fold(0.0, Real::max);
+        report.converged = report.max_displacement <= params.convergence_threshold;
 
-                    let dist = dist_sq.sqrt();
-                    let dir = d / dist;
-                    // Repulsion fades linearly to zero at the radius boundary.
-                    let proximity = 1.0 - dist / params.repulsion_radius;
-                    let mag = params.repulsion_strength * proximity;
-                    let push = dir * (mag * 0.5);
+        let split_start = (self.profiling && params.split_enabled).then(Instant::now);
+        let cooldown = params.split_refractory_generations;
+        let curvature_split;
+        let builtin: &dyn SplitPredicate = match params.split_min_curvature {
+            Some(min) => {
+                curvature_split = CurvatureSplit { min };
+                &curvature_split
+            }
+            None => &LengthSplit,
+        };
+        let predicate = self.split_predicate.as_deref().unwrap_or(builtin);
+        let StepBuffers {
+            positions: next_vertices,
+            ids: next_ids,
+            births: next_births,
+            masses: next_masses,
+            refractory: next_refractory,
+            capped,
+            ..
+        } = &mut self.scratch;
+        next_ids.clear();
+        next_births.clear();
+        next_masses.clear();
+        next_refractory.clear();
+        let mut start = 0;
+        for (polygon, &capped) in self.polygons.iter_mut().zip(capped.iter()) {
+            let params = &effective[usize::from(capped)];
+            let positions = polygon.vertices();
+            let range = start..start + positions.len();
+            start = range.end;
+            if !(params.split_enabled && params.split_length > 0.0 && positions.len() >= 2) {
+                // Splitting counts refractory periods down as it copies; do it here otherwise.
+                next_ids.extend_from_slice(&self.vertex_ids[range.clone()]);
+                next_births.extend_from_slice(&self.vertex_births[range.clone()]);
+                next_masses.extend_from_slice(&self.masses[range.clone()]);
+                next_refractory.extend(self.refractory[range].iter().map(|r| r.saturating_sub(1)));
+                continue;
+            }
+            let ids = &self.vertex_ids[range.clone()];
+            let births = &self.vertex_births[range.clone()];
+            let masses = &self.masses[range.clone()];
+            let refractory = &self.refractory[range];
+            next_vertices.clear();
+            let first = next_refractory.len();
+            // Whether the edge ending at the vertex about to be pushed was split.
+            let mut after_split = false;
+            for i in 0..positions.len() {
+                let j = next_index(i, positions.len());
+                let (a, b) = (positions[i], positions[j]);
+                let (mass_a, mass_b) = (masses[i], masses[j]);
+                next_vertices.push(a);
+                next_ids.push(ids[i]);
+                next_births.push(births[i]);
+                next_masses.push(mass_a);
+                next_refractory.push(refractory[i].saturating_sub(1));
 
-                    delta[i] -= push;
-                    delta[j] += push;
+                let len = a.distance(b);
+                let segments = ((len / params.split_length).ceil() as usize).max(2);
+                let resting = refractory[i] > 0 || refractory[j] > 0;
+                let split = !resting
+                    && predicate.should_split(EdgeContext {
+                        positions,
+                        start: i,
+                        end: j,
+                        length: len,
+                        params,
+                        generation: self.generation,
+                        births,
+                        masses,
+                    });
+                if after_split || split {
+                    *next_refractory.last_mut().expect("vertex was just pushed") = cooldown;
+                }
+                after_split = split;
+                if split {
+                    let denom = segments as Real;
+                    for k in 1..segments {
+                        let t = (k as Real) / denom;
+                        next_vertices.push(a.lerp(b, t));
+                        next_ids.push(self.next_vertex_id);
+                        next_births.push(self.generation + 1);
+                        next_masses.push(mass_a + (mass_b - mass_a) * t);
+                        next_refractory.push(cooldown);
+                        self.next_vertex_id += 1;
+                    }
                 }
             }
+            if after_split {
+                // The closing edge was split, so vertex 0 took part too.
+                next_refractory[first] = cooldown;
+            }
+            report.vertices_added += next_vertices.len() - positions.len();
+            polygon.swap_vertices(next_vertices);
+        }
+        std::mem::swap(&mut self.vertex_ids, next_ids);
+        std::mem::swap(&mut self.vertex_births, next_births);
+        std::mem::swap(&mut self.masses, next_masses);
+        std::mem::swap(&mut self.refractory, next_refractory);
+        if report.vertices_added > 0 && params.auto_retarget_on_split {
+            // Keep springs consistent with the refined edge lengths.
+            self.retargeted_edge_length = Some(average_edge_length_of(&self.polygons));
         }
 
-        if params.growth_enabled && params.growth_rate != 0.0 {
-            let area = signed_area(&positions);
-            let outward_sign = if area >= 0.0 { 1.0 } else { -1.0 };
+        if let Some(start) = split_start {
+            profile.split = start.elapsed();
+        }
 
-            for i in 0..n {
-                let prev = positions[(i + n - 1) % n];
-                let next = positions[(i + 1) % n];
-                let tangent = next - prev;
-                let len = tangent.length();
-                if len <= 1e-12 {
+        if let Some(merge_length) = params.merge_length
+            && merge_length > 0.0
+        {
+            report.vertices_removed = self.merge_short_edges(merge_length);
+        }
+
+        self.generation = self.generation.saturating_add(1);
+        self.log_vertex_count();
+        if let Some(start) = step_start {
+            profile.total = start.elapsed();
+            self.last_profile = Some(profile);
+        }
+        report
+    }
+
+    // Collapse each edge shorter than `merge_length` into one vertex and return how many
+    // vertices went. Candidates are taken shortest first, ties broken by the lower start
+    // index, and a vertex takes part in at most one merge per call, so the collapse
+    // order is fully determined by the positions. The survivor is the edge's start, or
+    // its pinned end, at the midpoint unless pinned; it keeps the earlier birth, the mean
+    // mass and the longer refractory period. Never leaves a polygon with fewer than three
+    // vertices.
+    fn merge_short_edges(&mut self, merge_length: Real) -> usize {
+        let pinned = self.pinned_index();
+        let StepBuffers {
+            positions: vertices,
+            merge_candidates: candidates,
+            merge_touched: touched,
+            merge_removed: removed,
+            ..
+        } = &mut self.scratch;
+        removed.clear();
+        removed.resize(self.vertex_ids.len(), false);
+        let mut merged = 0;
+        let mut start = 0;
+        for polygon in &mut self.polygons {
+            let n = polygon.len();
+            let range = start..start + n;
+            start = range.end;
+            if n <= 3 {
+                continue;
+            }
+            let pinned = local_index(pinned, &range);
+            let positions = polygon.vertices();
+            candidates.clear();
+            candidates.extend(
+                (0..n)
+                    .map(|i| (positions[i].distance(positions[next_index(i, n)]), i))
+                    .filter(|&(len, _)| len < merge_length),
+            );
+            if candidates.is_empty() {
+                continue;
+            }
+            // Lengths break ties by index, a total order, so the unstable sort is exact.
+            candidates.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            touched.clear();
+            touched.resize(n, false);
+            let removed = &mut removed[range.clone()];
+            let births = &mut self.vertex_births[range.clone()];
+            let masses = &mut self.masses[range.clone()];
+            let refractory = &mut self.refractory[range];
+            let mut remaining = n;
+            for &(_, i) in candidates.iter() {
+                let j = next_index(i, n);
+                if remaining <= 3 {
+                    break;
+                }
+                if touched[i] || touched[j] {
                     continue;
                 }
-                let dir = tangent / len;
-                let normal = Vec2::new(dir.y, -dir.x) * outward_sign;
-                delta[i] += normal * params.growth_rate;
+                touched[i] = true;
+                touched[j] = true;
+                let (keep, gone) = if pinned == Some(j) { (j, i) } else { (i, j) };
+                if pinned != Some(keep) {
+                    // Neither end was touched yet, so both still hold their step positions.
+                    let v = polygon.vertices_mut();
+                    v[keep] = v[i].midpoint(v[j]);
+                }
+                births[keep] = births[keep].min(births[gone]);
+                masses[keep] = (masses[keep] + masses[gone]) * 0.5;
+                refractory[keep] = refractory[keep].max(refractory[gone]);
+                removed[gone] = true;
+                remaining -= 1;
             }
+
+            // Compact in place; the vertices go through the scratch buffer and back.
+            polygon.swap_vertices(vertices);
+            retain_unremoved(vertices, removed);
+            polygon.swap_vertices(vertices);
+            merged += n - remaining;
+        }
+        if merged > 0 {
+            retain_unremoved(&mut self.vertex_ids, removed);
+            retain_unremoved(&mut self.vertex_births, removed);
+            retain_unremoved(&mut self.masses, removed);
+            retain_unremoved(&mut self.refractory, removed);
+        }
+        merged
+    }
+}
+
+// Whether `polygon`'s area is above `params.max_area`, halting its growth and splits.
+fn polygon_capped(polygon: &Polygon, params: &SimParams) -> bool {
+    params.max_area.is_some_and(|max| polygon.area() > max)
+}
+
+// `index` into the vertices of all polygons, relative to the polygon spanning `range`.
+fn local_index(index: Option<usize>, range: &Range<usize>) -> Option<usize> {
+    index.filter(|i| range.contains(i)).map(|i| i - range.start)
+}
+
+// Drop the entries whose flag in the parallel `removed` is set.
+fn retain_unremoved<T>(values: &mut Vec<T>, removed: &[bool]) {
+    let mut flags = removed.iter();
+    values.retain(|_| !flags.next().is_some_and(|&gone| gone));
+}
+
+// Scale the raw force displacement by inverse mass and zero it for a pinned vertex.
+fn apply_vertex_response(delta: &mut [Vec2], masses: &[Real], pinned: Option<usize>) {
+    for (d, mass) in delta.iter_mut().zip(masses) {
+        *d /= *mass;
+    }
+    if let Some(index) = pinned {
+        delta[index] = Vec2::ZERO;
+    }
+}
+
+// Position rounding used by `Simulation::state_hash`.
+pub const STATE_HASH_QUANTUM: Real = 1e-6;
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+// Iterator that advances its simulation by one step per `next()` and yields a clone of
+// the resulting first polygon. A standard `Iterator` cannot hand out borrows of the
+// polygon it keeps mutating, so items are owned; the iterator never ends on its own.
+#[derive(Debug)]
+pub struct StepIter<'a> {
+    sim: &'a mut Simulation,
+    params: SimParams,
+}
+
+impl Iterator for StepIter<'_> {
+    type Item = Polygon;
+
+    fn next(&mut self) -> Option<Polygon> {
+        self.sim.step(self.params);
+        Some(self.sim.polygon().clone())
+    }
+}
+
+// Total per-vertex displacement from every force pass, in `params.force_order`. All
+// forces see the same `positions`; only jitter draws from `rng`.
+pub fn compute_delta(positions: &[Vec2], params: &SimParams, rng: &mut StdRng) -> Vec<Vec2> {
+    let meta = PolygonMeta {
+        params,
+        generation: 0,
+        refractory: &[],
+        growth_outward: true,
+    };
+    let field = ForceField {
+        meta,
+        custom: &[],
+        gpu: false,
+        others: None,
+    };
+    let mut delta = Vec::new();
+    compute_delta_into(positions, &field, rng, &mut ForceBuffers::default(), None, &mut delta);
+    delta
+}
+
+// `compute_delta` into a reused buffer, adding the time each enabled pass takes into
+// `profile` when given. Vertices with a nonzero `refractory` count do not grow; an empty
+// slice leaves every vertex free to.
+fn compute_delta_into(
+    positions: &[Vec2],
+    field: &ForceField,
+    rng: &mut StdRng,
+    buffers: &mut ForceBuffers,
+    mut profile: Option<&mut StepProfile>,
+    delta: &mut Vec<Vec2>,
+) {
+    let ForceField {
+        meta,
+        custom,
+        gpu,
+        others,
+    } = field;
+    let params = meta.params;
+    delta.clear();
+    delta.resize(positions.len(), Vec2::ZERO);
+    let mut ctx = ForceContext {
+        rng,
+        buffers,
+        gpu: *gpu,
+        others: others.as_ref(),
+    };
+    for force in force_stack(params, custom) {
+        // Only time enabled built-in passes, so the `Instant` calls are skipped otherwise.
+        let timed = force.kind().filter(|kind| profile.is_some() && kind.is_enabled(params));
+        let start = timed.map(|kind| (kind, Instant::now()));
+        force.accumulate(positions, meta, &mut ctx, delta);
+        if let (Some(profile), Some((kind, start))) = (profile.as_deref_mut(), start) {
+            profile.forces[kind.index()] += start.elapsed();
         }
+    }
+}
+
+// The passes one force evaluation runs, in order: the built-in ones in
+// `params.force_order`, then `custom` in the order added.
+pub fn force_stack<'a>(
+    params: &'a SimParams,
+    custom: &'a [Box<dyn Force>],
+) -> impl Iterator<Item = &'a dyn Force> {
+    let builtin = params.force_order.iter().map(|kind| kind as &dyn Force);
+    builtin.chain(custom.iter().map(|force| force.as_ref()))
+}
+
+// What `compute_delta_into` evaluates besides the positions: the per-step context, the
+// custom passes after the built-in ones, whether repulsion may run on the GPU, and the
+// other polygons' vertices when there are any to repel.
+struct ForceField<'a> {
+    meta: PolygonMeta<'a>,
+    custom: &'a [Box<dyn Force>],
+    gpu: bool,
+    others: Option<OtherPolygons<'a>>,
+}
+
+// Every polygon's vertices as the step started, in `hash`, and the range the polygon
+// being moved occupies among them.
+#[derive(Debug)]
+pub(crate) struct OtherPolygons<'a> {
+    positions: &'a [Vec2],
+    hash: &'a SpatialHash,
+    own: Range<usize>,
+}
+
+// Fill `positions` and `hash` with the vertices of all `polygons` when repulsion is on and
+// there is more than one polygon, and return whether it did.
+fn hash_all_vertices(
+    polygons: &[Polygon],
+    params: &SimParams,
+    positions: &mut Vec<Vec2>,
+    hash: &mut SpatialHash,
+) -> bool {
+    let repels = params.repulsion_enabled
+        && params.repulsion_strength > 0.0
+        && params.repulsion_radius > 0.0;
+    if polygons.len() < 2 || !repels {
+        return false;
+    }
+    positions.clear();
+    positions.extend(polygons.iter().flat_map(|p| p.vertices()));
+    hash.rebuild(positions, params.repulsion_radius);
+    true
+}
+
+// Whether the GPU took the repulsion pass; false leaves it to the CPU.
+#[cfg(feature = "gpu")]
+fn accumulate_gpu_repulsion(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) -> bool {
+    crate::gpu::shared().is_some_and(|gpu| gpu.accumulate(positions, params, delta))
+}
+
+#[cfg(not(feature = "gpu"))]
+fn accumulate_gpu_repulsion(_: &[Vec2], _: &SimParams, _: &mut [Vec2]) -> bool {
+    false
+}
 
-        if params.constraint_enabled && params.constraint_strength > 0.0 && params.constraint_size > 0.0 {
-            for (i, p) in positions.iter().copied().enumerate() {
-                let mut push = constraint_push(p, params.constraint_shape, params.constraint_size);
-                if push != Vec2::ZERO && params.constraint_falloff == ConstraintFalloff::Quadratic {
-                    let mag = push.length();
-                    push *= mag;
+// The built-in passes, each switched by its own `SimParams` flags. Repulsion also pushes
+// away from the other polygons in `ctx` and runs on the GPU when it may and can.
+impl Force for ForceKind {
+    fn accumulate(
+        &self,
+        positions: &[Vec2],
+        meta: &PolygonMeta,
+        ctx: &mut ForceContext,
+        delta: &mut [Vec2],
+    ) {
+        let params = meta.params;
+        match self {
+            Self::Spring => accumulate_springs(positions, params, delta),
+            Self::Repulsion => {
+                if !(ctx.gpu && accumulate_gpu_repulsion(positions, params, delta)) {
+                    accumulate_repulsion(positions, params, ctx.buffers, delta);
+                }
+                if let Some(others) = ctx.others {
+                    accumulate_repulsion_between(positions, others, params, delta);
                 }
-                delta[i] += push * params.constraint_strength;
             }
+            Self::EdgeRepulsion => accumulate_edge_repulsion(positions, params, delta),
+            Self::Growth => accumulate_growth(positions, meta, ctx.buffers, delta),
+            Self::Jitter => accumulate_jitter(ctx.rng, params, delta),
+            Self::Constraint => accumulate_constraint(positions, params, delta),
+            Self::Attractor => accumulate_attractor(positions, params, delta),
+            Self::Centering => accumulate_centering(positions, params, delta),
         }
+    }
+
+    fn kind(&self) -> Option<ForceKind> {
+        Some(*self)
+    }
 
-        if params.jitter_enabled && params.jitter_strength > 0.0 {
-            // Brownian term adds small random perturbation per vertex.
-            for d in &mut delta {
-                let jx = self.rng.gen_range(-1.0..1.0) * params.jitter_strength;
-                let jy = self.rng.gen_range(-1.0..1.0) * params.jitter_strength;
-                *d += Vec2::new(jx, jy);
+    fn name(&self) -> &'static str {
+        self.label()
+    }
+
+    fn box_clone(&self) -> Box<dyn Force> {
+        Box::new(*self)
+    }
+}
+
+// Heap-free subset of `compute_delta` for fixed-capacity polygons: springs, serial
+// pairwise repulsion, plain growth, constraint, attractor and centering, in
+// `params.force_order`. Passes that need scratch memory or an RNG are skipped: edge
+// repulsion, jitter, capped, threaded or adaptive repulsion, and every growth option
+// beyond rate and inhibitors.
+pub(crate) fn accumulate_fixed_forces(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    let serial = SimParams {
+        repulsion_threads: 1,
+        repulsion_max_neighbors: 0,
+        adaptive_repulsion: false,
+        ..*params
+    };
+    for kind in params.force_order {
+        match kind {
+            ForceKind::Spring => accumulate_springs(positions, params, delta),
+            ForceKind::Repulsion => {
+                // Serial uncapped repulsion reads no buffers, so empty ones stay unallocated.
+                accumulate_repulsion(positions, &serial, &mut ForceBuffers::default(), delta)
             }
+            ForceKind::Growth => accumulate_plain_growth(positions, params, delta),
+            ForceKind::Constraint => accumulate_constraint(positions, params, delta),
+            ForceKind::Attractor => accumulate_attractor(positions, params, delta),
+            ForceKind::Centering => accumulate_centering(positions, params, delta),
+            ForceKind::EdgeRepulsion | ForceKind::Jitter => {}
         }
+    }
+}
+
+// `growth_rate` along each vertex's neighbor-chord normal, damped by inhibitors; what
+// `accumulate_growth` does when no other growth option is set.
+fn accumulate_plain_growth(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    if !params.growth_enabled || params.growth_rate == 0.0 {
+        return;
+    }
+    let outward_sign = outward_sign(positions);
+    for (i, d) in delta.iter_mut().enumerate() {
+        if let Some(normal) = chord_normal(positions, i, 1, outward_sign) {
+            let damping = inhibition_factor(positions[i], &params.growth_inhibitors);
+            *d += normal * (params.growth_rate * damping);
+        }
+    }
+}
 
-        // Apply total displacement field to the polygon.
-        for (v, d) in self.polygon.vertices_mut().iter_mut().zip(delta) {
-            *v += d;
+fn accumulate_springs(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    let n = positions.len();
+    let tension = params.edge_stiffness_tension.unwrap_or(params.edge_stiffness);
+    let compression = params.edge_stiffness_compression.unwrap_or(params.edge_stiffness);
+    if params.edge_regularization_enabled
+        && (tension > 0.0 || compression > 0.0)
+        && params.target_edge_length > 0.0
+    {
+        // Edge springs keep local spacing near target length.
+        for i in 0..n {
+            let j = next_index(i, n);
+            let d = positions[j] - positions[i];
+            let len = d.length();
+            let error = len - params.target_edge_length;
+            if len > 1e-12 && error.abs() > params.edge_dead_zone {
+                let dir = d / len;
+                let stiffness = if error > 0.0 { tension } else { compression };
+                // Apply equal/opposite correction to edge endpoints.
+                let correction = dir * (error * stiffness * 0.5);
+                delta[i] += correction;
+                delta[j] -= correction;
+            }
         }
+    }
+}
+
+// Smallest distance between two vertices closer than `within` whose separation along the
+// curve exceeds twice `within`. Such pairs belong to different parts of the curve folding
+// toward each other rather than to one locally curved stretch.
+pub fn min_self_distance(positions: &[Vec2], within: Real) -> Option<Real> {
+    min_self_distance_in(positions, within, &mut ForceBuffers::default())
+}
+
+fn min_self_distance_in(
+    positions: &[Vec2],
+    within: Real,
+    buffers: &mut ForceBuffers,
+) -> Option<Real> {
+    let n = positions.len();
+    if n < 4 || within <= 0.0 {
+        return None;
+    }
+    let ForceBuffers { hash, arc, .. } = buffers;
+    // Arc length from vertex 0 to each vertex.
+    arc.clear();
+    let mut perimeter = 0.0;
+    for (a, b) in edges(positions, true) {
+        arc.push(perimeter);
+        perimeter += a.distance(b);
+    }
+
+    hash.rebuild(positions, within);
+    let mut min_sq: Option<Real> = None;
+    for i in 0..n {
+        hash.for_each_within(positions, positions[i], within, |j| {
+            let along = (arc[i] - arc[j]).abs();
+            if j > i && along.min(perimeter - along) > 2.0 * within {
+                let d_sq = positions[i].distance_squared(positions[j]);
+                if min_sq.is_none_or(|m| d_sq < m) {
+                    min_sq = Some(d_sq);
+                }
+            }
+        });
+    }
+    min_sq.map(Real::sqrt)
+}
 
-        if params.split_enabled && params.split_length > 0.0 {
-            let positions = self.polygon.vertices();
-            if positions.len() >= 2 {
-                let mut next_vertices = Vec::with_capacity(positions.len());
-                for i in 0..positions.len() {
-                    let a = positions[i];
-                    let b = positions[(i + 1) % positions.len()];
-                    next_vertices.push(a);
+// Vertex repulsion strength for this configuration, after adaptive scaling.
+pub fn effective_repulsion_strength(positions: &[Vec2], params: &SimParams) -> Real {
+    effective_repulsion_strength_in(positions, params, &mut ForceBuffers::default())
+}
 
-                    let len = a.distance(b);
-                    if len > params.split_length {
-                        let segments = (len / params.split_length).ceil() as usize;
-                        if segments > 1 {
-                            let denom = segments as f64;
-                            for k in 1..segments {
-                                let t = (k as f64) / denom;
-                                next_vertices.push(a.lerp(b, t));
+fn effective_repulsion_strength_in(
+    positions: &[Vec2],
+    params: &SimParams,
+    buffers: &mut ForceBuffers,
+) -> Real {
+    if !params.adaptive_repulsion {
+        return params.repulsion_strength;
+    }
+    let max = params.adaptive_repulsion_max.max(1.0);
+    let factor = match min_self_distance_in(positions, params.repulsion_radius, buffers) {
+        Some(d) => (params.repulsion_radius / d.max(1e-12)).clamp(1.0, max),
+        None => 1.0,
+    };
+    params.repulsion_strength * factor
+}
+
+fn accumulate_repulsion(
+    positions: &[Vec2],
+    params: &SimParams,
+    buffers: &mut ForceBuffers,
+    delta: &mut [Vec2],
+) {
+    let n = positions.len();
+    let adapted;
+    let params = if params.adaptive_repulsion && params.repulsion_enabled {
+        adapted = SimParams {
+            repulsion_strength: effective_repulsion_strength_in(positions, params, buffers),
+            ..*params
+        };
+        &adapted
+    } else {
+        params
+    };
+    if params.repulsion_enabled && params.repulsion_strength > 0.0 && params.repulsion_radius > 0.0 {
+        let threads = params.repulsion_threads.clamp(1, n.max(1));
+        if params.repulsion_max_neighbors > 0 {
+            accumulate_capped_repulsion(positions, params, buffers, delta);
+        } else if threads == 1 {
+            // Pairwise repulsion skips pairs within the neighbor exclusion along the loop.
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    if let Some(push) = repulsion_push(positions, i, j, params) {
+                        delta[i] -= push;
+                        delta[j] += push;
+                    }
+                }
+            }
+        } else {
+            // Each worker owns a contiguous block of vertices and gathers their pushes in
+            // the same order the serial loop scatters them, so sums match it bit for bit.
+            let chunk = n.div_ceil(threads);
+            std::thread::scope(|scope| {
+                for (c, out) in delta.chunks_mut(chunk).enumerate() {
+                    scope.spawn(move || {
+                        for (offset, d) in out.iter_mut().enumerate() {
+                            let k = c * chunk + offset;
+                            for i in 0..k {
+                                if let Some(push) = repulsion_push(positions, i, k, params) {
+                                    *d += push;
+                                }
+                            }
+                            for j in (k + 1)..n {
+                                if let Some(push) = repulsion_push(positions, k, j, params) {
+                                    *d -= push;
+                                }
                             }
                         }
-                    }
+                    });
+                }
+            });
+        }
+    }
+}
+
+// Each vertex gathers pushes from at most `repulsion_max_neighbors` of its closest
+// interacting neighbors. The cap makes forces asymmetric, so unlike the uncapped passes
+// this does not conserve momentum.
+fn accumulate_capped_repulsion(
+    positions: &[Vec2],
+    params: &SimParams,
+    buffers: &mut ForceBuffers,
+    delta: &mut [Vec2],
+) {
+    let ForceBuffers { hash, nearest, .. } = buffers;
+    hash.rebuild(positions, params.repulsion_radius);
+    for (i, d) in delta.iter_mut().enumerate() {
+        nearest.clear();
+        hash.for_each_within(positions, positions[i], params.repulsion_radius, |j| {
+            if j != i {
+                nearest.push((positions[j].distance_squared(positions[i]), j));
+            }
+        });
+        // Ties resolve by index so the selection is deterministic, and the order is total,
+        // so the unstable sort, which needs no buffer, gives the same result.
+        nearest.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let pushes = nearest.iter().filter_map(|&(_, j)| {
+            if i < j {
+                repulsion_push(positions, i, j, params).map(|push| -push)
+            } else {
+                repulsion_push(positions, j, i, params)
+            }
+        });
+        for push in pushes.take(params.repulsion_max_neighbors) {
+            *d += push;
+        }
+    }
+}
+
+// Push applied to `j` (and negated for `i`) for the pair `i < j`, if they interact.
+fn repulsion_push(positions: &[Vec2], i: usize, j: usize, params: &SimParams) -> Option<Vec2> {
+    let n = positions.len();
+    if within_ring_distance(i, j, n, params.repulsion_neighbor_exclusion) {
+        return None;
+    }
+    pair_push(positions[i], positions[j], params)
+}
+
+// Vertex repulsion from the other polygons, which the per-polygon pass never sees. Each
+// vertex of `positions` takes the push every nearby vertex of another polygon gives it,
+// at that polygon's position when the step started; the other polygon takes the opposite
+// push when it moves. The plain `repulsion_strength` applies, even when adaptive.
+fn accumulate_repulsion_between(
+    positions: &[Vec2],
+    others: &OtherPolygons,
+    params: &SimParams,
+    delta: &mut [Vec2],
+) {
+    let OtherPolygons {
+        positions: all,
+        hash,
+        own,
+    } = others;
+    for (p, d) in positions.iter().zip(delta) {
+        hash.for_each_within(all, *p, params.repulsion_radius, |j| {
+            if !own.contains(&j)
+                && let Some(push) = pair_push(all[j], *p, params)
+            {
+                *d += push;
+            }
+        });
+    }
+}
+
+// Push on `b` away from `a`, fading linearly to zero at `repulsion_radius`.
+fn pair_push(a: Vec2, b: Vec2, params: &SimParams) -> Option<Vec2> {
+    let d = b - a;
+    let dist_sq = d.length_squared();
+    let radius_sq = params.repulsion_radius * params.repulsion_radius;
+    if dist_sq <= 1e-18 || dist_sq >= radius_sq {
+        return None;
+    }
+
+    let dist = dist_sq.sqrt();
+    let dir = d / dist;
+    // Repulsion fades linearly to zero at the radius boundary.
+    let proximity = 1.0 - dist / params.repulsion_radius;
+    let mag = params.repulsion_strength * proximity;
+    Some(dir * (mag * 0.5))
+}
+
+fn accumulate_edge_repulsion(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    let n = positions.len();
+    if !params.edge_repulsion_enabled
+        || params.edge_repulsion_strength <= 0.0
+        || params.repulsion_radius <= 0.0
+        || n < 3
+    {
+        return;
+    }
+
+    let tree = EdgeQuadtree::build(positions);
+    for i in 0..n {
+        let p = positions[i];
+        for e in tree.query_near(p, params.repulsion_radius) {
+            let f = next_index(e, n);
+            // Skip the two edges incident to the vertex itself.
+            if e == i || f == i {
+                continue;
+            }
+
+            let a = positions[e];
+            let b = positions[f];
+            let closest = closest_point_on_segment(p, a, b);
+            let d = p - closest;
+            let dist = d.length();
+            if dist <= 1e-9 || dist >= params.repulsion_radius {
+                continue;
+            }
+
+            // Push the vertex off the edge and the edge back, split by where it was hit.
+            let proximity = 1.0 - dist / params.repulsion_radius;
+            let push = (d / dist) * (params.edge_repulsion_strength * proximity * 0.5);
+            let ab = b - a;
+            let t = if ab.length_squared() > 1e-24 {
+                ((closest - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            delta[i] += push;
+            delta[e] -= push * (1.0 - t);
+            delta[f] -= push * t;
+        }
+    }
+}
+
+// `outward` false grows against the detected outward normals.
+fn accumulate_growth(
+    positions: &[Vec2],
+    meta: &PolygonMeta,
+    buffers: &mut ForceBuffers,
+    delta: &mut [Vec2],
+) {
+    let (params, refractory) = (meta.params, meta.refractory);
+    let n = positions.len();
+    if params.growth_enabled && params.growth_rate != 0.0 {
+        let ForceBuffers {
+            hash,
+            raw_normals,
+            normals,
+            growth: steps,
+            growth_scales: scales,
+            ..
+        } = buffers;
+        let area = signed_area(positions);
+        let outward_sign = outward_sign(positions);
+        let density_hash = (params.growth_density_gain > 0.0 && params.repulsion_radius > 0.0)
+            .then(|| {
+                hash.rebuild(positions, params.repulsion_radius);
+                &*hash
+            });
+        growth_normals(
+            positions,
+            if meta.growth_outward { outward_sign } else { -outward_sign },
+            params.growth_tangent_window,
+            params.growth_normal_smoothing,
+            raw_normals,
+            normals,
+        );
+        let base_rate = match params.growth_area_goal {
+            Some(goal) if goal > 0.0 => {
+                let error = ((goal - area.abs()) / goal).clamp(-1.0, 1.0);
+                params.growth_rate.abs() * error
+            }
+            _ => params.growth_rate,
+        };
+        // Growth per vertex, gathered first so the erosion guard can see where the
+        // neighbors are headed.
+        steps.clear();
+        steps.resize(n, Vec2::ZERO);
+        for i in 0..n {
+            if refractory.get(i).is_some_and(|&r| r > 0) {
+                continue;
+            }
+            if params.growth_curvature_filter != GrowthCurvatureFilter::All {
+                // Orient curvature so positive always means convex: left turns are convex
+                // on a CCW loop, whose outward sign is -1.
+                let convexity = -vertex_curvature(positions, i) * outward_sign;
+                let keep = match params.growth_curvature_filter {
+                    GrowthCurvatureFilter::All => true,
+                    GrowthCurvatureFilter::ConvexOnly => convexity > 0.0,
+                    GrowthCurvatureFilter::ConcaveOnly => convexity < 0.0,
+                };
+                if !keep {
+                    continue;
                 }
-                self.polygon.replace_vertices(next_vertices);
             }
+
+            let Some(normal) = normals[i] else {
+                continue;
+            };
+            let mut rate = base_rate;
+            if let Some(hash) = density_hash {
+                // Crowded vertices grow less, keeping resolution even.
+                let mut neighbors = 0usize;
+                hash.for_each_within(positions, positions[i], params.repulsion_radius, |j| {
+                    if j != i {
+                        neighbors += 1;
+                    }
+                });
+                rate /= 1.0 + params.growth_density_gain * neighbors as Real;
+            }
+            rate *= inhibition_factor(positions[i], &params.growth_inhibitors);
+            steps[i] = normal * rate;
         }
 
-        self.generation = self.generation.saturating_add(1);
+        if let Some(min_edge) = params.growth_erosion_min_edge
+            && base_rate < 0.0
+            && let Some(center) = centroid_of_kind(positions, params.centroid_kind)
+        {
+            clamp_erosion(positions, steps, scales, min_edge, center);
+        }
+        for (d, step) in delta.iter_mut().zip(steps.iter()) {
+            *d += *step;
+        }
+    }
+}
+
+// Shortens each erosion step that `erodes_too_far` rejects to the fraction it allows, so
+// only the vertices at the limit slow down. Halting a vertex alone lets its neighbors
+// overtake it and fold the outline, so each vertex also keeps within halfway between its
+// neighbors' fractions and a full step. Any vertex still too far after that stays put.
+fn clamp_erosion(
+    positions: &[Vec2],
+    steps: &mut [Vec2],
+    scales: &mut Vec<Real>,
+    min_edge: Real,
+    center: Vec2,
+) {
+    let n = positions.len();
+    scales.clear();
+    scales.extend((0..n).map(|i| allowed_fraction(positions, steps, i, min_edge, center)));
+    for i in 0..n {
+        let relaxed = 0.5 * (1.0 + scales[prev_index(i, n)].min(scales[next_index(i, n)]));
+        steps[i] *= scales[i].min(relaxed);
+    }
+    for i in 0..n {
+        if erodes_too_far(positions, steps, i, min_edge, center) {
+            steps[i] = Vec2::ZERO;
+        }
+    }
+}
+
+// Largest fraction, to within 1/64, of the steps of vertex `i` and its neighbors that
+// `erodes_too_far` accepts when all three are scaled by it alike. Scaling them together
+// keeps an evenly eroding stretch even. Leaves `steps` unchanged.
+fn allowed_fraction(
+    positions: &[Vec2],
+    steps: &mut [Vec2],
+    i: usize,
+    min_edge: Real,
+    center: Vec2,
+) -> Real {
+    if !erodes_too_far(positions, steps, i, min_edge, center) {
+        return 1.0;
+    }
+    let n = positions.len();
+    let local = [prev_index(i, n), i, next_index(i, n)];
+    let full = local.map(|j| steps[j]);
+    let (mut lo, mut hi): (Real, Real) = (0.0, 1.0);
+    for _ in 0..6 {
+        let mid = 0.5 * (lo + hi);
+        for (&j, step) in local.iter().zip(full) {
+            steps[j] = step * mid;
+        }
+        if erodes_too_far(positions, steps, i, min_edge, center) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    for (&j, step) in local.iter().zip(full) {
+        steps[j] = step;
+    }
+    lo
+}
+
+// Whether applying `steps` would shorten an edge next to vertex `i` below `min_edge`,
+// or bring vertex `i` within `min_edge` of `center` or past it.
+fn erodes_too_far(
+    positions: &[Vec2],
+    steps: &[Vec2],
+    i: usize,
+    min_edge: Real,
+    center: Vec2,
+) -> bool {
+    let n = positions.len();
+    let (p, moved) = (positions[i], positions[i] + steps[i]);
+    let too_short = [prev_index(i, n), next_index(i, n)].iter().any(|&j| {
+        let length = moved.distance(positions[j] + steps[j]);
+        length < min_edge && length < p.distance(positions[j])
+    });
+    let past_center = (center - p).dot(center - moved) <= 0.0 || moved.distance(center) < min_edge;
+    too_short || (past_center && moved.distance(center) < p.distance(center))
+}
+
+// Growth multiplier in [0, 1] from every inhibitor covering `p`.
+fn inhibition_factor(p: Vec2, inhibitors: &[Option<Attractor>]) -> Real {
+    let mut factor = 1.0;
+    for inhibitor in inhibitors.iter().flatten() {
+        let d = p.distance(inhibitor.position);
+        if inhibitor.radius > 0.0 && d < inhibitor.radius {
+            factor *= (1.0 - inhibitor.strength * (1.0 - d / inhibitor.radius)).clamp(0.0, 1.0);
+        }
+    }
+    factor
+}
+
+// Unit outward normal per vertex from its neighbors' chord, or `None` where degenerate.
+// With `smoothing > 0` each normal is blended toward the mean of its neighbors' normals,
+// a cheap low-pass that keeps jitter from being amplified into spikes.
+// Writes the normals into `normals`, using `raw` as scratch when smoothing.
+fn growth_normals(
+    positions: &[Vec2],
+    outward_sign: Real,
+    window: usize,
+    smoothing: Real,
+    raw: &mut Vec<Option<Vec2>>,
+    normals: &mut Vec<Option<Vec2>>,
+) {
+    let n = positions.len();
+    // The edges in the window telescope to one chord; keep it shorter than the loop.
+    let k = window.clamp(1, (n.max(3) - 1) / 2);
+    let smoothing = smoothing.clamp(0.0, 1.0);
+    let chords = (0..n).map(|i| chord_normal(positions, i, k, outward_sign));
+    normals.clear();
+    if smoothing == 0.0 || n < 3 {
+        normals.extend(chords);
+        return;
+    }
+
+    raw.clear();
+    raw.extend(chords);
+    normals.extend((0..n).map(|i| {
+        let own = raw[i]?;
+        let neighbors =
+            raw[prev_index(i, n)].unwrap_or(own) + raw[next_index(i, n)].unwrap_or(own);
+        let blended = own * (1.0 - smoothing) + neighbors * (0.5 * smoothing);
+        // Opposing neighbors can cancel out; keep the raw direction then.
+        Some(blended.try_normalize().unwrap_or(own))
+    }));
+}
+
+// Unit normal at vertex `i` from the chord between vertices `i - k` and `i + k`.
+fn chord_normal(positions: &[Vec2], i: usize, k: usize, outward_sign: Real) -> Option<Vec2> {
+    let n = positions.len();
+    let tangent = positions[(i + k) % n] - positions[(i + n * k - k) % n];
+    let len = tangent.length();
+    (len > 1e-12).then(|| {
+        let dir = tangent / len;
+        Vec2::new(-dir.y, dir.x) * outward_sign
+    })
+}
+
+fn accumulate_constraint(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    if params.constraint_enabled && params.constraint_strength > 0.0 && params.constraint_size > 0.0 {
+        for (i, p) in positions.iter().copied().enumerate() {
+            let p = p - params.constraint_center;
+            let to_boundary = if params.constraint_inverted {
+                exclusion_push(p, params.constraint_shape, params.constraint_size)
+            } else {
+                constraint_push(p, params.constraint_shape, params.constraint_size)
+            };
+            let mut push = to_boundary;
+            if push != Vec2::ZERO && params.constraint_falloff == ConstraintFalloff::Quadratic {
+                push *= to_boundary.length();
+            }
+            push *= params.constraint_strength;
+            // Never move past the boundary: a push longer than the overshoot lands the
+            // vertex exactly on it instead of oscillating across it.
+            if push.length_squared() > to_boundary.length_squared() {
+                push = to_boundary;
+            }
+            delta[i] += push;
+        }
+    }
+}
+
+fn accumulate_attractor(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    if let Some(attractor) = params.attractor
+        && attractor.strength != 0.0
+        && attractor.radius > 0.0
+    {
+        for (i, p) in positions.iter().copied().enumerate() {
+            let d = attractor.position - p;
+            let dist = d.length();
+            if dist <= 1e-12 || dist >= attractor.radius {
+                continue;
+            }
+            // Pull fades linearly to zero at the attractor radius.
+            let falloff = 1.0 - dist / attractor.radius;
+            delta[i] += (d / dist) * (attractor.strength * falloff);
+        }
     }
 }
 
-pub fn regular_ngon_edge_length(radius: f64, sides: usize) -> f64 {
+fn accumulate_centering(positions: &[Vec2], params: &SimParams, delta: &mut [Vec2]) {
+    if params.centering_strength == 0.0 {
+        return;
+    }
+    let Some(center) = centroid_of_kind(positions, params.centroid_kind) else {
+        return;
+    };
+    for (d, p) in delta.iter_mut().zip(positions) {
+        *d += (center - *p) * params.centering_strength;
+    }
+}
+
+fn accumulate_jitter(rng: &mut StdRng, params: &SimParams, delta: &mut [Vec2]) {
+    if params.jitter_enabled && params.jitter_strength > 0.0 {
+        // Brownian term adds small random perturbation per vertex.
+        for d in delta.iter_mut() {
+            let jx = rng.gen_range(-1.0..1.0) * params.jitter_strength;
+            let jy = rng.gen_range(-1.0..1.0) * params.jitter_strength;
+            *d += Vec2::new(jx, jy);
+        }
+    }
+}
+
+pub fn regular_ngon_edge_length(radius: Real, sides: usize) -> Real {
     if radius <= 0.0 || sides < 3 {
         return 0.0;
     }
     // Chord length of an inscribed regular n-gon.
-    2.0 * radius * (PI / sides as f64).sin()
+    2.0 * radius * (PI / sides as Real).sin()
 }
 
-pub fn average_edge_length(polygon: &Polygon) -> f64 {
-    let n = polygon.len();
-    if n < 2 {
-        return 0.0;
+pub fn average_edge_length(polygon: &Polygon) -> Real {
+    mean_edge_length(polygon, polygon.is_closed())
+}
+
+// `average_edge_length` over several polygons, counting every edge once.
+pub fn average_edge_length_of(polygons: &[Polygon]) -> Real {
+    let (length, edges) = polygons.iter().fold((0.0, 0), |(length, edges), p| {
+        let closed = p.is_closed();
+        (length + p.curve_length(closed), edges + p.edge_count(closed))
+    });
+    match edges {
+        0 => 0.0,
+        edges => length / edges as Real,
     }
-    // Mean edge length for a closed polygon.
-    polygon.perimeter() / n as f64
 }
 
-fn are_neighbors(i: usize, j: usize, n: usize) -> bool {
+// Mean edge length; an open chain of k vertices has k - 1 edges.
+pub fn mean_edge_length(polygon: &Polygon, closed: bool) -> Real {
+    match polygon.edge_count(closed) {
+        0 => 0.0,
+        edges => polygon.curve_length(closed) / edges as Real,
+    }
+}
+
+// Whether `i` and `j` are at most `hops` edges apart around the closed loop of `n`.
+fn within_ring_distance(i: usize, j: usize, n: usize, hops: usize) -> bool {
     if n < 2 || i == j {
         return true;
     }
-    // Adjacent indices are connected by an edge in the closed loop.
-    let next_i = (i + 1) % n;
-    let prev_i = (i + n - 1) % n;
-    j == next_i || j == prev_i
+    let forward = (j + n - i) % n;
+    forward.min(n - forward) <= hops
 }
 
-fn constraint_push(p: Vec2, shape: ConstraintShape, size: f64) -> Vec2 {
+fn constraint_push(p: Vec2, shape: ConstraintShape, size: Real) -> Vec2 {
     match shape {
         ConstraintShape::Circle => {
             let r = p.length();
@@ -238,63 +2450,107 @@ fn constraint_push(p: Vec2, shape: ConstraintShape, size: f64) -> Vec2 {
                 dir * (size - r)
             }
         }
-        ConstraintShape::Square => {
-            let clamped = Vec2::new(p.x.clamp(-size, size), p.y.clamp(-size, size));
-            if clamped == p {
+        ConstraintShape::Square => rectangle_push(p, size, size),
+        ConstraintShape::Rectangle {
+            half_width,
+            half_height,
+        } => rectangle_push(p, half_width, half_height),
+        ConstraintShape::Triangle => {
+            let verts = triangle_vertices(size);
+            let outside = (0..3).any(|i| {
+                let edge = verts[(i + 1) % 3] - verts[i];
+                // Outward normal for CCW triangle.
+                let normal = Vec2::new(edge.y, -edge.x);
+                (p - verts[i]).dot(normal) > 0.0
+            });
+            if !outside {
+                return Vec2::ZERO;
+            }
+            // Nearest boundary point, which is a corner when `p` lies past two edges.
+            (0..3)
+                .map(|i| closest_point_on_segment(p, verts[i], verts[(i + 1) % 3]))
+                .min_by(|a, b| p.distance_squared(*a).total_cmp(&p.distance_squared(*b)))
+                .map_or(Vec2::ZERO, |q| q - p)
+        }
+    }
+}
+
+// Shortest offset that moves an interior point `p` onto the shape boundary; zero outside.
+fn exclusion_push(p: Vec2, shape: ConstraintShape, size: Real) -> Vec2 {
+    match shape {
+        ConstraintShape::Circle => {
+            let r = p.length();
+            // The exact center has no preferred way out.
+            if r >= size || r <= 1e-12 {
                 Vec2::ZERO
             } else {
-                clamped - p
+                p / r * (size - r)
             }
         }
+        ConstraintShape::Square => rectangle_exclusion_push(p, size, size),
+        ConstraintShape::Rectangle {
+            half_width,
+            half_height,
+        } => rectangle_exclusion_push(p, half_width, half_height),
         ConstraintShape::Triangle => {
             let verts = triangle_vertices(size);
-            let mut max_dist = 0.0;
-            let mut max_normal = Vec2::ZERO;
-
+            let mut nearest = Real::INFINITY;
+            let mut push = Vec2::ZERO;
             for i in 0..3 {
-                let a = verts[i];
-                let b = verts[(i + 1) % 3];
-                let edge = b - a;
+                let edge = verts[(i + 1) % 3] - verts[i];
                 let len = edge.length();
                 if len <= 1e-12 {
                     continue;
                 }
-                // Outward normal for CCW triangle.
                 let normal = Vec2::new(edge.y, -edge.x) / len;
-                let dist = (p - a).dot(normal);
-                if dist > max_dist {
-                    max_dist = dist;
-                    max_normal = normal;
+                // Negative inside; the least negative edge is the closest way out.
+                let dist = (p - verts[i]).dot(normal);
+                if dist >= 0.0 {
+                    return Vec2::ZERO;
+                }
+                if -dist < nearest {
+                    nearest = -dist;
+                    push = normal * nearest;
                 }
             }
-
-            if max_dist > 0.0 {
-                -max_normal * max_dist
-            } else {
-                Vec2::ZERO
-            }
+            push
         }
     }
 }
 
-fn triangle_vertices(size: f64) -> [Vec2; 3] {
-    let a = Vec2::new(0.0, size);
-    let b = Vec2::new(-0.866_025_403_784, -0.5) * size;
-    let c = Vec2::new(0.866_025_403_784, -0.5) * size;
-    [a, b, c]
+fn rectangle_exclusion_push(p: Vec2, half_width: Real, half_height: Real) -> Vec2 {
+    let gap_x = half_width - p.x.abs();
+    let gap_y = half_height - p.y.abs();
+    if gap_x <= 0.0 || gap_y <= 0.0 {
+        return Vec2::ZERO;
+    }
+    // Leave through the nearer side, on the side of the axis the point already sits.
+    if gap_x <= gap_y {
+        Vec2::new(gap_x.copysign(p.x), 0.0)
+    } else {
+        Vec2::new(0.0, gap_y.copysign(p.y))
+    }
 }
 
-fn signed_area(points: &[Vec2]) -> f64 {
-    let n = points.len();
-    if n < 3 {
-        return 0.0;
+fn rectangle_push(p: Vec2, half_width: Real, half_height: Real) -> Vec2 {
+    // Per-axis overshoot: each coordinate is pulled back only along the axis it exceeds.
+    let clamped = Vec2::new(
+        p.x.clamp(-half_width, half_width),
+        p.y.clamp(-half_height, half_height),
+    );
+    if clamped == p {
+        Vec2::ZERO
+    } else {
+        clamped - p
     }
+}
 
-    let mut sum = 0.0;
-    for i in 0..n {
-        let a = points[i];
-        let b = points[(i + 1) % n];
-        sum += a.x * b.y - b.x * a.y;
-    }
-    0.5 * sum
+// Equilateral triangle with circumradius `size`, apex up, in CCW order.
+// The literal is written for f64 and merely rounds under the `f32` feature.
+#[allow(clippy::excessive_precision)]
+pub fn triangle_vertices(size: Real) -> [Vec2; 3] {
+    let a = Vec2::new(0.0, size);
+    let b = Vec2::new(-0.866_025_403_784, -0.5) * size;
+    let c = Vec2::new(0.866_025_403_784, -0.5) * size;
+    [a, b, c]
 }
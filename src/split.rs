@@ -0,0 +1,69 @@
+use crate::geometry::{vertex_curvature, Real, Vec2};
+use crate::sim::SimParams;
+use std::fmt::Debug;
+
+// One edge from vertex `start` to `end == start + 1` (wrapping), as the split pass sees it
+// before inserting anything. The per-vertex slices are parallel to `positions`.
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeContext<'a> {
+    pub positions: &'a [Vec2],
+    pub start: usize,
+    pub end: usize,
+    pub length: Real,
+    // Parameters after the simulation's adjustments, as the rest of the step sees them.
+    pub params: &'a SimParams,
+    // Generation the step starts from.
+    pub generation: u64,
+    // Generation each vertex was born in.
+    pub births: &'a [u64],
+    pub masses: &'a [Real],
+}
+
+// Decides which edges the split pass subdivides. A chosen edge is cut into
+// `ceil(length / split_length)` equal pieces, and at least two. Edges in their
+// refractory period are never offered. Install one with `Simulation::set_split_predicate`;
+// without one, `LengthSplit` or `CurvatureSplit` applies as the params ask.
+pub trait SplitPredicate: Debug + Send {
+    fn should_split(&self, edge: EdgeContext) -> bool;
+
+    // `Simulation` is `Clone`, so boxed predicates must be too.
+    fn box_clone(&self) -> Box<dyn SplitPredicate>;
+}
+
+impl Clone for Box<dyn SplitPredicate> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+// Edges longer than `split_length`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthSplit;
+
+impl SplitPredicate for LengthSplit {
+    fn should_split(&self, edge: EdgeContext) -> bool {
+        edge.length > edge.params.split_length
+    }
+
+    fn box_clone(&self) -> Box<dyn SplitPredicate> {
+        Box::new(*self)
+    }
+}
+
+// `LengthSplit` edges with an endpoint whose absolute curvature exceeds `min`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvatureSplit {
+    pub min: Real,
+}
+
+impl SplitPredicate for CurvatureSplit {
+    fn should_split(&self, edge: EdgeContext) -> bool {
+        LengthSplit.should_split(edge)
+            && (vertex_curvature(edge.positions, edge.start).abs() > self.min
+                || vertex_curvature(edge.positions, edge.end).abs() > self.min)
+    }
+
+    fn box_clone(&self) -> Box<dyn SplitPredicate> {
+        Box::new(*self)
+    }
+}
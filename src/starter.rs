@@ -0,0 +1,59 @@
+use crate::geometry::{Real, Vec2};
+
+// Family of initial outlines offered by the GUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarterShape {
+    // Regular polygon with `sides` vertices.
+    Ngon,
+    // `sides` points alternating between the radius and an inner radius.
+    Star,
+    // Box of width 2 * radius with quarter-circle corners.
+    RoundedRect,
+    // Regular polygon with a vertex on top, exactly mirror-symmetric about the vertical
+    // axis through the center.
+    SymmetricNgon,
+}
+
+impl StarterShape {
+    pub const ALL: [Self; 4] = [Self::Ngon, Self::Star, Self::RoundedRect, Self::SymmetricNgon];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ngon => "Regular Polygon",
+            Self::Star => "Star",
+            Self::RoundedRect => "Rounded Rectangle",
+            Self::SymmetricNgon => "Mirror-Symmetric Polygon",
+        }
+    }
+}
+
+// Starter shape controls, as shared in a config code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Starter {
+    pub shape: StarterShape,
+    pub radius: Real,
+    pub sides: usize,
+    // Inner/outer radius ratio for stars.
+    pub star_inner_ratio: Real,
+    // Height/width ratio and corner radius (relative to the radius) for rounded rectangles.
+    pub rect_aspect: Real,
+    pub corner_ratio: Real,
+    pub center: Vec2,
+    pub perturb_magnitude: Real,
+}
+
+impl Default for Starter {
+    // Matches the GUI's initial starter controls.
+    fn default() -> Self {
+        Self {
+            shape: StarterShape::Ngon,
+            radius: 1.0,
+            sides: 32,
+            star_inner_ratio: 0.5,
+            rect_aspect: 0.6,
+            corner_ratio: 0.3,
+            center: Vec2::ZERO,
+            perturb_magnitude: 0.0,
+        }
+    }
+}
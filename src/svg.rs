@@ -0,0 +1,454 @@
+use crate::colormap::Colormap;
+use crate::error::GrowthError;
+use crate::geometry::{Polygon, Real, Vec2};
+use crate::history::History;
+use std::fmt::Write;
+
+// Reduction applied to a copy of the outline before it is saved as SVG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportReduction {
+    None,
+    ConvexHull,
+    Simplify,
+    Decimate,
+}
+
+impl ExportReduction {
+    pub const ALL: [Self; 4] = [Self::None, Self::ConvexHull, Self::Simplify, Self::Decimate];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "Full Outline",
+            Self::ConvexHull => "Convex Hull",
+            Self::Simplify => "Simplified",
+            Self::Decimate => "Decimated",
+        }
+    }
+
+    // Reduced copy of `polygon`; `simplify_tolerance` and `decimate_count` are only read
+    // by the reduction they belong to.
+    pub fn apply(
+        self,
+        polygon: &Polygon,
+        simplify_tolerance: Real,
+        decimate_count: usize,
+    ) -> Polygon {
+        match self {
+            Self::None => polygon.clone(),
+            Self::ConvexHull => polygon.convex_hull(),
+            Self::Simplify => polygon.simplify(simplify_tolerance),
+            Self::Decimate => polygon.decimate_to(decimate_count),
+        }
+    }
+}
+
+impl Polygon {
+    // Path data (`M x y L x y ...`) in world coordinates, ending in `Z` only when closed.
+    pub fn to_svg_path(&self) -> String {
+        let mut d = String::new();
+        for (i, v) in self.vertices().iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(d, "{command} {} {} ", v.x, v.y);
+        }
+        if !self.is_empty() && self.is_closed() {
+            d.push('Z');
+        }
+        d.trim_end().to_owned()
+    }
+
+    // One `<path>` per edge (edge `i` joins vertex `i` to `i + 1`, with the closing edge
+    // only when closed), stroked by mapping `values[i]` through `colormap` over the range
+    // of `values`.
+    pub fn to_svg_path_colored(
+        &self,
+        values: &[Real],
+        colormap: Colormap,
+    ) -> Result<String, GrowthError> {
+        let polygon = std::slice::from_ref(self);
+        let (min, span) = value_range(polygon, values)?;
+        let mut paths = String::new();
+        write_colored_edges(&mut paths, self, values, colormap, min, span);
+        Ok(paths)
+    }
+
+    // Like `to_svg_document`, with edges colored by `values` via `to_svg_path_colored`.
+    pub fn to_svg_document_colored(
+        &self,
+        values: &[Real],
+        colormap: Colormap,
+        stroke_width: Real,
+    ) -> Result<String, GrowthError> {
+        svg_document_colored(std::slice::from_ref(self), values, colormap, stroke_width)
+    }
+
+    // Parse path data made of straight segments (M, L, H, V, Z in absolute or relative
+    // form). Only the first subpath is read; a trailing vertex repeating the first one
+    // is dropped since the polygon is implicitly closed.
+    pub fn from_svg_path(d: &str) -> Result<Self, GrowthError> {
+        let tokens = tokenize_path(d)?;
+        let mut vertices: Vec<Vec2> = Vec::new();
+        let mut current = Vec2::ZERO;
+        let mut command = None;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let cmd = match tokens[i] {
+                PathToken::Command(c) => {
+                    i += 1;
+                    c
+                }
+                // Bare numbers repeat the previous command; after M they mean L.
+                PathToken::Number(_) => match command {
+                    Some('M') => 'L',
+                    Some('m') => 'l',
+                    Some(c) => c,
+                    None => {
+                        return Err(GrowthError::Parse(
+                            "path data must start with a command".to_owned(),
+                        ));
+                    }
+                },
+            };
+
+            let mut number = || -> Result<Real, GrowthError> {
+                match tokens.get(i) {
+                    Some(PathToken::Number(value)) => {
+                        i += 1;
+                        Ok(*value)
+                    }
+                    _ => Err(GrowthError::Parse(format!("missing coordinate for '{cmd}' command"))),
+                }
+            };
+
+            match cmd {
+                'M' | 'm' if !vertices.is_empty() => break,
+                'M' | 'L' => current = Vec2::new(number()?, number()?),
+                'm' | 'l' => current += Vec2::new(number()?, number()?),
+                'H' => current.x = number()?,
+                'h' => current.x += number()?,
+                'V' => current.y = number()?,
+                'v' => current.y += number()?,
+                'Z' | 'z' => break,
+                other => {
+                    return Err(GrowthError::Parse(format!("unsupported path command '{other}'")));
+                }
+            }
+            vertices.push(current);
+            command = Some(cmd);
+        }
+
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 3 {
+            return Err(GrowthError::Parse(format!(
+                "path has {} vertices; need at least 3",
+                vertices.len()
+            )));
+        }
+
+        let mut polygon = Polygon::new();
+        polygon.replace_vertices(vertices);
+        Ok(polygon)
+    }
+
+    // Standalone SVG document with the outline framed by its bounds. World y points up,
+    // so the path sits in a flipped group and keeps world coordinates in its data.
+    pub fn to_svg_document(&self, stroke_width: Real) -> String {
+        svg_document(std::slice::from_ref(self), stroke_width)
+    }
+}
+
+// `Polygon::to_svg_document` for several outlines: one `<path>` each, in order, framed
+// by their combined bounds. `polygons_from_svg` reads them back one polygon per path.
+pub fn svg_document(polygons: &[Polygon], stroke_width: Real) -> String {
+    let (x, y, w, h) = svg_view_box(polygons, stroke_width);
+    let mut paths = String::new();
+    for polygon in polygons {
+        let _ = writeln!(
+            paths,
+            "<path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"{stroke_width}\"/>",
+            polygon.to_svg_path()
+        );
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {w} {h}\">\n\
+         <g transform=\"scale(1 -1)\">\n\
+         {paths}</g>\n\
+         </svg>\n"
+    )
+}
+
+// `svg_document` with every edge colored as `Polygon::to_svg_path_colored` does. `values`
+// runs over the edges of all polygons in order and shares one color range.
+pub fn svg_document_colored(
+    polygons: &[Polygon],
+    values: &[Real],
+    colormap: Colormap,
+    stroke_width: Real,
+) -> Result<String, GrowthError> {
+    let (min, span) = value_range(polygons, values)?;
+    let mut paths = String::new();
+    let mut rest = values;
+    for polygon in polygons {
+        let (own, others) = rest.split_at(polygon.edge_count(polygon.is_closed()));
+        write_colored_edges(&mut paths, polygon, own, colormap, min, span);
+        rest = others;
+    }
+    let (x, y, w, h) = svg_view_box(polygons, stroke_width);
+    Ok(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {w} {h}\">\n\
+         <g transform=\"scale(1 -1)\" fill=\"none\" stroke-width=\"{stroke_width}\" \
+         stroke-linecap=\"round\">\n\
+         {paths}</g>\n\
+         </svg>\n"
+    ))
+}
+
+// Minimum and nonzero span of `values`, after checking there is one per edge.
+fn value_range(polygons: &[Polygon], values: &[Real]) -> Result<(Real, Real), GrowthError> {
+    let n: usize = polygons.iter().map(|p| p.edge_count(p.is_closed())).sum();
+    if values.len() != n {
+        return Err(GrowthError::InvalidParameter(format!(
+            "got {} values for {n} edges",
+            values.len()
+        )));
+    }
+    let min = values.iter().copied().fold(Real::INFINITY, Real::min);
+    let max = values.iter().copied().fold(Real::NEG_INFINITY, Real::max);
+    Ok((min, (max - min).max(1e-12)))
+}
+
+// One stroked `<path>` per edge of `polygon`, colored by its entry of `values`.
+fn write_colored_edges(
+    paths: &mut String,
+    polygon: &Polygon,
+    values: &[Real],
+    colormap: Colormap,
+    min: Real,
+    span: Real,
+) {
+    for (value, (start, end)) in values.iter().zip(polygon.edges()) {
+        let [r, g, b] = colormap.sample((value - min) / span);
+        let _ = writeln!(
+            paths,
+            "<path d=\"M {} {} L {} {}\" stroke=\"#{r:02x}{g:02x}{b:02x}\"/>",
+            start.x, start.y, end.x, end.y
+        );
+    }
+}
+
+// Combined bounds plus a margin, in the flipped coordinates used by the document group.
+fn svg_view_box(polygons: &[Polygon], stroke_width: Real) -> (Real, Real, Real, Real) {
+    let (min, max) = polygons
+        .iter()
+        .filter_map(Polygon::bounds)
+        .reduce(|(a0, a1), (b0, b1)| (a0.min(b0), a1.max(b1)))
+        .unwrap_or((Vec2::ZERO, Vec2::ZERO));
+    let margin = stroke_width.max((max - min).max_element() * 0.05);
+    (
+        min.x - margin,
+        -max.y - margin,
+        max.x - min.x + 2.0 * margin,
+        max.y - min.y + 2.0 * margin,
+    )
+}
+
+// Both sets of outlines plus one arrow per vertex displacement from each polygon of
+// `from` to the one at the same position in `to`, framed by their combined bounds. Uses
+// the same flipped group as `to_svg_document`.
+pub fn displacement_svg_document(from: &[Polygon], to: &[Polygon], stroke_width: Real) -> String {
+    let (min, max) = from
+        .iter()
+        .chain(to)
+        .filter_map(Polygon::bounds)
+        .reduce(|(a0, a1), (b0, b1)| (a0.min(b0), a1.max(b1)))
+        .unwrap_or((Vec2::ZERO, Vec2::ZERO));
+    let margin = stroke_width.max((max - min).max_element() * 0.05);
+    let (x, y) = (min.x - margin, -max.y - margin);
+    let (w, h) = (max.x - min.x + 2.0 * margin, max.y - min.y + 2.0 * margin);
+    let head = stroke_width * 4.0;
+
+    let mut arrows = String::new();
+    for (origin, offset) in from.iter().zip(to).flat_map(|(a, b)| a.displacements_to(b)) {
+        let tip = origin + offset;
+        let _ = writeln!(
+            arrows,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+            origin.x, origin.y, tip.x, tip.y
+        );
+    }
+    let outlines = |polygons: &[Polygon], stroke: &str| {
+        let mut paths = String::new();
+        for polygon in polygons {
+            let _ = writeln!(
+                paths,
+                "<path d=\"{}\" fill=\"none\" stroke=\"{stroke}\" \
+                 stroke-width=\"{stroke_width}\"/>",
+                polygon.to_svg_path()
+            );
+        }
+        paths
+    };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {w} {h}\">\n\
+         <defs><marker id=\"head\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+         markerUnits=\"userSpaceOnUse\" markerWidth=\"{head}\" markerHeight=\"{head}\" \
+         orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 Z\" fill=\"crimson\"/></marker></defs>\n\
+         <g transform=\"scale(1 -1)\">\n\
+         {}{}<g stroke=\"crimson\" stroke-width=\"{stroke_width}\" marker-end=\"url(#head)\">\n\
+         {arrows}</g>\n\
+         </g>\n\
+         </svg>\n",
+        outlines(from, "gray"),
+        outlines(to, "black")
+    )
+}
+
+// `(x, y, w, h)` of a flipped-y viewBox around all `polygons` with a 5% margin.
+pub(crate) fn shared_view_box<'a>(
+    polygons: impl IntoIterator<Item = &'a Polygon>,
+) -> (Real, Real, Real, Real) {
+    let (min, max) = polygons
+        .into_iter()
+        .filter_map(Polygon::bounds)
+        .reduce(|(a0, a1), (b0, b1)| (a0.min(b0), a1.max(b1)))
+        .unwrap_or((Vec2::ZERO, Vec2::ZERO));
+    let margin = ((max - min).max_element() * 0.05).max(1e-6);
+    (
+        min.x - margin,
+        -max.y - margin,
+        max.x - min.x + 2.0 * margin,
+        max.y - min.y + 2.0 * margin,
+    )
+}
+
+// Every history snapshot as one `<path>` per polygon in a single document, oldest first,
+// with stroke opacity ramping linearly from `1/n` for the oldest to 1 for the latest.
+pub fn history_trail_svg(history: &History) -> String {
+    let (x, y, w, h) = shared_view_box(history.iter().flat_map(|s| &s.polygons));
+    let n = history.len();
+    let mut paths = String::new();
+    for (i, snapshot) in history.iter().enumerate() {
+        let opacity = (i + 1) as Real / n as Real;
+        for polygon in &snapshot.polygons {
+            let _ = writeln!(
+                paths,
+                "<path d=\"{}\" stroke-opacity=\"{opacity}\" \
+                 vector-effect=\"non-scaling-stroke\"/>",
+                polygon.to_svg_path()
+            );
+        }
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {w} {h}\">\n\
+         <g transform=\"scale(1 -1)\" fill=\"none\" stroke=\"black\" stroke-width=\"1\">\n\
+         {paths}</g>\n\
+         </svg>\n"
+    )
+}
+
+// `d` attribute values of every `<path>` element, in document order.
+// This is a minimal scan, not an XML parser: it ignores comments, CDATA and entities.
+pub fn extract_path_data(svg: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut rest = svg;
+    while let Some(start) = rest.find("<path") {
+        let element = &rest[start..];
+        let end = element.find('>').unwrap_or(element.len());
+        let tag = &element[..end];
+        if let Some(d) = attribute_value(tag, "d") {
+            paths.push(d.to_owned());
+        }
+        rest = &element[end..];
+    }
+    paths
+}
+
+// One polygon per `<path>` that parses, in document order. Paths that fail are skipped
+// and reported by index with their error instead of aborting the whole document.
+pub fn polygons_from_svg(svg: &str) -> (Vec<Polygon>, Vec<(usize, GrowthError)>) {
+    let mut polygons = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, d) in extract_path_data(svg).iter().enumerate() {
+        match Polygon::from_svg_path(d) {
+            Ok(polygon) => polygons.push(polygon),
+            Err(err) => skipped.push((index, err)),
+        }
+    }
+    (polygons, skipped)
+}
+
+fn attribute_value<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut search = tag;
+    while let Some(pos) = search.find(name) {
+        // Require a word boundary before the name so `id=` does not match `d=`.
+        let preceded_by_space = pos > 0
+            && search[..pos]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_whitespace);
+        let after = search[pos + name.len()..].trim_start();
+        if preceded_by_space && let Some(after_eq) = after.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let quote = after_eq.chars().next()?;
+            if quote == '"' || quote == '\'' {
+                let value = &after_eq[1..];
+                let close = value.find(quote)?;
+                return Some(&value[..close]);
+            }
+        }
+        search = &search[pos + name.len()..];
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathToken {
+    Command(char),
+    Number(Real),
+}
+
+fn tokenize_path(d: &str) -> Result<Vec<PathToken>, GrowthError> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = d.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            tokens.push(PathToken::Command(c));
+            i += 1;
+        } else {
+            // Number: optional sign, digits with one dot, optional exponent.
+            let start = i;
+            if chars[i] == '-' || chars[i] == '+' {
+                i += 1;
+            }
+            let mut seen_dot = false;
+            while i < chars.len() && (chars[i].is_ascii_digit() || (chars[i] == '.' && !seen_dot)) {
+                seen_dot |= chars[i] == '.';
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                i += 1;
+                if i < chars.len() && (chars[i] == '-' || chars[i] == '+') {
+                    i += 1;
+                }
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            if i == start {
+                return Err(GrowthError::Parse(format!("unexpected character '{c}' in path data")));
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<Real>()
+                .map_err(|_| GrowthError::Parse(format!("invalid number '{text}' in path data")))?;
+            tokens.push(PathToken::Number(value));
+        }
+    }
+    Ok(tokens)
+}
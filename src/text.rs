@@ -0,0 +1,120 @@
+use crate::error::GrowthError;
+use crate::geometry::{Polygon, Real, Vec2};
+use ttf_parser::{Face, OutlineBuilder};
+
+// Outline of `ch` in `font` (TrueType or OpenType bytes, first face), one polygon per
+// contour in the font's order. The em square is scaled to `size` and the glyph's bounding
+// box is centered on the origin. Curves are cut into equal parameter steps, as few as keep
+// every chord within `tolerance` of the curve, so equal inputs give equal vertices.
+// Contours keep the font's winding: outer ones usually run clockwise, counters the other
+// way. Contours with fewer than three vertices are dropped.
+#[cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+pub fn glyph_polygons(
+    font: &[u8],
+    ch: char,
+    size: Real,
+    tolerance: Real,
+) -> Result<Vec<Polygon>, GrowthError> {
+    if size <= 0.0 || tolerance <= 0.0 {
+        return Err(GrowthError::InvalidParameter(format!(
+            "glyph size {size} and tolerance {tolerance} must be positive"
+        )));
+    }
+    let face = Face::parse(font, 0)
+        .map_err(|err| GrowthError::Parse(format!("cannot read font: {err}")))?;
+    let glyph = face
+        .glyph_index(ch)
+        .ok_or_else(|| GrowthError::InvalidParameter(format!("font has no glyph for {ch:?}")))?;
+    let scale = size / face.units_per_em() as Real;
+    let mut flattener = Flattener {
+        // Measured in font units, where the curve points arrive.
+        tolerance: tolerance / scale,
+        current: Vec2::ZERO,
+        contour: Vec::new(),
+        contours: Vec::new(),
+    };
+    let Some(bbox) = face.outline_glyph(glyph, &mut flattener) else {
+        return Ok(Vec::new());
+    };
+    flattener.close();
+
+    let center = Vec2::new(
+        (bbox.x_min as Real + bbox.x_max as Real) * 0.5,
+        (bbox.y_min as Real + bbox.y_max as Real) * 0.5,
+    );
+    let mut polygons = Vec::with_capacity(flattener.contours.len());
+    for contour in flattener.contours {
+        let mut polygon = Polygon::new();
+        polygon.replace_vertices(contour.into_iter().map(|p| (p - center) * scale).collect());
+        polygons.push(polygon);
+    }
+    Ok(polygons)
+}
+
+// Collects contours in font units.
+struct Flattener {
+    tolerance: Real,
+    current: Vec2,
+    contour: Vec<Vec2>,
+    contours: Vec<Vec<Vec2>>,
+}
+
+impl Flattener {
+    // Points along `at(t)` for t in (0, 1], in `ceil(sqrt(bound / tolerance))` equal steps.
+    // `bound` is an eighth of the curve's largest second derivative, which bounds the
+    // chord error of a unit step.
+    fn push_curve(&mut self, bound: Real, at: impl Fn(Real) -> Vec2) {
+        let steps = ((bound / self.tolerance).sqrt().ceil() as usize).clamp(1, 1024);
+        for i in 1..=steps {
+            self.contour.push(at(i as Real / steps as Real));
+        }
+    }
+}
+
+#[cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+impl OutlineBuilder for Flattener {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.current = Vec2::new(x as Real, y as Real);
+        self.contour.push(self.current);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.current = Vec2::new(x as Real, y as Real);
+        self.contour.push(self.current);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p0, p1) = (self.current, Vec2::new(x1 as Real, y1 as Real));
+        let p2 = Vec2::new(x as Real, y as Real);
+        let bound = (p0 - 2.0 * p1 + p2).length() * 0.25;
+        self.push_curve(bound, |t| {
+            let s = 1.0 - t;
+            p0 * (s * s) + p1 * (2.0 * s * t) + p2 * (t * t)
+        });
+        self.current = p2;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p0, p1) = (self.current, Vec2::new(x1 as Real, y1 as Real));
+        let (p2, p3) = (Vec2::new(x2 as Real, y2 as Real), Vec2::new(x as Real, y as Real));
+        let bound = (p0 - 2.0 * p1 + p2).length().max((p1 - 2.0 * p2 + p3).length()) * 0.75;
+        self.push_curve(bound, |t| {
+            let s = 1.0 - t;
+            p0 * (s * s * s) + p1 * (3.0 * s * s * t) + p2 * (3.0 * s * t * t) + p3 * (t * t * t)
+        });
+        self.current = p3;
+    }
+
+    // Also called by `move_to` and at the end, since not every font closes each contour.
+    fn close(&mut self) {
+        let mut contour = std::mem::take(&mut self.contour);
+        // An explicit segment back to the start would duplicate it.
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if contour.len() >= 3 {
+            self.contours.push(contour);
+        }
+    }
+}
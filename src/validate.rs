@@ -0,0 +1,160 @@
+use crate::geometry::{next_index, signed_area, Polygon, Real, Vec2};
+use std::cmp::Ordering;
+
+// Edges at or below this length count as degenerate.
+const DEGENERATE_EDGE_LENGTH: Real = 1e-12;
+
+// Problem found by `Polygon::validate`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PolygonWarning {
+    // Fewer than three vertices; nothing else is checked.
+    TooFewVertices(usize),
+    // Edge `index` (vertex `index` to `index + 1`) has near-zero length.
+    DegenerateEdge { index: usize },
+    // Enclosed area is tiny relative to the squared perimeter, e.g. a collapsed loop.
+    NearZeroArea,
+    // Vertices run clockwise. Forces handle either winding; `repair` normalizes to CCW.
+    Clockwise,
+    // Two non-adjacent edges cross or touch.
+    SelfIntersection { first: usize, second: usize },
+}
+
+impl PolygonWarning {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::TooFewVertices(n) => format!("only {n} vertices"),
+            Self::DegenerateEdge { index } => format!("edge {index} has zero length"),
+            Self::NearZeroArea => "near-zero area".to_owned(),
+            Self::Clockwise => "clockwise winding".to_owned(),
+            Self::SelfIntersection { first, second } => {
+                format!("edges {first} and {second} intersect")
+            }
+        }
+    }
+}
+
+impl Polygon {
+    // Health check for the outline. Intersections are found with a sweep over edge
+    // x-extents, so typical growth shapes cost close to O(n log n).
+    pub fn validate(&self) -> Vec<PolygonWarning> {
+        let vertices = self.vertices();
+        let n = vertices.len();
+        if n < 3 {
+            return vec![PolygonWarning::TooFewVertices(n)];
+        }
+
+        let mut warnings: Vec<PolygonWarning> = (0..n)
+            .filter(|&i| vertices[i].distance(vertices[next_index(i, n)]) <= DEGENERATE_EDGE_LENGTH)
+            .map(|index| PolygonWarning::DegenerateEdge { index })
+            .collect();
+        let area = self.signed_area();
+        let perimeter = self.perimeter();
+        if area.abs() <= 1e-9 * perimeter * perimeter {
+            warnings.push(PolygonWarning::NearZeroArea);
+        } else if area < 0.0 {
+            warnings.push(PolygonWarning::Clockwise);
+        }
+        warnings.extend(
+            intersecting_edges(vertices)
+                .into_iter()
+                .map(|(first, second)| PolygonWarning::SelfIntersection { first, second }),
+        );
+        warnings
+    }
+
+    // Drop vertices that duplicate their predecessor and reverse clockwise loops so the
+    // winding is counter-clockwise. Returns the original index of each kept vertex, in
+    // the new order, so parallel per-vertex data can follow. Intersections are left alone.
+    pub fn repair(&mut self) -> Vec<usize> {
+        let vertices = self.vertices();
+        // Compare against the last kept vertex so runs of duplicates collapse to one.
+        let mut kept: Vec<usize> = Vec::with_capacity(vertices.len());
+        for (i, v) in vertices.iter().enumerate() {
+            if kept
+                .last()
+                .is_none_or(|&last| vertices[last].distance(*v) > DEGENERATE_EDGE_LENGTH)
+            {
+                kept.push(i);
+            }
+        }
+        // The closing edge: drop trailing vertices that repeat the first.
+        while kept.len() > 1
+            && vertices[kept[kept.len() - 1]].distance(vertices[kept[0]]) <= DEGENERATE_EDGE_LENGTH
+        {
+            kept.pop();
+        }
+
+        let mut repaired: Vec<Vec2> = kept.iter().map(|&k| vertices[k]).collect();
+        if signed_area(&repaired) < 0.0 {
+            // Keep vertex 0 first so the loop only changes direction.
+            repaired[1..].reverse();
+            kept[1..].reverse();
+        }
+        self.replace_vertices(repaired);
+        kept
+    }
+
+    // Pairs `(i, j)` of edge `i` of this outline and edge `j` of `other` that cross or
+    // touch, sorted. Empty when the two outlines are disjoint or one lies inside the other.
+    pub fn crossings(&self, other: &Polygon) -> Vec<(usize, usize)> {
+        let (a, b) = (self.vertices(), other.vertices());
+        let edge = |v: &[Vec2], i: usize| (v[i], v[next_index(i, v.len())]);
+        let mut pairs = Vec::new();
+        for i in 0..a.len() {
+            let (p, q) = edge(a, i);
+            for j in 0..b.len() {
+                let (r, s) = edge(b, j);
+                if segments_intersect(p, q, r, s) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+// Pairs `(i, j)` with `i < j` of non-adjacent edges that intersect.
+pub(crate) fn intersecting_edges(vertices: &[Vec2]) -> Vec<(usize, usize)> {
+    let n = vertices.len();
+    let edge = |i: usize| (vertices[i], vertices[next_index(i, n)]);
+    let mut order: Vec<usize> = (0..n).collect();
+    let min_x = |i: usize| edge(i).0.x.min(edge(i).1.x);
+    let max_x = |i: usize| edge(i).0.x.max(edge(i).1.x);
+    order.sort_by(|&a, &b| min_x(a).partial_cmp(&min_x(b)).unwrap_or(Ordering::Equal));
+
+    let mut pairs = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    for &i in &order {
+        active.retain(|&j| max_x(j) >= min_x(i));
+        for &j in &active {
+            let adjacent = next_index(i, n) == j || next_index(j, n) == i;
+            let (a, b) = edge(i);
+            let (c, d) = edge(j);
+            if !adjacent && segments_intersect(a, b, c, d) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+        active.push(i);
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+fn segments_intersect(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let orient = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p);
+    let on_segment = |p: Vec2, q: Vec2, r: Vec2| {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    };
+    let (d1, d2) = (orient(c, d, a), orient(c, d, b));
+    let (d3, d4) = (orient(a, b, c), orient(a, b, d));
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+    // Collinear touching cases.
+    (d1 == 0.0 && on_segment(c, d, a))
+        || (d2 == 0.0 && on_segment(c, d, b))
+        || (d3 == 0.0 && on_segment(a, b, c))
+        || (d4 == 0.0 && on_segment(a, b, d))
+}
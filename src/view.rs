@@ -0,0 +1,36 @@
+use crate::geometry::{Real, Vec2};
+use eframe::egui::{self, Pos2};
+
+// Mapping between world units and viewport pixels for one frame.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewTransform {
+    // Screen position that the view center maps to.
+    pub screen_center: Pos2,
+    // World point framed at the viewport center, before panning.
+    pub center: Vec2,
+    // Pixels per world unit.
+    pub scale: Real,
+    // World-space offset applied on top of `center`.
+    pub pan: Vec2,
+}
+
+// egui works in f32, so these casts are no-ops under the `f32` feature.
+#[cfg_attr(feature = "f32", allow(clippy::unnecessary_cast))]
+impl ViewTransform {
+    pub fn to_screen(self, p: Vec2) -> Pos2 {
+        let local = (p - self.center - self.pan) * self.scale;
+        Pos2::new(
+            self.screen_center.x + local.x as f32,
+            self.screen_center.y - local.y as f32,
+        )
+    }
+
+    pub fn to_world(self, p: Pos2) -> Vec2 {
+        self.center + self.pan + self.to_world_delta(p - self.screen_center)
+    }
+
+    // Convert a screen-space offset into a world-space offset (y flipped).
+    pub fn to_world_delta(self, d: egui::Vec2) -> Vec2 {
+        Vec2::new(d.x as Real, -d.y as Real) / self.scale
+    }
+}
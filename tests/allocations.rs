@@ -0,0 +1,85 @@
+// Counts heap allocations made on the test's own thread, so steady-state steps can be
+// checked to reuse their buffers.
+use dg4::integrator::Midpoint;
+use dg4::sim::{SimParams, SimParamsBuilder, Simulation};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+// Steps that leave the vertex count alone allocate nothing once the buffers have grown.
+fn assert_steady_state_is_allocation_free(mut sim: Simulation, params: SimParams) {
+    for _ in 0..3 {
+        sim.step(params);
+    }
+    let count = sim.polygon().len();
+    let allocations = allocations_during(|| {
+        for _ in 0..20 {
+            sim.step(params);
+        }
+    });
+    assert_eq!(sim.polygon().len(), count, "vertex count changed");
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn default_steps_do_not_allocate() {
+    assert_steady_state_is_allocation_free(Simulation::benchmark(300), SimParams::default());
+}
+
+#[test]
+fn growth_steps_do_not_allocate() {
+    let params = SimParamsBuilder::new()
+        .growth_enabled(true)
+        .growth_density_gain(0.5)
+        .growth_normal_smoothing(0.5)
+        .repulsion_max_neighbors(8)
+        .adaptive_repulsion(true)
+        .merge_length(Some(1e-4))
+        .min_area(Some(0.1))
+        .build();
+    assert_steady_state_is_allocation_free(Simulation::benchmark(300), params);
+}
+
+#[test]
+fn unsplit_steps_do_not_allocate() {
+    let params = SimParamsBuilder::new().split_enabled(true).split_length(10.0).build();
+    assert_steady_state_is_allocation_free(Simulation::benchmark(300), params);
+}
+
+#[test]
+fn midpoint_steps_do_not_allocate() {
+    let mut sim = Simulation::benchmark(300);
+    sim.set_integrator(Box::new(Midpoint::default()));
+    let params = SimParamsBuilder::new().growth_enabled(true).build();
+    assert_steady_state_is_allocation_free(sim, params);
+}
@@ -0,0 +1,60 @@
+use dg4::array_polygon::ArrayPolygon;
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::{compute_delta, SimParams};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// The forces `ArrayPolygon::step` supports, with jitter off so the heap pass needs no RNG.
+fn fixed_params() -> SimParams {
+    SimParams {
+        jitter_enabled: false,
+        growth_rate: 0.002,
+        repulsion_radius: 0.1,
+        ..SimParams::default()
+    }
+}
+
+#[test]
+fn matches_the_heap_polygon() {
+    let fixed = ArrayPolygon::<64>::regular_ngon(0.3, 48).unwrap();
+    let heap = Polygon::regular_ngon(0.3, 48);
+    assert_eq!(fixed.to_polygon().vertices(), heap.vertices());
+    assert!((fixed.perimeter() - heap.perimeter()).abs() < 1e-9);
+    assert!((fixed.signed_area() - heap.signed_area()).abs() < 1e-9);
+
+    let params = fixed_params();
+    let mut fixed = fixed;
+    let mut positions = heap.vertices().to_vec();
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..10 {
+        fixed.step(&params);
+        let delta = compute_delta(&positions, &params, &mut rng);
+        for (p, d) in positions.iter_mut().zip(delta) {
+            *p += d;
+        }
+    }
+    let worst = fixed
+        .vertices()
+        .iter()
+        .zip(&positions)
+        .map(|(a, b)| a.distance(*b))
+        .fold(0.0, Real::max);
+    assert!(worst < 1e-5, "vertices differ by up to {worst}");
+    assert!(fixed.perimeter() > heap.perimeter(), "growth had no effect");
+}
+
+#[test]
+fn capacity_bounds_push_and_regular_ngon() {
+    assert!(ArrayPolygon::<8>::regular_ngon(1.0, 9).is_none());
+    let mut polygon = ArrayPolygon::<8>::regular_ngon(1.0, 8).unwrap();
+    assert_eq!(polygon.len(), polygon.capacity());
+    let extra = Vec2::new(2.0, 0.0);
+    assert_eq!(polygon.push(extra), Err(extra));
+
+    let mut polygon = ArrayPolygon::<3>::new();
+    assert!(polygon.is_empty());
+    for v in [Vec2::ZERO, Vec2::X, Vec2::Y] {
+        polygon.push(v).unwrap();
+    }
+    assert!((polygon.signed_area() - 0.5).abs() < 1e-9);
+}
@@ -0,0 +1,57 @@
+use dg4::geometry::Polygon;
+use dg4::sim::{
+    average_edge_length, SimParams, SimParamsBuilder, Simulation, BENCHMARK_EDGE_LENGTH,
+    BENCHMARK_SEED,
+};
+
+#[test]
+fn with_ngon_installs_the_regular_polygon() {
+    let sim = Simulation::with_ngon(3, 2.0, 12);
+    assert_eq!(sim.seed(), 3);
+    assert_eq!(sim.generation(), 0);
+    assert!(sim.polygon().approx_eq(&Polygon::regular_ngon(2.0, 12), 1e-6));
+}
+
+#[test]
+fn benchmark_states_are_fixed() {
+    for vertices in [100, 1_000, 10_000] {
+        let sim = Simulation::benchmark(vertices);
+        assert_eq!(sim.seed(), BENCHMARK_SEED);
+        assert_eq!(sim.polygon().len(), vertices);
+        let spacing = average_edge_length(sim.polygon());
+        assert!((spacing / BENCHMARK_EDGE_LENGTH - 1.0).abs() < 0.01, "spacing {spacing}");
+        assert_eq!(sim.state_hash(), Simulation::benchmark(vertices).state_hash());
+    }
+}
+
+// The states the benches time, stepped a few times the same way.
+#[test]
+fn benchmark_states_step_reproducibly() {
+    let step = SimParamsBuilder::new().target_edge_length(BENCHMARK_EDGE_LENGTH).build();
+    let split = SimParamsBuilder::new()
+        .edge_regularization_enabled(false)
+        .repulsion_enabled(false)
+        .jitter_enabled(false)
+        .split_enabled(true)
+        .split_length(BENCHMARK_EDGE_LENGTH / 2.0)
+        .build();
+    let run = |params: SimParams| {
+        let mut sim = Simulation::benchmark(1_000);
+        for _ in 0..3 {
+            sim.step(params);
+        }
+        sim
+    };
+    let stepped = run(step);
+    assert_eq!(stepped.polygon().len(), 1_000);
+    assert_eq!(stepped.state_hash(), run(step).state_hash());
+    // Every edge starts over the split length, so the first step cuts each one at least
+    // in two.
+    let split_once = {
+        let mut sim = Simulation::benchmark(1_000);
+        sim.step(split);
+        sim
+    };
+    assert!(split_once.polygon().len() >= 2_000);
+    assert_eq!(run(split).state_hash(), run(split).state_hash());
+}
@@ -0,0 +1,118 @@
+use dg4::config_code::{decode_config, decode_params, encode_config, encode_params, SharedConfig};
+use dg4::error::GrowthError;
+use dg4::geometry::{CentroidKind, Real, Vec2};
+use dg4::render::{Appearance, VertexMarker};
+use dg4::sim::{
+    Attractor, ConstraintFalloff, ConstraintShape, GrowthCurvatureFilter, SimParams, Simulation,
+    DEFAULT_FORCE_ORDER,
+};
+use dg4::starter::{Starter, StarterShape};
+
+// Every kind of field away from its default, with values that do not round-trip through
+// a short decimal string.
+fn unusual_params() -> SimParams {
+    let third = 1.0 / 3.0 as Real;
+    let mut force_order = DEFAULT_FORCE_ORDER;
+    force_order.reverse();
+    let mut params = SimParams {
+        edge_regularization_enabled: false,
+        target_edge_length: 0.1 + 0.2,
+        edge_stiffness_tension: Some(third),
+        edge_dead_zone: 1e-7,
+        repulsion_threads: 3,
+        repulsion_max_neighbors: 17,
+        adaptive_repulsion: true,
+        growth_enabled: true,
+        growth_rate: third * 1e-3,
+        growth_area_goal: Some(12.5),
+        min_area: Some(Real::MIN_POSITIVE),
+        growth_curvature_filter: GrowthCurvatureFilter::ConcaveOnly,
+        split_refractory_generations: u64::MAX,
+        constraint_shape: ConstraintShape::Rectangle {
+            half_width: 1.25,
+            half_height: third,
+        },
+        constraint_falloff: ConstraintFalloff::Quadratic,
+        constraint_center: Vec2::new(-0.5, third),
+        attractor: Some(Attractor {
+            position: Vec2::new(third, -2.0),
+            radius: 0.75,
+            strength: -0.01,
+        }),
+        centroid_kind: CentroidKind::Area,
+        force_order,
+        ..SimParams::default()
+    };
+    params.growth_inhibitors[3] = Some(Attractor {
+        position: Vec2::ZERO,
+        radius: third,
+        strength: 1.0,
+    });
+    params
+}
+
+#[test]
+fn config_code_reproduces_the_exact_params() {
+    let config = SharedConfig {
+        params: unusual_params(),
+        seed: u64::MAX - 1,
+        starter: Starter {
+            shape: StarterShape::Star,
+            sides: 7,
+            star_inner_ratio: 0.4,
+            center: Vec2::new(0.25, -0.125),
+            ..Starter::default()
+        },
+        appearance: Appearance::default(),
+    };
+    let code = encode_config(&config);
+    assert!(code.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+    assert_eq!(decode_config(&code).unwrap(), config);
+
+    let params_code = encode_params(&config.params, config.seed);
+    assert_eq!(decode_params(&params_code).unwrap(), (config.params, config.seed));
+    // The two kinds of code are not interchangeable.
+    assert!(matches!(decode_params(&code), Err(GrowthError::Parse(_))));
+    assert!(matches!(decode_config(&params_code), Err(GrowthError::Parse(_))));
+}
+
+#[test]
+fn config_code_carries_the_appearance() {
+    let config = SharedConfig {
+        params: SimParams::default(),
+        seed: 7,
+        starter: Starter::default(),
+        appearance: Appearance {
+            background: [245, 245, 245, 255],
+            curve: [30, 30, 30, 255],
+            vertex: [200, 70, 40, 255],
+            constraint_fill: [40, 60, 90, 20],
+            constraint_stroke: [40, 60, 90, 90],
+            vertex_marker: VertexMarker::Square,
+            vertex_marker_size: 4.5,
+            line_width: 3.25,
+        },
+    };
+    let decoded = decode_config(&encode_config(&config)).unwrap();
+    assert_eq!(decoded.appearance, config.appearance);
+    assert_eq!(decoded, config);
+}
+
+#[test]
+fn loading_a_params_file_leaves_the_polygon_alone() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("params.dg4");
+    let saved = unusual_params();
+    std::fs::write(&path, encode_params(&saved, 42)).unwrap();
+
+    let mut sim = Simulation::benchmark(64);
+    for _ in 0..5 {
+        sim.step(SimParams::default());
+    }
+    let (polygon, generation) = (sim.polygon().clone(), sim.generation());
+    // What the app's Load Params does: take the params and seed, keep the shape.
+    let (params, seed) = decode_params(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    sim.reset_seed(seed);
+    assert_eq!((params, sim.seed()), (saved, 42));
+    assert_eq!(sim.polygon(), &polygon);
+    assert_eq!(sim.generation(), generation);
+}
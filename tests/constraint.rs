@@ -0,0 +1,201 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::{
+    framing_bounds, triangle_vertices, ConstraintShape, SimParams, SimParamsBuilder, Simulation,
+};
+
+// Only the constraint acts, strong enough to overshoot by far if unclamped.
+fn constraint_only(shape: ConstraintShape) -> SimParams {
+    SimParamsBuilder::new()
+        .edge_regularization_enabled(false)
+        .repulsion_enabled(false)
+        .jitter_enabled(false)
+        .constraint_enabled(true)
+        .constraint_shape(shape)
+        .constraint_size(1.0)
+        .constraint_strength(1e6)
+        .build()
+}
+
+// Steps a triangle whose first vertex is `outside` and returns where that vertex lands.
+fn land(outside: Vec2, shape: ConstraintShape) -> Vec2 {
+    land_with(outside, constraint_only(shape))
+}
+
+fn land_with(start: Vec2, params: SimParams) -> Vec2 {
+    let mut sim = Simulation::new(1);
+    sim.set_polygon(
+        Polygon::from_svg_path(&format!("M {} {} L 0 0.1 L -0.1 -0.1 Z", start.x, start.y))
+            .unwrap(),
+    );
+    sim.step(params);
+    sim.polygon().vertices()[0]
+}
+
+fn assert_near(actual: Vec2, expected: Vec2) {
+    let tolerance: Real = 1e-5;
+    assert!(actual.distance(expected) < tolerance, "{actual} != {expected}");
+}
+
+#[test]
+fn strong_circle_constraint_lands_on_the_boundary() {
+    assert_near(land(Vec2::new(1.01, 0.0), ConstraintShape::Circle), Vec2::new(1.0, 0.0));
+}
+
+#[test]
+fn strong_triangle_constraint_lands_on_the_corner() {
+    let corner = triangle_vertices(1.0)[2];
+    // Past both edges that meet at the corner.
+    assert_near(land(corner * 1.02, ConstraintShape::Triangle), corner);
+}
+
+#[test]
+fn strong_triangle_constraint_lands_on_an_edge() {
+    let [_, b, c] = triangle_vertices(1.0);
+    let mid = b.midpoint(c);
+    assert_near(land(mid - Vec2::new(0.0, 0.05), ConstraintShape::Triangle), mid);
+}
+
+#[test]
+fn rectangle_pushes_back_along_the_overshooting_axis() {
+    let wide = ConstraintShape::Rectangle {
+        half_width: 2.0,
+        half_height: 0.5,
+    };
+    assert_near(land(Vec2::new(0.3, 0.6), wide), Vec2::new(0.3, 0.5));
+    assert_near(land(Vec2::new(2.1, -0.2), wide), Vec2::new(2.0, -0.2));
+    // Inside the rectangle but outside the unit circle the size would give.
+    assert_near(land(Vec2::new(1.5, 0.0), wide), Vec2::new(1.5, 0.0));
+}
+
+#[test]
+fn inverted_constraint_pushes_interior_vertices_out() {
+    let inside = Vec2::new(0.9, 0.0);
+    assert_near(land(inside, ConstraintShape::Circle), inside);
+    let inverted = SimParams {
+        constraint_inverted: true,
+        ..constraint_only(ConstraintShape::Circle)
+    };
+    assert_near(land_with(inside, inverted), Vec2::new(1.0, 0.0));
+    // Outside the inverted area nothing pulls the vertex back in.
+    let outside = Vec2::new(1.2, 0.0);
+    assert_near(land_with(outside, inverted), outside);
+}
+
+#[test]
+fn framing_covers_a_large_constraint_around_a_small_polygon() {
+    let center = Vec2::new(0.5, 0.0);
+    let small = Polygon::regular_ngon_at(center, 0.1, 8);
+    let (min, max) = small.bounds().unwrap();
+    assert_eq!(framing_bounds(&small, None), Some((min, max)));
+
+    let framed = framing_bounds(&small, Some((ConstraintShape::Circle, 2.0, center)));
+    assert_eq!(framed, Some((Vec2::new(-1.5, -2.0), Vec2::new(2.5, 2.0))));
+    // With nothing to frame but the constraint, its box is used alone.
+    let empty = framing_bounds(&Polygon::new(), Some((ConstraintShape::Square, 1.0, Vec2::ZERO)));
+    assert_eq!(empty, Some((Vec2::splat(-1.0), Vec2::splat(1.0))));
+    assert_eq!(framing_bounds(&Polygon::new(), None), None);
+}
+
+fn steps(sim: &mut Simulation, params: SimParams, count: usize) {
+    for _ in 0..count {
+        sim.step(params);
+    }
+}
+
+#[test]
+fn constraint_strength_ramps_in_after_enabling() {
+    let off = SimParams::default();
+    let on = SimParams {
+        constraint_enabled: true,
+        constraint_strength: 0.2,
+        constraint_ramp_generations: 10,
+        ..off
+    };
+    let mut sim = Simulation::benchmark(32);
+    steps(&mut sim, off, 5);
+    assert_eq!(sim.effective_constraint_strength(&on), 0.0);
+    steps(&mut sim, on, 5);
+    assert!((sim.effective_constraint_strength(&on) - 0.1).abs() < 1e-6);
+    steps(&mut sim, on, 5);
+    assert_eq!(sim.effective_constraint_strength(&on), 0.2);
+    steps(&mut sim, on, 20);
+    assert_eq!(sim.effective_constraint_strength(&on), 0.2);
+
+    // Turning it off and on again starts a new ramp.
+    steps(&mut sim, off, 1);
+    assert_eq!(sim.effective_constraint_strength(&on), 0.0);
+    let instant = SimParams {
+        constraint_ramp_generations: 0,
+        ..on
+    };
+    assert_eq!(sim.effective_constraint_strength(&instant), 0.2);
+}
+
+#[test]
+fn constraint_region_grows_and_drifts_with_the_generation() {
+    let params = SimParams {
+        constraint_size_rate: 0.01,
+        constraint_center_velocity: Vec2::new(0.02, 0.0),
+        ..SimParams::default()
+    };
+    let mut sim = Simulation::benchmark(32);
+    let (shape, size, center) = sim.constraint_region(&params);
+    assert_eq!(shape, params.constraint_shape);
+    assert_eq!((size, center), (params.constraint_size, Vec2::ZERO));
+    steps(&mut sim, params, 10);
+    let (_, later_size, later_center) = sim.constraint_region(&params);
+    assert!((later_size - (size + 0.1)).abs() < 1e-6);
+    assert!(later_center.distance(Vec2::new(0.2, 0.0)) < 1e-6);
+
+    let rectangle = SimParams {
+        constraint_shape: ConstraintShape::Rectangle {
+            half_width: 1.0,
+            half_height: 0.5,
+        },
+        ..params
+    };
+    match sim.constraint_region(&rectangle).0 {
+        ConstraintShape::Rectangle {
+            half_width,
+            half_height,
+        } => assert!((half_width - 1.1).abs() < 1e-6 && (half_height - 0.6).abs() < 1e-6),
+        other => panic!("{other:?}"),
+    }
+    // Shrinking stops at nothing rather than turning inside out.
+    let shrinking = SimParams {
+        constraint_size_rate: -1.0,
+        ..params
+    };
+    assert_eq!(sim.constraint_region(&shrinking).1, 0.0);
+}
+
+#[test]
+fn every_constraint_shape_classifies_points() {
+    let center = Vec2::new(1.0, -1.0);
+    let rectangle = ConstraintShape::Rectangle {
+        half_width: 2.0,
+        half_height: 0.5,
+    };
+    // (shape, clearly inside, on the boundary, clearly outside), relative to the center.
+    let cases = [
+        (ConstraintShape::Circle, Vec2::new(0.5, 0.5), Vec2::new(0.0, 1.0), Vec2::new(0.8, 0.8)),
+        (ConstraintShape::Square, Vec2::new(0.9, -0.9), Vec2::new(1.0, 0.3), Vec2::new(1.1, 0.0)),
+        (rectangle, Vec2::new(1.9, 0.4), Vec2::new(-2.0, 0.0), Vec2::new(0.0, 0.6)),
+    ];
+    for (shape, inside, boundary, outside) in cases {
+        assert!(shape.contains(center + inside, 1.0, center, 0.0), "{shape:?} inside");
+        assert!(shape.contains(center + boundary, 1.0, center, 0.0), "{shape:?} boundary");
+        assert!(!shape.contains(center + outside, 1.0, center, 0.0), "{shape:?} outside");
+    }
+
+    let [a, b, c] = triangle_vertices(1.0);
+    let triangle = ConstraintShape::Triangle;
+    assert!(triangle.contains((a + b + c) / 3.0, 1.0, Vec2::ZERO, 0.0));
+    assert!(triangle.contains(a.lerp(b, 0.5), 1.0, Vec2::ZERO, 0.0));
+    assert!(!triangle.contains(a * 1.1, 1.0, Vec2::ZERO, 0.0));
+
+    // A quarter turn swaps the rectangle's extents.
+    assert!(!rectangle.contains(Vec2::new(0.0, 1.5), 1.0, Vec2::ZERO, 0.0));
+    let quarter = std::f64::consts::FRAC_PI_2 as Real;
+    assert!(rectangle.contains(Vec2::new(0.0, 1.5), 1.0, Vec2::ZERO, quarter));
+}
@@ -0,0 +1,180 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::force::{Force, ForceContext, PolygonMeta};
+use dg4::sim::{
+    compute_delta, effective_repulsion_strength, force_stack, ForceKind, SimParams, Simulation,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Springs alone toward unit edges.
+fn springs() -> SimParams {
+    SimParams {
+        repulsion_enabled: false,
+        jitter_enabled: false,
+        target_edge_length: 1.0,
+        ..SimParams::default()
+    }
+}
+
+fn square(side: Real) -> Vec<Vec2> {
+    let mut square = Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap();
+    square.map_vertices(|v| v * side);
+    square.vertices().to_vec()
+}
+
+fn delta(positions: &[Vec2], params: &SimParams) -> Vec<Vec2> {
+    compute_delta(positions, params, &mut StdRng::seed_from_u64(1))
+}
+
+#[test]
+fn tension_and_compression_stiffness_differ() {
+    let params = SimParams {
+        edge_stiffness_tension: Some(0.4),
+        edge_stiffness_compression: Some(0.1),
+        ..springs()
+    };
+    // Both squares are off by 0.2 per edge, one stretched and one compressed.
+    let stretched = delta(&square(1.2), &params)[0];
+    let compressed = delta(&square(0.8), &params)[0];
+    assert!((stretched.length() - 4.0 * compressed.length()).abs() < 1e-6);
+    // Stretched edges pull the corner inward, compressed ones push it out.
+    assert!(stretched.x > 0.0 && compressed.x < 0.0);
+
+    let symmetric = delta(&square(1.2), &springs())[0].length();
+    assert!((symmetric - delta(&square(0.8), &springs())[0].length()).abs() < 1e-6);
+}
+
+// Long thin rectangle sampled every 0.05 along its length, whose sides are `gap` apart
+// and far apart along the curve.
+fn hairpin(gap: Real) -> Vec<Vec2> {
+    let bottom = (0..=40).map(|k| Vec2::new(k as Real * 0.05, 0.0));
+    let top = (0..=40).rev().map(|k| Vec2::new(k as Real * 0.05, gap));
+    bottom.chain(top).collect()
+}
+
+#[test]
+fn adaptive_repulsion_rises_as_the_curve_closes_in() {
+    let params = SimParams {
+        adaptive_repulsion: true,
+        ..SimParams::default()
+    };
+    let base = params.repulsion_strength;
+    let strength = |gap| effective_repulsion_strength(&hairpin(gap), &params);
+    assert_eq!(strength(0.5), base);
+    assert!((strength(0.1) - 1.5 * base).abs() < 1e-9);
+    assert!((strength(0.05) - 3.0 * base).abs() < 1e-9);
+    // Capped at `adaptive_repulsion_max`, and back to the base once the sides separate.
+    assert_eq!(strength(0.001), params.adaptive_repulsion_max * base);
+    assert_eq!(strength(0.3), base);
+}
+
+#[test]
+fn excluding_two_hops_spares_the_opposite_corner() {
+    let repulsion = |exclusion| SimParams {
+        edge_regularization_enabled: false,
+        jitter_enabled: false,
+        repulsion_radius: 0.2,
+        repulsion_neighbor_exclusion: exclusion,
+        ..SimParams::default()
+    };
+    // Every corner of the small square is within the radius of every other.
+    let corners = square(0.05);
+    let one_hop = delta(&corners, &repulsion(1));
+    assert!(one_hop[0].x < 0.0 && one_hop[0].y < 0.0, "pushed away from corner 2");
+    assert!((one_hop[0].x - one_hop[0].y).abs() < 1e-9);
+    assert!(delta(&corners, &repulsion(2)).iter().all(|d| *d == Vec2::ZERO));
+}
+
+#[test]
+fn springs_ignore_errors_inside_the_dead_zone() {
+    let params = SimParams {
+        edge_dead_zone: 0.1,
+        ..springs()
+    };
+    let zero = Vec2::ZERO;
+    assert!(delta(&square(1.09), &params).iter().all(|d| *d == zero));
+    assert!(delta(&square(0.91), &params).iter().all(|d| *d == zero));
+    // Just outside, the whole error is corrected as without a dead zone.
+    assert_eq!(delta(&square(1.11), &params), delta(&square(1.11), &springs()));
+    assert_ne!(delta(&square(1.11), &params)[0], zero);
+}
+
+// Moves every vertex by the same offset.
+#[derive(Debug, Clone, Copy)]
+struct ConstantPush(Vec2);
+
+impl Force for ConstantPush {
+    fn accumulate(&self, _: &[Vec2], _: &PolygonMeta, _: &mut ForceContext, delta: &mut [Vec2]) {
+        for d in delta {
+            *d += self.0;
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Constant Push"
+    }
+
+    fn box_clone(&self) -> Box<dyn Force> {
+        Box::new(*self)
+    }
+}
+
+// Every built-in pass off.
+fn no_forces() -> SimParams {
+    SimParams {
+        edge_regularization_enabled: false,
+        repulsion_enabled: false,
+        jitter_enabled: false,
+        ..SimParams::default()
+    }
+}
+
+#[test]
+fn custom_force_is_applied_by_step() {
+    let push = Vec2::new(0.01, -0.02);
+    let mut sim = Simulation::with_ngon(1, 1.0, 8);
+    let start = sim.polygon().clone();
+    sim.add_force(Box::new(ConstantPush(push)));
+    assert_eq!(sim.forces()[0].name(), "Constant Push");
+    let mut copy = sim.clone();
+    sim.step(no_forces());
+    copy.step(no_forces());
+    assert_eq!(sim.polygon(), copy.polygon());
+    for (a, b) in start.vertices().iter().zip(sim.polygon().vertices()) {
+        assert!((*b - *a - push).length() < 1e-6);
+    }
+
+    sim.clear_forces();
+    let before = sim.polygon().clone();
+    sim.step(no_forces());
+    assert_eq!(sim.polygon(), &before);
+}
+
+
+#[test]
+fn force_stack_runs_the_order_then_custom_passes() {
+    let params = SimParams::default();
+    let custom: Vec<Box<dyn Force>> = vec![Box::new(ConstantPush(Vec2::X))];
+    let kinds: Vec<_> = force_stack(&params, &custom).map(|force| force.kind()).collect();
+    let mut expected: Vec<_> = params.force_order.iter().copied().map(Some).collect();
+    expected.push(None);
+    assert_eq!(kinds, expected);
+}
+
+#[test]
+fn built_in_pass_registered_as_custom_draws_from_the_rng() {
+    let params = SimParams {
+        jitter_enabled: true,
+        jitter_strength: 0.01,
+        ..no_forces()
+    };
+    let mut plain = Simulation::with_ngon(1, 1.0, 8);
+    let mut doubled = plain.clone();
+    doubled.add_force(Box::new(ForceKind::Jitter));
+    let mut again = doubled.clone();
+    plain.step(params);
+    doubled.step(params);
+    again.step(params);
+    assert_ne!(plain.polygon(), doubled.polygon());
+    assert_eq!(doubled.polygon(), again.polygon());
+}
@@ -0,0 +1,318 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::{average_edge_length, mean_edge_length};
+
+fn rectangle() -> Polygon {
+    Polygon::from_svg_path("M 0 0 L 2 0 L 2 1 L 0 1 Z").unwrap()
+}
+
+fn assert_close(actual: Real, expected: Real) {
+    assert!((actual - expected).abs() < 1e-5, "{actual} != {expected}");
+}
+
+#[test]
+fn open_chain_metrics_skip_the_closing_edge() {
+    let closed = rectangle();
+    let mut open = rectangle();
+    open.set_closed(false);
+
+    assert!(closed.is_closed());
+    assert_eq!(closed.edge_count(closed.is_closed()), 4);
+    assert_eq!(open.edge_count(open.is_closed()), 3);
+    assert_eq!(open.edges().count(), 3);
+    assert_eq!(open.closed_edges().count(), 4);
+    assert_close(closed.perimeter(), 6.0);
+    assert_close(open.perimeter(), 5.0);
+    assert_close(average_edge_length(&closed), 1.5);
+    assert_close(average_edge_length(&open), 5.0 / 3.0);
+    assert_close(mean_edge_length(&closed, false), 5.0 / 3.0);
+}
+
+#[test]
+fn subpath_is_an_open_chain() {
+    let chain = rectangle().subpath(1, 3);
+    assert!(!chain.is_closed());
+    assert_eq!(chain.len(), 3);
+    assert_close(chain.perimeter(), 3.0);
+    assert_close(average_edge_length(&chain), 1.5);
+}
+
+#[test]
+fn subpath_keeps_the_endpoints_and_wraps() {
+    let square = rectangle();
+    let v = square.vertices();
+    let arc = square.subpath(1, 3);
+    assert_eq!(arc.vertices(), &v[1..=3]);
+    assert!(!arc.is_closed() && square.is_closed());
+
+    let wrapped = square.subpath(3, 1);
+    assert!(!wrapped.is_closed());
+    assert_eq!(wrapped.vertices(), [v[3], v[0], v[1]]);
+    assert!(square.subpath(0, 4).is_empty());
+}
+
+#[test]
+fn approx_eq_compares_vertex_by_vertex() {
+    let square = Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap();
+    assert_eq!(square, square.clone());
+    assert!(square.approx_eq(&square, 0.0));
+
+    let mut nudged = square.clone();
+    nudged.vertices_mut()[2] += Vec2::new(1e-4, 0.0);
+    assert_ne!(square, nudged);
+    assert!(square.approx_eq(&nudged, 1e-3));
+    assert!(!square.approx_eq(&nudged, 1e-5));
+
+    let triangle = Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 Z").unwrap();
+    assert!(!square.approx_eq(&triangle, 10.0));
+}
+
+#[test]
+fn hausdorff_distance_measures_a_shift() {
+    let circle = Polygon::regular_ngon(1.0, 128);
+    assert_eq!(circle.hausdorff_distance(&circle), 0.0);
+    for d in [0.01, 0.1, 0.5] {
+        let mut shifted = circle.clone();
+        shifted.map_vertices(|v| v + Vec2::new(d, 0.0));
+        let distance = circle.hausdorff_distance(&shifted);
+        assert!((distance - d).abs() < 1e-3, "shift {d} measured as {distance}");
+        assert_eq!(shifted.hausdorff_distance(&circle), distance);
+    }
+    assert_eq!(circle.hausdorff_distance(&Polygon::new()), Real::INFINITY);
+}
+
+#[test]
+fn degenerate_polygons_have_no_perimeter_or_area() {
+    let point = rectangle().subpath(1, 1);
+    assert_eq!(point.len(), 1);
+    assert_eq!(point.try_perimeter(), None);
+    assert_eq!(point.try_area(), None);
+    assert_eq!(Polygon::new().try_perimeter(), None);
+
+    let segment = rectangle().subpath(0, 1);
+    assert_eq!(segment.try_perimeter(), Some(2.0));
+    assert_eq!(segment.try_area(), None);
+
+    let square = rectangle();
+    assert_close(square.try_perimeter().unwrap(), 6.0);
+    assert_close(square.try_area().unwrap(), 2.0);
+}
+
+#[test]
+fn map_vertices_applies_a_shear() {
+    let mut sheared = rectangle();
+    sheared.map_vertices(|v| Vec2::new(v.x + 0.5 * v.y, v.y));
+    let expected = [(0.0, 0.0), (2.0, 0.0), (2.5, 1.0), (0.5, 1.0)];
+    for (v, (x, y)) in sheared.vertices().iter().zip(expected) {
+        assert_eq!(*v, Vec2::new(x, y));
+    }
+    // Shear preserves area.
+    assert_close(sheared.area(), rectangle().area());
+}
+
+#[test]
+fn decimating_a_circle_leaves_an_octagon() {
+    let circle = Polygon::regular_ngon(1.0, 100);
+    let octagon = circle.decimate_to(8);
+    assert_eq!(octagon.len(), 8);
+    for v in octagon.vertices() {
+        assert_close(v.length(), 1.0);
+    }
+    // On a circle every triangle costs the same at first, so the survivors are not evenly
+    // spaced. They still go round once, with no gap near twice the regular 45 degrees.
+    let angle = |v: Vec2| v.y.atan2(v.x).to_degrees();
+    let gaps: Vec<Real> = octagon
+        .edges()
+        .map(|(a, b)| (angle(b) - angle(a)).rem_euclid(360.0))
+        .collect();
+    assert!(gaps.iter().all(|gap| *gap > 15.0 && *gap < 75.0), "gaps {gaps:?}");
+    assert!((gaps.iter().sum::<Real>() - 360.0).abs() < 1e-3);
+    assert!((octagon.perimeter() - circle.perimeter()).abs() < 0.05 * circle.perimeter());
+}
+
+#[test]
+fn outward_sign_flips_with_the_winding() {
+    let ccw = Polygon::regular_ngon(1.0, 6);
+    let mut cw = ccw.clone();
+    cw.vertices_mut().reverse();
+    assert_eq!(ccw.outward_sign(), -1.0);
+    assert_eq!(cw.outward_sign(), 1.0);
+    for polygon in [ccw, cw] {
+        let sign = polygon.outward_sign();
+        for (a, b) in polygon.edges() {
+            let d = b - a;
+            let normal = Vec2::new(-d.y, d.x) * sign;
+            // The polygon is centered on the origin, so outward means away from it.
+            assert!(normal.dot(a.midpoint(b)) > 0.0);
+        }
+    }
+}
+
+// Koch snowflake after `order` refinements of a triangle, dimension ln 4 / ln 3.
+fn koch_snowflake(order: usize) -> Polygon {
+    let mut points = Polygon::regular_ngon(0.5, 3).vertices().to_vec();
+    for _ in 0..order {
+        let n = points.len();
+        let mut refined = Vec::with_capacity(4 * n);
+        for i in 0..n {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            let d = b - a;
+            // Points wind CCW, so the outward side is the right of each edge.
+            let peak = a + d * 0.5 - d.perp() * (3.0 as Real).sqrt() / 6.0;
+            refined.extend([a, a + d / 3.0, peak, a + d * (2.0 / 3.0)]);
+        }
+        points = refined;
+    }
+    let mut d = format!("M {} {}", points[0].x, points[0].y);
+    for p in &points[1..] {
+        d += &format!(" L {} {}", p.x, p.y);
+    }
+    Polygon::from_svg_path(&(d + " Z")).unwrap()
+}
+
+#[test]
+fn box_counting_separates_smooth_from_convoluted() {
+    let scales = [0.01, 0.02, 0.04, 0.08];
+    let circle = Polygon::regular_ngon(0.5, 512).box_counting_dimension(&scales);
+    assert!((circle - 1.0).abs() < 0.1, "circle dimension {circle}");
+
+    let snowflake = koch_snowflake(5);
+    let dimension = snowflake.box_counting_dimension(&scales);
+    assert!(dimension > circle + 0.15, "snowflake dimension {dimension}");
+
+    assert!(snowflake.box_counting_dimension(&[0.02]).is_nan());
+}
+
+#[test]
+fn point_at_fraction_walks_the_perimeter() {
+    // Vertices (1, 0), (0, 1), (-1, 0), (0, -1).
+    let square = Polygon::regular_ngon(1.0, 4);
+    let at = |f| square.point_at_fraction(f).unwrap();
+    let near = |a: Vec2, b: Vec2| a.distance(b) < 1e-5;
+    assert!(near(at(0.0), square.vertices()[0]));
+    assert!(near(at(0.5), Vec2::new(-1.0, 0.0)));
+    assert!(near(at(0.125), Vec2::new(0.5, 0.5)));
+    assert!(near(at(1.25), Vec2::new(0.0, 1.0)));
+    assert!(near(at(-0.25), Vec2::new(0.0, -1.0)));
+
+    // An open chain walks only its own edges: halfway along three is the middle one's middle.
+    let chain = square.subpath(0, 3);
+    assert!(near(chain.point_at_fraction(0.5).unwrap(), Vec2::new(-0.5, 0.5)));
+
+    assert_eq!(Polygon::new().point_at_fraction(0.5), None);
+    assert_eq!(rectangle().subpath(0, 0).point_at_fraction(0.5), None);
+    assert_eq!(square.point_at_fraction(Real::NAN), None);
+}
+
+#[test]
+fn unit_square_rasterizes_to_the_middle_cells() {
+    let square = Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap();
+    // Cells of half a unit, centered at -0.25, 0.25, 0.75 and 1.25 on each axis.
+    let grid = square.rasterize((Vec2::splat(-0.5), Vec2::splat(1.5)), (4, 4));
+    let rows: Vec<String> = grid
+        .chunks(4)
+        .map(|row| row.iter().map(|&inside| if inside { '#' } else { '.' }).collect())
+        .collect();
+    assert_eq!(rows, ["....", ".##.", ".##.", "...."]);
+
+    // Columns and rows are independent, and the grid is row-major.
+    let grid = square.rasterize((Vec2::ZERO, Vec2::new(2.0, 1.0)), (4, 2));
+    assert_eq!(grid, [true, true, false, false, true, true, false, false]);
+}
+
+#[test]
+fn distance_matrix_of_a_unit_square() {
+    let square = Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap();
+    let matrix = square.distance_matrix();
+    let diagonal = (2.0 as Real).sqrt();
+    assert_eq!(matrix.len(), 4);
+    for (i, row) in matrix.iter().enumerate() {
+        assert_eq!(row.len(), 4);
+        for (j, &d) in row.iter().enumerate() {
+            let expected = match (i + 4 - j) % 4 {
+                0 => 0.0,
+                2 => diagonal,
+                _ => 1.0,
+            };
+            assert_close(d, expected);
+            assert_eq!(d, matrix[j][i]);
+        }
+    }
+    assert!(Polygon::new().distance_matrix().is_empty());
+}
+
+fn triangle_area([a, b, c]: [Vec2; 3]) -> Real {
+    0.5 * (b - a).perp_dot(c - a)
+}
+
+#[test]
+fn triangulation_covers_the_area_exactly() {
+    for n in [3, 5, 12, 40] {
+        let ngon = Polygon::regular_ngon(1.0, n);
+        let triangles = ngon.triangulate();
+        assert_eq!(triangles.len(), n - 2);
+        assert!(triangles.iter().all(|&t| triangle_area(t) > 0.0), "all counter-clockwise");
+        assert_close(triangles.iter().map(|&t| triangle_area(t)).sum(), ngon.area());
+    }
+
+    // A concave L shape, and the same shape clockwise.
+    let l_shape = Polygon::from_svg_path("M 0 0 L 2 0 L 2 1 L 1 1 L 1 2 L 0 2 Z").unwrap();
+    let reversed = Polygon::from_svg_path("M 0 0 L 0 2 L 1 2 L 1 1 L 2 1 L 2 0 Z").unwrap();
+    for shape in [l_shape, reversed] {
+        let triangles = shape.triangulate();
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles.iter().all(|&t| triangle_area(t) > 0.0));
+        assert_close(triangles.iter().map(|&t| triangle_area(t)).sum(), 3.0);
+    }
+
+    let bowtie = Polygon::from_svg_path("M 0 0 L 1 1 L 1 0 L 0 1 Z").unwrap();
+    assert!(bowtie.triangulate().is_empty());
+}
+
+#[test]
+fn neighbor_indices_wrap_at_both_ends() {
+    let pentagon = Polygon::regular_ngon(1.0, 5);
+    assert_eq!(pentagon.next_index(4), 0);
+    assert_eq!(pentagon.prev_index(0), 4);
+    assert_eq!(pentagon.next_index(0), 1);
+    assert_eq!(pentagon.prev_index(4), 3);
+    for i in 0..5 {
+        assert_eq!(pentagon.prev_index(pentagon.next_index(i)), i);
+    }
+}
+
+#[test]
+#[should_panic(expected = "empty polygon")]
+fn neighbor_index_of_an_empty_polygon_panics() {
+    Polygon::new().next_index(0);
+}
+
+#[test]
+fn compactness_separates_circles_from_fingers() {
+    let circle = Polygon::regular_ngon(1.0, 256).compactness().unwrap();
+    assert!(circle > 0.999 && circle < 1.0, "{circle}");
+    // Regular polygons approach 1 from below as the side count rises.
+    let square = Polygon::regular_ngon(1.0, 4).compactness().unwrap();
+    assert_close(square, std::f64::consts::FRAC_PI_4 as Real);
+
+    let fingers = Polygon::star(16, 0.2, 1.0).compactness().unwrap();
+    assert!(fingers < 0.3, "{fingers}");
+    assert!(koch_snowflake(5).compactness().unwrap() < 0.1);
+
+    assert_eq!(Polygon::new().compactness(), None);
+    assert_eq!(rectangle().subpath(0, 1).compactness(), None);
+}
+
+#[test]
+fn edges_pair_consecutive_vertices() {
+    let square = Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap();
+    let v = square.vertices();
+    let closed: Vec<_> = square.edges().collect();
+    assert_eq!(closed, [(v[0], v[1]), (v[1], v[2]), (v[2], v[3]), (v[3], v[0])]);
+    assert_eq!(square.closed_edges().collect::<Vec<_>>(), closed);
+
+    // An open chain of the same vertices drops the closing edge unless asked for it.
+    let chain = square.subpath(0, 3);
+    assert_eq!(chain.edges().collect::<Vec<_>>(), closed[..3]);
+    assert_eq!(chain.closed_edges().collect::<Vec<_>>(), closed);
+    assert_eq!(Polygon::new().edges().count(), 0);
+}
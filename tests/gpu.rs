@@ -0,0 +1,38 @@
+// Runs only with `--features gpu`, and passes trivially where no adapter is available.
+#![cfg(feature = "gpu")]
+
+use dg4::geometry::{Real, Vec2};
+use dg4::gpu::{shared, GPU_REPULSION_TOLERANCE};
+use dg4::sim::{compute_delta, SimParams};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn gpu_repulsion_matches_cpu() {
+    let Some(gpu) = shared() else {
+        eprintln!("no GPU adapter; skipping");
+        return;
+    };
+    // Only repulsion acts, so `compute_delta` is the CPU pass alone.
+    let params = SimParams {
+        edge_regularization_enabled: false,
+        jitter_enabled: false,
+        repulsion_radius: 0.05,
+        repulsion_strength: 0.01,
+        ..SimParams::default()
+    };
+    let mut rng = StdRng::seed_from_u64(1);
+    for n in [100, 2_000, 8_000] {
+        let cloud: Vec<Vec2> = (0..n)
+            .map(|_| Vec2::new(rng.gen_range(-2.0..2.0), rng.gen_range(-2.0..2.0)))
+            .collect();
+        let cpu = compute_delta(&cloud, &params, &mut rng);
+        let mut on_gpu = vec![Vec2::ZERO; n];
+        assert!(gpu.accumulate(&cloud, &params, &mut on_gpu), "GPU declined {n} vertices");
+        let worst = cpu.iter().zip(&on_gpu).map(|(a, b)| a.distance(*b)).fold(0.0, Real::max);
+        assert!(
+            worst <= GPU_REPULSION_TOLERANCE * params.repulsion_strength,
+            "{n} vertices differ by up to {worst}"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::{
+    Attractor, GrowthCurvatureFilter, SimParams, SimParamsBuilder, Simulation,
+    MAX_GROWTH_INHIBITORS,
+};
+
+// Growth alone, with every other force and the RNG out of the way.
+fn growth_only() -> SimParamsBuilder {
+    SimParamsBuilder::new()
+        .edge_regularization_enabled(false)
+        .repulsion_enabled(false)
+        .jitter_enabled(false)
+        .growth_enabled(true)
+        .growth_rate(0.01)
+}
+
+fn assert_close(actual: Real, expected: Real, tolerance: Real) {
+    assert!((actual - expected).abs() <= tolerance, "{actual} != {expected}");
+}
+
+#[test]
+fn conserving_growth_keeps_the_perimeter() {
+    let mut sim = Simulation::benchmark(64);
+    let before = sim.polygon().clone();
+    let params = growth_only().growth_conserve_perimeter(true).build();
+    sim.step(params);
+    let after = sim.polygon();
+    assert_close(after.perimeter(), before.perimeter(), 1e-5 * before.perimeter());
+    let moved = before.vertices().iter().zip(after.vertices()).map(|(a, b)| a.distance(*b));
+    assert!(moved.fold(0.0, Real::max) > 1e-4, "the shape did not redistribute");
+
+    let mut plain = Simulation::benchmark(64);
+    plain.step(SimParams {
+        growth_conserve_perimeter: false,
+        ..params
+    });
+    assert!(plain.polygon().perimeter() > before.perimeter() * 1.01);
+}
+
+// 64 vertices, two bulges along x and a concave waist along y; vertex 0 is on a bulge
+// and vertex 16 at the waist.
+fn peanut() -> Polygon {
+    let mut polygon = Polygon::regular_ngon(1.0, 64);
+    polygon.map_vertices(|v| v * (1.0 + 0.6 * (2.0 * v.y.atan2(v.x)).cos()));
+    polygon
+}
+
+#[test]
+fn convex_only_growth_leaves_the_waist() {
+    let mut sim = Simulation::new(1);
+    sim.set_polygon(peanut());
+    let growth = |filter| sim.growth_delta(growth_only().growth_curvature_filter(filter).build());
+
+    let convex = growth(GrowthCurvatureFilter::ConvexOnly);
+    assert!(convex[0].length() > 1e-3 && convex[32].length() > 1e-3, "bulges grow");
+    assert_eq!(convex[16].length(), 0.0);
+    assert_eq!(convex[48].length(), 0.0);
+
+    let concave = growth(GrowthCurvatureFilter::ConcaveOnly);
+    assert_eq!(concave[0].length(), 0.0);
+    assert!(concave[16].length() > 1e-3, "the waist grows");
+
+    let mut sim = Simulation::new(1);
+    sim.set_polygon(peanut());
+    for _ in 0..5 {
+        sim.step(growth_only().growth_curvature_filter(GrowthCurvatureFilter::ConvexOnly).build());
+    }
+    let (start, end) = (peanut(), sim.polygon().clone());
+    assert_eq!(end.vertices()[16], start.vertices()[16]);
+    assert!(end.vertices()[0].x > start.vertices()[0].x + 0.04);
+}
+
+// Unit circle sampled every 0.01 rad over [0, 0.3] and every 0.4 rad elsewhere, as a
+// path for `Polygon::from_svg_path`.
+fn circle_with_dense_stretch() -> Polygon {
+    let dense = (0..=30).map(|k| k as Real * 0.01);
+    let sparse = (1..15).map(|k| 0.3 + k as Real * 0.4);
+    let points: Vec<String> = dense
+        .chain(sparse)
+        .map(|t| format!("{} {}", t.cos(), t.sin()))
+        .collect();
+    Polygon::from_svg_path(&format!("M {} Z", points.join(" L "))).unwrap()
+}
+
+#[test]
+fn crowded_vertices_grow_less() {
+    let mut sim = Simulation::new(1);
+    sim.set_polygon(circle_with_dense_stretch());
+    // Vertex 15 sits mid-stretch and vertex 38 on the sparse side.
+    let (crowded, isolated) = (15, 38);
+    let params = growth_only().repulsion_radius(0.1);
+    let plain = sim.growth_delta(params.build());
+    assert!((plain[crowded].length() - plain[isolated].length()).abs() < 1e-9);
+
+    let damped = sim.growth_delta(params.growth_density_gain(1.0).build());
+    assert!(damped[crowded].length() < 0.1 * damped[isolated].length());
+    assert!((damped[isolated].length() - plain[isolated].length()).abs() < 1e-9);
+}
+
+// Mean squared change between consecutive unit growth directions around the loop.
+fn roughness(growth: &[Vec2]) -> Real {
+    let n = growth.len();
+    let dirs: Vec<Vec2> = growth.iter().map(|g| g.normalize_or_zero()).collect();
+    (0..n).map(|i| dirs[i].distance_squared(dirs[(i + 1) % n])).sum::<Real>() / n as Real
+}
+
+// 200-gon with noise of half an edge on every vertex.
+fn noisy() -> Simulation {
+    let mut sim = Simulation::benchmark(200);
+    sim.perturb(0.04);
+    sim
+}
+
+#[test]
+fn smoothing_calms_noisy_growth_directions() {
+    let sim = noisy();
+    let raw = roughness(&sim.growth_delta(growth_only().build()));
+    let smoothed = roughness(&sim.growth_delta(growth_only().growth_normal_smoothing(0.8).build()));
+    assert!(smoothed < 0.5 * raw, "smoothed {smoothed} vs raw {raw}");
+}
+
+#[test]
+fn wider_tangent_window_calms_noisy_growth_directions() {
+    let sim = noisy();
+    let window = |k| roughness(&sim.growth_delta(growth_only().growth_tangent_window(k).build()));
+    let (narrow, wide) = (window(1), window(4));
+    assert!(wide < 0.5 * narrow, "window 4: {wide} vs window 1: {narrow}");
+}
+
+#[test]
+fn area_goal_is_approached_without_overshoot() {
+    let mut sim = Simulation::with_ngon(1, 0.5, 32);
+    let goal = 2.0;
+    let params = growth_only().growth_area_goal(Some(goal)).build();
+    let mut area = sim.polygon().area();
+    assert!(area < 0.5 * goal);
+    for _ in 0..400 {
+        sim.step(params);
+        let next = sim.polygon().area();
+        assert!(next >= area, "area shrank from {area} to {next}");
+        assert!(next <= goal * 1.01, "area overshot to {next}");
+        area = next;
+    }
+    assert!(area > 0.95 * goal, "area only reached {area}");
+}
+
+#[test]
+fn inhibitors_only_slow_growth_near_them() {
+    let start = Polygon::regular_ngon(1.0, 16);
+    let mut growth_inhibitors = [None; MAX_GROWTH_INHIBITORS];
+    growth_inhibitors[0] = Some(Attractor {
+        position: start.vertices()[0],
+        radius: 0.3,
+        strength: 0.5,
+    });
+    let params = SimParams {
+        growth_inhibitors,
+        ..growth_only().split_enabled(false).build()
+    };
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    sim.step(params);
+    let moved = |i: usize| sim.polygon().vertices()[i].distance(start.vertices()[i]);
+    assert!(moved(8) > 0.0);
+    // Right on the inhibitor growth is damped by its strength; far away it is untouched.
+    assert_close(moved(0), 0.5 * moved(8), 1e-3 * moved(8));
+    assert_close(moved(8), params.growth_rate, 1e-3 * moved(8));
+}
+
+#[test]
+fn guarded_erosion_stops_before_the_polygon_inverts() {
+    let params = SimParams {
+        growth_rate: -0.02,
+        growth_erosion_min_edge: Some(0.005),
+        ..growth_only().split_enabled(false).build()
+    };
+    let mut sim = Simulation::with_ngon(1, 0.1, 16);
+    let start = sim.polygon().signed_area();
+    let mut areas = Vec::new();
+    for _ in 0..200 {
+        sim.step(params);
+        areas.push(sim.polygon().signed_area());
+    }
+    // Each step asks every vertex to move a fifth of the radius inward; the polygon
+    // shrinks, then holds.
+    assert!(areas[0] < start);
+    assert!(areas.iter().all(|&a| a > 0.0), "area went to {areas:?}");
+    assert_eq!(areas[198], areas[199]);
+}
+
+#[test]
+fn erosion_guard_holds_back_only_the_vertices_at_the_limit() {
+    let params = SimParams {
+        growth_rate: -0.02,
+        growth_erosion_min_edge: Some(0.2),
+        ..growth_only().split_enabled(false).build()
+    };
+    // A deep dent puts vertex 0 within the guard distance of the centroid; the rest of
+    // the rim is far from it.
+    let mut dented = Polygon::regular_ngon(1.0, 16);
+    dented.vertices_mut()[0] = Vec2::new(0.02, 0.0);
+    let mut sim = Simulation::with_polygons(1, vec![dented.clone()]);
+    sim.step(params);
+    let moved = |i: usize| sim.polygon().vertices()[i].distance(dented.vertices()[i]);
+    let rate = params.growth_rate.abs();
+    assert_eq!(moved(0), 0.0);
+    // Its neighbors go halfway so they do not overtake it; the far side erodes in full.
+    assert_close(moved(1), 0.5 * rate, 1e-6);
+    assert_close(moved(15), 0.5 * rate, 1e-6);
+    assert_close(moved(8), rate, 1e-6);
+}
+
+#[test]
+fn split_vertices_rest_for_the_refractory_period() {
+    let params = growth_only()
+        .split_enabled(true)
+        .split_length(0.3)
+        .split_refractory_generations(3)
+        .build();
+    // Edges of about 0.39 all split once, then are too short to split again.
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    assert_eq!(sim.step(params).vertices_added, 16);
+    assert!(sim.refractory().iter().all(|&r| r == 3));
+
+    let resting = |sim: &Simulation| sim.growth_delta(params).iter().all(|g| *g == Vec2::ZERO);
+    for remaining in [2, 1] {
+        assert!(resting(&sim));
+        let before = sim.polygon().clone();
+        sim.step(params);
+        assert_eq!(sim.polygon(), &before, "resting vertices moved");
+        assert!(sim.refractory().iter().all(|&r| r == remaining));
+    }
+    sim.step(params);
+    assert!(sim.refractory().iter().all(|&r| r == 0));
+    assert!(!resting(&sim));
+}
+
+#[test]
+fn area_cap_stops_growth_and_splitting() {
+    let params = SimParams {
+        max_area: Some(1.0),
+        ..growth_only().split_enabled(true).split_length(0.1).build()
+    };
+    let mut sim = Simulation::with_ngon(1, 0.5, 32);
+    let mut added = 0;
+    let capped_after = (1..=500).find(|_| {
+        let report = sim.step(params);
+        added += report.vertices_added;
+        report.area_capped
+    });
+    assert!(capped_after.is_some(), "never reached the cap");
+    assert!(added > 0, "it split on the way");
+    assert!(sim.polygon().area() > 1.0);
+
+    let capped = sim.polygon().clone();
+    for _ in 0..20 {
+        let report = sim.step(params);
+        assert!(report.area_capped);
+        assert_eq!(report.vertices_added, 0);
+    }
+    assert_eq!(sim.polygon(), &capped);
+}
+
+#[test]
+fn growth_outward_override_flips_the_direction() {
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    let params = growth_only().build();
+    let radial = |sim: &Simulation| sim.growth_delta(params)[0].dot(sim.polygon().vertices()[0]);
+    assert!(radial(&sim) > 0.0, "outward by default");
+    sim.set_growth_outward(false);
+    assert!(radial(&sim) < 0.0);
+    sim.set_growth_outward(true);
+    assert!(radial(&sim) > 0.0);
+
+    // The override is relative to the detected winding, so clockwise shapes agree.
+    sim.set_polygon(Polygon::from_svg_path("M 0 1 L 1 0 L 0 -1 L -1 0 Z").unwrap());
+    assert!(sim.polygon().signed_area() < 0.0);
+    assert!(radial(&sim) > 0.0);
+    sim.set_growth_outward(false);
+    assert!(radial(&sim) < 0.0);
+}
@@ -0,0 +1,27 @@
+use std::path::Path;
+use std::process::Command;
+
+// Builds and lists the library's dependencies with the GUI feature off, in a target
+// directory of its own so the outer test build's lock is not contended.
+fn cargo(args: &[&str]) -> String {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO"))
+        .args(args)
+        .args(["--no-default-features", "--offline"])
+        .current_dir(manifest)
+        .env("CARGO_TARGET_DIR", manifest.join("target/headless-check"))
+        .output()
+        .expect("failed to run cargo");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "cargo {args:?} failed:\n{stderr}");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn library_builds_without_the_gui() {
+    let tree = cargo(&["tree", "--edges", "normal", "--prefix", "none"]);
+    for gui_crate in ["eframe ", "egui "] {
+        assert!(!tree.lines().any(|l| l.starts_with(gui_crate)), "{gui_crate}in:\n{tree}");
+    }
+    cargo(&["check", "--lib"]);
+}
@@ -0,0 +1,51 @@
+use dg4::history::{Checkpoints, History};
+use dg4::sim::{SimParams, Simulation};
+
+// Steps `steps` times, taking a checkpoint whenever the history takes a snapshot, as the
+// GUI does.
+fn run(sim: &mut Simulation, history: &mut History, checkpoints: &mut Checkpoints, steps: u64) {
+    for _ in 0..steps {
+        sim.step(SimParams::default());
+        if history.record(sim.generation(), sim.polygons()) {
+            checkpoints.push(sim);
+        }
+    }
+}
+
+#[test]
+fn forward_back_forward_reproduces_the_run() {
+    let mut sim = Simulation::benchmark(200);
+    let mut history = History::new(16, 5);
+    let mut checkpoints = Checkpoints::new(16);
+    history.record(sim.generation(), sim.polygons());
+    checkpoints.push(&sim);
+    run(&mut sim, &mut history, &mut checkpoints, 20);
+    let forward = (sim.polygon().clone(), sim.state_hash());
+
+    let mut back = checkpoints.step_back(sim.generation()).unwrap();
+    assert_eq!(back.generation(), 15);
+    history.truncate_after(back.generation());
+    assert_eq!(history.latest().unwrap().generation, 15);
+    assert_eq!(checkpoints.len(), 4);
+    run(&mut back, &mut history, &mut checkpoints, 5);
+    assert_eq!((back.polygon().clone(), back.state_hash()), forward);
+    assert_eq!(history.latest().unwrap().generation, 20);
+
+    // Two steps back, then forward again over both.
+    let _ = checkpoints.step_back(back.generation()).unwrap();
+    let mut back = checkpoints.step_back(15).unwrap();
+    assert_eq!(back.generation(), 10);
+    history.truncate_after(back.generation());
+    run(&mut back, &mut history, &mut checkpoints, 10);
+    assert_eq!((back.polygon().clone(), back.state_hash()), forward);
+}
+
+#[test]
+fn step_back_needs_an_earlier_checkpoint() {
+    let sim = Simulation::benchmark(50);
+    let mut checkpoints = Checkpoints::new(2);
+    assert!(checkpoints.step_back(10).is_none());
+    checkpoints.push(&sim);
+    assert!(checkpoints.step_back(sim.generation()).is_none());
+    assert_eq!(checkpoints.len(), 1);
+}
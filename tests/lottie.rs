@@ -0,0 +1,49 @@
+use dg4::geometry::{Polygon, Vec2};
+use dg4::history::History;
+use dg4::lottie::{export_lottie, LottieStyle};
+use dg4::sim::{SimParamsBuilder, Simulation};
+use serde_json::Value;
+
+#[test]
+fn export_is_json_with_a_keyframe_per_snapshot() {
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    let mut history = History::new(32, 2);
+    history.record(sim.generation(), sim.polygons());
+    let params = SimParamsBuilder::new().growth_enabled(true).split_enabled(true).build();
+    for _ in 0..8 {
+        sim.step(params);
+        history.record(sim.generation(), sim.polygons());
+    }
+    assert_eq!(history.len(), 5);
+
+    let style = LottieStyle::default();
+    let json: Value = serde_json::from_str(&export_lottie(&history, &style)).unwrap();
+    let path = &json["layers"][0]["shapes"][0]["it"][0]["ks"];
+    let keyframes = path["k"].as_array().unwrap();
+    assert_eq!(keyframes.len(), 5);
+    // Every keyframe carries the same resampled vertex count.
+    let count = history.iter().map(|s| s.polygons[0].len()).max().unwrap();
+    for (k, keyframe) in keyframes.iter().enumerate() {
+        assert_eq!(keyframe["t"], k as u64 * style.frames_per_keyframe as u64);
+        assert_eq!(keyframe["s"][0]["v"].as_array().unwrap().len(), count);
+    }
+    assert_eq!(json["op"], 4 * style.frames_per_keyframe as u64 + 1);
+}
+
+#[test]
+fn every_polygon_gets_its_own_path() {
+    let left = Polygon::regular_ngon_at(Vec2::new(-2.0, 0.0), 1.0, 6);
+    let right = Polygon::regular_ngon_at(Vec2::new(2.0, 0.0), 1.0, 8);
+    let mut history = History::new(4, 1);
+    history.record(0, &[left, right]);
+
+    let lottie = export_lottie(&history, &LottieStyle::default());
+    let json: Value = serde_json::from_str(&lottie).unwrap();
+    let items = json["layers"][0]["shapes"][0]["it"].as_array().unwrap();
+    let counts: Vec<usize> = items
+        .iter()
+        .filter(|item| item["ty"] == "sh")
+        .map(|item| item["ks"]["k"][0]["s"][0]["v"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(counts, [6, 8]);
+}
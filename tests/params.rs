@@ -0,0 +1,61 @@
+use dg4::param_info::{lookup, tooltip, PARAMS};
+use dg4::param_log::ParamLog;
+use dg4::sim::{SimParams, SimParamsBuilder};
+
+#[test]
+fn builder_sets_one_field_and_keeps_the_defaults() {
+    let params = SimParamsBuilder::default().edge_stiffness(0.5).build();
+    assert_eq!(
+        params,
+        SimParams {
+            edge_stiffness: 0.5,
+            ..SimParams::default()
+        }
+    );
+    assert_eq!(SimParamsBuilder::new().build(), SimParams::default());
+}
+
+#[test]
+fn every_field_has_a_registry_entry() {
+    // Top-level fields are the lines indented by exactly one level in pretty `Debug`.
+    let debug = format!("{:#?}", SimParams::default());
+    let fields: Vec<&str> = debug
+        .lines()
+        .filter_map(|line| line.strip_prefix("    "))
+        .filter(|line| !line.starts_with(' '))
+        .filter_map(|line| line.split_once(':').map(|(name, _)| name))
+        .collect();
+    assert_eq!(fields.len(), PARAMS.len());
+    for name in fields {
+        let info = lookup(name).unwrap_or_else(|| panic!("{name} has no registry entry"));
+        assert!(!info.label.is_empty() && !info.description.is_empty(), "{name}");
+        assert!(tooltip(name).starts_with(info.description));
+    }
+    assert_eq!(tooltip("no_such_param"), "");
+}
+
+#[test]
+fn changing_two_params_logs_two_entries() {
+    let before = SimParams::default();
+    let after = SimParams {
+        growth_rate: 0.002,
+        split_enabled: true,
+        ..before
+    };
+    let mut log = ParamLog::new();
+    assert_eq!(log.record(12, &before, &after), 2);
+    assert_eq!(log.record(13, &after, &after), 0);
+    let entries = log.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        (entries[0].generation, entries[0].name, &*entries[0].old, &*entries[0].new),
+        (12, "growth_rate", "0.001", "0.002")
+    );
+    assert_eq!(
+        (entries[1].name, &*entries[1].old, &*entries[1].new),
+        ("split_enabled", "false", "true")
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&log.to_json()).unwrap();
+    assert_eq!(json[1]["param"], "split_enabled");
+}
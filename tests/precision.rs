@@ -0,0 +1,57 @@
+// Every integration test also runs in single precision with
+// `cargo test --no-default-features --features f32`. This file pins down the switch
+// itself and checks a few forces against closed-form values with a precision-dependent
+// tolerance.
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::{compute_delta, SimParams};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const TOLERANCE: Real = if cfg!(feature = "f32") { 1e-6 } else { 1e-12 };
+
+fn square() -> Vec<Vec2> {
+    Polygon::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap().vertices().to_vec()
+}
+
+fn assert_near(actual: Vec2, expected: Vec2) {
+    assert!(actual.distance(expected) <= TOLERANCE, "{actual} != {expected}");
+}
+
+#[test]
+fn real_follows_the_feature() {
+    let bytes = if cfg!(feature = "f32") { 4 } else { 8 };
+    assert_eq!(std::mem::size_of::<Real>(), bytes);
+    assert_eq!(std::mem::size_of::<Vec2>(), 2 * bytes);
+}
+
+#[test]
+fn springs_match_the_closed_form() {
+    let params = SimParams {
+        repulsion_enabled: false,
+        jitter_enabled: false,
+        target_edge_length: 0.5,
+        edge_stiffness: 0.2,
+        ..SimParams::default()
+    };
+    // Every edge is 0.5 too long, so each endpoint moves 0.5 * 0.2 / 2 along each edge.
+    let delta = compute_delta(&square(), &params, &mut StdRng::seed_from_u64(1));
+    assert_near(delta[0], Vec2::new(0.05, 0.05));
+    assert_near(delta[2], Vec2::new(-0.05, -0.05));
+}
+
+#[test]
+fn repulsion_matches_the_closed_form() {
+    let params = SimParams {
+        edge_regularization_enabled: false,
+        jitter_enabled: false,
+        repulsion_radius: 2.0,
+        repulsion_strength: 0.1,
+        ..SimParams::default()
+    };
+    // Only the diagonals interact: half of 0.1 * (1 - sqrt(2) / 2) along each diagonal.
+    let delta = compute_delta(&square(), &params, &mut StdRng::seed_from_u64(1));
+    let push = 0.5 * 0.1 * (1.0 - Real::sqrt(2.0) / 2.0);
+    let diagonal = Vec2::new(1.0, 1.0).normalize();
+    assert_near(delta[0], -diagonal * push);
+    assert_near(delta[2], diagonal * push);
+}
@@ -0,0 +1,47 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::render::{render_contact_sheet, render_svg_frames, CONTACT_SHEET_CELL};
+use dg4::sim::SimParams;
+use std::path::Path;
+
+fn view_box(svg: &str) -> &str {
+    let start = svg.find("viewBox=\"").unwrap() + "viewBox=\"".len();
+    &svg[start..start + svg[start..].find('"').unwrap()]
+}
+
+#[test]
+fn svg_frames_share_one_view_box() {
+    // Growing and drifting, so each frame alone would have its own bounds.
+    let history: Vec<[Polygon; 1]> = (1..=3)
+        .map(|k| k as Real)
+        .map(|k| [Polygon::regular_ngon_at(Vec2::new(k, 0.0), 0.5 * k, 16)])
+        .collect();
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("svg_frames");
+    let _ = std::fs::remove_dir_all(&dir);
+    let paths = render_svg_frames(&history, &dir).unwrap();
+    assert_eq!(paths.len(), 3);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+
+    let frames: Vec<String> = paths.iter().map(|p| std::fs::read_to_string(p).unwrap()).collect();
+    assert!(frames.iter().all(|svg| view_box(svg) == view_box(&frames[0])));
+    for (svg, [polygon]) in frames.iter().zip(&history) {
+        assert!(svg.contains(&polygon.to_svg_path()));
+    }
+}
+
+#[test]
+fn contact_sheet_lays_seeds_out_in_a_grid() {
+    let sheet = render_contact_sheet(SimParams::default(), &[1, 2, 3, 4], 5, 2);
+    assert_eq!(sheet.dimensions(), (2 * CONTACT_SHEET_CELL, 2 * CONTACT_SHEET_CELL));
+    let cell = |k: u32| {
+        let (x, y) = ((k % 2) * CONTACT_SHEET_CELL, (k / 2) * CONTACT_SHEET_CELL);
+        image::imageops::crop_imm(&sheet, x, y, CONTACT_SHEET_CELL, CONTACT_SHEET_CELL)
+            .to_image()
+    };
+    // Different seeds draw different labels, if nothing else.
+    assert_ne!(cell(0), cell(1));
+    assert_ne!(cell(2), cell(3));
+
+    // A partly filled last row still takes a full row.
+    let sheet = render_contact_sheet(SimParams::default(), &[1, 2, 3], 0, 2);
+    assert_eq!(sheet.dimensions(), (2 * CONTACT_SHEET_CELL, 2 * CONTACT_SHEET_CELL));
+}
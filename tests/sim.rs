@@ -0,0 +1,367 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::integrator::Midpoint;
+use dg4::sim::{
+    compute_delta, regular_ngon_edge_length, EdgeQuadtree, SimParams, SimParamsBuilder,
+    Simulation, DEFAULT_FORCE_ORDER,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Deterministic defaults: every force as in the GUI but without jitter.
+fn quiet() -> SimParamsBuilder {
+    SimParamsBuilder::new().jitter_enabled(false)
+}
+
+#[test]
+fn settled_polygon_converges_and_growing_one_does_not() {
+    // Edges at their rest length and vertices too far apart to repel.
+    let settled = quiet()
+        .target_edge_length(regular_ngon_edge_length(1.0, 16))
+        .repulsion_radius(0.1)
+        .build();
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    assert!(!sim.is_converged(settled.convergence_threshold), "no step has run yet");
+    assert!(sim.step(settled).converged);
+    assert!(sim.is_converged(settled.convergence_threshold));
+
+    let growing = SimParams {
+        growth_enabled: true,
+        growth_rate: 0.01,
+        ..settled
+    };
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    assert!(!sim.step(growing).converged);
+    assert!(!sim.is_converged(growing.convergence_threshold));
+}
+
+#[test]
+fn force_order_does_not_matter_without_jitter() {
+    let params = quiet()
+        .growth_enabled(true)
+        .growth_rate(0.005)
+        .constraint_enabled(true)
+        .constraint_size(0.6)
+        .centering_strength(0.01)
+        .build();
+    let mut reversed = DEFAULT_FORCE_ORDER;
+    reversed.reverse();
+
+    let mut a = Simulation::benchmark(100);
+    let mut b = a.clone();
+    for _ in 0..10 {
+        a.step(params);
+        b.step(SimParams {
+            force_order: reversed,
+            ..params
+        });
+    }
+    // Only the summation order differs.
+    assert!(a.polygon().approx_eq(b.polygon(), 1e-5));
+}
+
+#[test]
+fn threaded_repulsion_matches_serial_exactly() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let cloud: Vec<Vec2> = (0..1_500)
+        .map(|_| Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+        .collect();
+    let serial = SimParams {
+        edge_regularization_enabled: false,
+        jitter_enabled: false,
+        repulsion_radius: 0.05,
+        ..SimParams::default()
+    };
+    let expected = compute_delta(&cloud, &serial, &mut rng);
+    for threads in [2, 3, 8] {
+        let threaded = SimParams {
+            repulsion_threads: threads,
+            ..serial
+        };
+        for _ in 0..3 {
+            assert_eq!(compute_delta(&cloud, &threaded, &mut rng), expected, "{threads} threads");
+        }
+    }
+}
+
+#[test]
+fn edge_quadtree_finds_the_same_edges_as_a_scan() {
+    let mut sim = Simulation::benchmark(600);
+    // Fold the outline a little so edges from different parts come close.
+    for _ in 0..30 {
+        sim.step(quiet().growth_enabled(true).growth_rate(0.01).build());
+    }
+    let positions = sim.polygon().vertices();
+    let n = positions.len();
+    let tree = EdgeQuadtree::build(positions);
+    let radius = 0.15;
+    for &p in positions.iter().step_by(7) {
+        let scanned: Vec<usize> = (0..n)
+            .filter(|&e| {
+                let (a, b) = (positions[e], positions[(e + 1) % n]);
+                let (min, max) = (a.min(b), a.max(b));
+                min.x <= p.x + radius
+                    && max.x >= p.x - radius
+                    && min.y <= p.y + radius
+                    && max.y >= p.y - radius
+            })
+            .collect();
+        assert_eq!(tree.query_near(p, radius), scanned);
+    }
+}
+
+#[test]
+fn centering_pulls_the_vertex_mean_to_the_area_centroid() {
+    // Unit square sampled densely along its bottom edge, so the vertex mean sits low.
+    let lopsided = Polygon::from_svg_path("M 0 0 L 0.25 0 L 0.5 0 L 0.75 0 L 1 0 L 1 1 L 0 1 Z");
+    let mut sim = Simulation::new(1);
+    sim.set_polygon(lopsided.unwrap());
+    let center = Vec2::new(0.5, 0.5);
+    let params = SimParamsBuilder::new()
+        .edge_regularization_enabled(false)
+        .repulsion_enabled(false)
+        .jitter_enabled(false)
+        .centering_strength(0.1)
+        .build();
+    let mut offset = sim.polygon().centroid().unwrap().distance(center);
+    for _ in 0..10 {
+        sim.step(params);
+        let polygon = sim.polygon();
+        assert!(polygon.area_centroid().unwrap().distance(center) < 1e-5);
+        let next = polygon.centroid().unwrap().distance(center);
+        assert!(next < offset, "vertex mean moved away: {next} after {offset}");
+        offset = next;
+    }
+}
+
+#[test]
+fn capped_repulsion_keeps_only_the_closest_neighbor() {
+    let repulsion = SimParams {
+        edge_regularization_enabled: false,
+        jitter_enabled: false,
+        repulsion_radius: 0.2,
+        ..SimParams::default()
+    };
+    // Vertex 0 at the origin; its loop neighbors 1 and n - 1 are out of range, and vertex
+    // 2 is the closest of several points in range.
+    let far = [Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0)];
+    let closest = Vec2::new(0.03, 0.02);
+    let crowd = [Vec2::new(0.0, 0.1), Vec2::new(0.05, 0.12), Vec2::new(-0.05, 0.12)];
+    let mut cloud = vec![Vec2::ZERO, far[0], closest];
+    cloud.extend(crowd);
+    cloud.push(far[1]);
+    let pair = [Vec2::ZERO, far[0], closest, far[1]];
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let uncapped = compute_delta(&cloud, &repulsion, &mut rng)[0];
+    let capped = SimParams {
+        repulsion_max_neighbors: 1,
+        ..repulsion
+    };
+    let one = compute_delta(&cloud, &capped, &mut rng)[0];
+    let alone = compute_delta(&pair, &repulsion, &mut rng)[0];
+    assert!(one.distance(alone) < 1e-9, "{one} != {alone}");
+    assert!(one.distance(uncapped) > 1e-3);
+}
+
+#[test]
+fn taking_from_the_step_iterator_advances_the_generation() {
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    let params = quiet().growth_enabled(true).build();
+    let polygons: Vec<Polygon> = sim.iter_steps(params).take(5).collect();
+    assert_eq!(polygons.len(), 5);
+    assert_eq!(sim.generation(), 5);
+    assert_eq!(polygons.last(), Some(sim.polygon()));
+}
+
+#[test]
+fn heavier_vertices_move_less() {
+    let params = quiet().growth_enabled(true).growth_rate(0.01).split_enabled(false).build();
+    let mut sim = Simulation::with_ngon(1, 1.0, 16);
+    assert!(sim.set_masses(vec![1.0; 15]).is_err());
+    assert!(sim.set_masses(vec![0.0; 16]).is_err());
+    let mut masses = vec![1.0; 16];
+    masses[0] = 2.0;
+    sim.set_masses(masses).unwrap();
+
+    let start = sim.polygon().clone();
+    sim.step(params);
+    let moved = |i: usize| sim.polygon().vertices()[i].distance(start.vertices()[i]);
+    // Every vertex of the regular polygon feels the same force.
+    assert!(moved(1) > 0.0);
+    assert!((moved(0) - 0.5 * moved(1)).abs() < 1e-3 * moved(1), "{} vs {}", moved(0), moved(1));
+}
+
+#[test]
+fn fewer_than_three_sides_builds_nothing_to_step() {
+    assert_eq!(regular_ngon_edge_length(1.0, 2), 0.0);
+    let mut sim = Simulation::with_ngon(1, 1.0, 2);
+    assert!(sim.polygon().is_empty());
+    for _ in 0..10 {
+        sim.step(SimParams::default());
+    }
+    assert!(sim.polygon().is_empty());
+
+    // A two-vertex polygon is stepped without producing NaNs.
+    sim.set_polygon(Polygon::regular_ngon(1.0, 4).subpath(0, 1));
+    for _ in 0..10 {
+        sim.step(SimParams::default());
+    }
+    assert!(sim.polygon().vertices().iter().all(|v| v.is_finite()));
+}
+
+fn hash_after(params: SimParams, steps: usize) -> u64 {
+    let mut sim = Simulation::benchmark(64);
+    sim.reset_seed(7);
+    for _ in 0..steps {
+        sim.step(params);
+    }
+    sim.state_hash()
+}
+
+#[test]
+fn state_hash_is_stable_and_tracks_the_dynamics() {
+    let params = SimParams::default();
+    let hash = hash_after(params, 20);
+    assert_eq!(hash, hash_after(params, 20));
+    assert_ne!(hash, hash_after(params, 19));
+    let stronger = SimParams {
+        repulsion_strength: params.repulsion_strength * 2.0,
+        ..params
+    };
+    assert_ne!(hash, hash_after(stronger, 20));
+    // Pinned for the default double-precision build; a change here is a change in dynamics.
+    if !cfg!(feature = "f32") {
+        assert_eq!(hash, 0x74ab_1381_601c_7193, "{hash:#x}");
+    }
+}
+
+#[test]
+fn profiling_times_only_the_enabled_passes() {
+    let params = SimParams {
+        growth_enabled: true,
+        split_enabled: false,
+        ..SimParams::default()
+    };
+    let mut sim = Simulation::benchmark(512);
+    sim.step(params);
+    assert!(sim.last_profile().is_none(), "profiling is off by default");
+
+    sim.set_profiling(true);
+    sim.step(params);
+    let profile = *sim.last_profile().unwrap();
+    for kind in DEFAULT_FORCE_ORDER {
+        let time = profile.force(kind);
+        if kind.is_enabled(&params) {
+            assert!(!time.is_zero(), "{kind:?} was not timed");
+        } else {
+            assert!(time.is_zero(), "{kind:?} is disabled but took {time:?}");
+        }
+    }
+    assert!(profile.split.is_zero());
+    assert!(profile.total >= profile.forces.iter().sum());
+
+    sim.set_profiling(false);
+    assert!(sim.last_profile().is_none());
+}
+
+#[test]
+fn euler_is_the_direct_update_and_midpoint_differs() {
+    let params = quiet().growth_enabled(true).growth_rate(0.01).split_enabled(false).build();
+    let start = Simulation::benchmark(64);
+    assert_eq!(start.integrator().name(), "Euler");
+
+    let mut euler = start.clone();
+    let mut positions = start.polygon().vertices().to_vec();
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..10 {
+        euler.step(params);
+        let delta = compute_delta(&positions, &params, &mut rng);
+        for (p, d) in positions.iter_mut().zip(delta) {
+            *p += d;
+        }
+    }
+    assert_eq!(euler.polygon().vertices(), positions.as_slice());
+
+    let mut midpoint = start.clone();
+    midpoint.set_integrator(Box::new(Midpoint::default()));
+    assert_eq!(midpoint.integrator().name(), "Midpoint");
+    for _ in 0..10 {
+        midpoint.step(params);
+    }
+    let gap = euler.polygon().hausdorff_distance(midpoint.polygon());
+    // A different trajectory, but closer to Euler's than either is to the start.
+    let travel = euler.polygon().hausdorff_distance(start.polygon());
+    assert!(gap > 0.0 && gap < 0.5 * travel, "{gap} apart after moving {travel}");
+}
+
+#[test]
+fn skipping_rng_values_only_changes_the_jitter() {
+    let run = |skip, params| {
+        let mut sim = Simulation::benchmark(64);
+        sim.skip_rng(skip);
+        for _ in 0..10 {
+            sim.step(params);
+        }
+        sim
+    };
+    let deterministic = quiet().build();
+    assert_eq!(run(0, deterministic).polygon(), run(5, deterministic).polygon());
+
+    let jittery = SimParams::default();
+    assert!(jittery.jitter_enabled);
+    assert_eq!(run(5, jittery).polygon(), run(5, jittery).polygon());
+    assert_ne!(run(0, jittery).polygon(), run(5, jittery).polygon());
+}
+
+#[test]
+fn area_floor_rescales_a_collapsing_polygon() {
+    // Springs pulling toward tiny edges shrink the outline every step.
+    let collapsing = quiet().repulsion_enabled(false).target_edge_length(0.01).build();
+    let run = |params: SimParams| {
+        let mut sim = Simulation::with_ngon(1, 1.0, 32);
+        let mut areas = Vec::new();
+        for _ in 0..50 {
+            sim.step(params);
+            areas.push(sim.polygon().area());
+        }
+        (areas, sim)
+    };
+    let floor = 2.8;
+    let (free, _) = run(collapsing);
+    assert!(free[49] < 0.8 * floor, "only shrank to {}", free[49]);
+
+    let (held, sim) = run(SimParams {
+        min_area: Some(floor),
+        ..collapsing
+    });
+    assert!(held.iter().all(|&a| a >= floor * (1.0 - 1e-5)), "{held:?}");
+    assert!((held[49] - floor).abs() < 1e-4 * floor);
+    // Scaling is about the centroid, so the shape stays put.
+    let center = sim.polygon().centroid().unwrap();
+    assert!(center.length() < 1e-5, "drifted to {center}");
+}
+
+#[test]
+fn neighboring_polygons_repel_instead_of_crossing() {
+    // Two growing circles a little closer than the repulsion radius.
+    let circle = |x: Real| Polygon::regular_ngon_at(Vec2::new(x, 0.0), 0.5, 32);
+    let mut sim = Simulation::with_polygons(1, vec![circle(-0.55), circle(0.55)]);
+    let params = SimParams {
+        growth_enabled: true,
+        growth_rate: 0.002,
+        split_enabled: true,
+        split_length: 0.12,
+        target_edge_length: 0.1,
+        ..SimParams::default()
+    };
+    for _ in 0..300 {
+        sim.step(params);
+    }
+    let [left, right] = sim.polygons() else {
+        panic!("expected two polygons");
+    };
+    assert!(left.len() > 64 && right.len() > 64, "the circles grew");
+    assert_eq!(left.crossings(right), []);
+    assert!(!left.vertices().iter().any(|&v| right.contains_point(v)));
+    assert!(!right.vertices().iter().any(|&v| left.contains_point(v)));
+}
@@ -0,0 +1,189 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::{average_edge_length, SimParamsBuilder, Simulation, VERTEX_COUNT_LOG_CAPACITY};
+use dg4::split::{EdgeContext, SplitPredicate};
+
+// Splitting with every force off, so only the split pass changes the polygon.
+fn split_only(split_length: Real) -> SimParamsBuilder {
+    SimParamsBuilder::new()
+        .edge_regularization_enabled(false)
+        .repulsion_enabled(false)
+        .jitter_enabled(false)
+        .split_enabled(true)
+        .split_length(split_length)
+}
+
+#[test]
+fn retargeting_follows_the_split_edges() {
+    let mut sim = Simulation::with_ngon(1, 1.0, 8);
+    let params = split_only(0.5).auto_retarget_on_split(true).build();
+    let report = sim.step(params);
+    assert!(report.vertices_added > 0);
+    let target = sim.retargeted_edge_length().expect("the step split edges");
+    assert!((target - average_edge_length(sim.polygon())).abs() < 1e-9);
+
+    let mut plain = Simulation::with_ngon(1, 1.0, 8);
+    plain.step(split_only(0.5).build());
+    assert_eq!(plain.retargeted_edge_length(), None);
+}
+
+#[test]
+fn step_reports_the_vertices_a_split_adds() {
+    let mut sim = Simulation::with_ngon(1, 1.0, 8);
+    let edge = sim.polygon().perimeter() / 8.0;
+    // Each edge is a little over two split lengths, so it splits into three.
+    let report = sim.step(split_only(edge / 2.5).build());
+    assert_eq!(report.vertices_added, 16);
+    assert_eq!(report.vertices_removed, 0);
+    assert_eq!(sim.polygon().len(), 24);
+
+    let mut sim = Simulation::with_ngon(1, 1.0, 8);
+    let report = sim.step(split_only(1.0).split_enabled(false).build());
+    assert_eq!(report.vertices_added, 0);
+    assert_eq!(report.vertices_removed, 0);
+    assert_eq!(report.max_displacement, 0.0);
+}
+
+#[test]
+fn vertex_ids_are_unique_and_survive_steps() {
+    let mut sim = Simulation::with_ngon(1, 1.0, 8);
+    let start = sim.vertex_ids().to_vec();
+    assert_eq!(start, (0..8).collect::<Vec<u64>>());
+    sim.step(split_only(10.0).build());
+    assert_eq!(sim.vertex_ids(), start);
+
+    sim.step(split_only(0.3).build());
+    let ids = sim.vertex_ids();
+    assert_eq!(ids.len(), sim.polygon().len());
+    let mut unique = ids.to_vec();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), ids.len());
+    // The original vertices keep their ids and their order around the loop.
+    let kept: Vec<u64> = ids.iter().copied().filter(|id| start.contains(id)).collect();
+    assert_eq!(kept, start);
+}
+
+#[test]
+fn curvature_split_leaves_straight_edges_alone() {
+    // Unit edges along a straight bottom; the right side is a unit edge between corners.
+    let strip = Polygon::from_svg_path("M -2 0 L -1 0 L 0 0 L 1 0 L 2 0 L 2 1 L -2 1 Z").unwrap();
+    let split = |min_curvature| {
+        let mut sim = Simulation::new(1);
+        sim.set_polygon(strip.clone());
+        sim.step(split_only(0.75).split_min_curvature(min_curvature).build());
+        sim.polygon().vertices().to_vec()
+    };
+    let straight_midpoint = Vec2::new(-0.5, 0.0);
+    let corner_midpoint = Vec2::new(2.0, 0.5);
+
+    let by_length = split(None);
+    assert!(by_length.contains(&straight_midpoint) && by_length.contains(&corner_midpoint));
+    let by_curvature = split(Some(0.5));
+    assert!(!by_curvature.contains(&straight_midpoint));
+    assert!(by_curvature.contains(&corner_midpoint));
+    assert!(by_curvature.len() < by_length.len());
+}
+
+#[test]
+fn vertex_count_log_rises_with_splits() {
+    let params = split_only(0.1).growth_enabled(true).growth_rate(0.01).build();
+    let mut sim = Simulation::with_ngon(1, 0.5, 8);
+    for _ in 0..30 {
+        sim.step(params);
+    }
+    let log: Vec<(u64, usize)> = sim.vertex_count_log().iter().copied().collect();
+    assert_eq!(log.len(), 31);
+    assert_eq!(log[0], (0, 8));
+    assert_eq!(*log.last().unwrap(), (30, sim.polygon().len()));
+    for pair in log.windows(2) {
+        assert_eq!(pair[1].0, pair[0].0 + 1);
+        assert!(pair[1].1 >= pair[0].1, "count fell in {pair:?}");
+    }
+    assert!(log[30].1 > log[0].1);
+}
+
+#[test]
+fn vertex_count_log_keeps_the_latest_entries() {
+    let mut sim = Simulation::with_ngon(1, 0.5, 8);
+    let steps = VERTEX_COUNT_LOG_CAPACITY as u64 + 10;
+    for _ in 0..steps {
+        sim.step(split_only(1.0).split_enabled(false).build());
+    }
+    let log = sim.vertex_count_log();
+    assert_eq!(log.len(), VERTEX_COUNT_LOG_CAPACITY);
+    assert_eq!(log.back().unwrap().0, steps);
+    assert_eq!(log.front().unwrap().0, steps + 1 - VERTEX_COUNT_LOG_CAPACITY as u64);
+}
+
+#[test]
+fn equal_short_edges_merge_in_index_order() {
+    // Four bottom edges of exactly 1/8 in a row, all below the merge length.
+    let strip = "M 0 0 L 0.125 0 L 0.25 0 L 0.375 0 L 0.5 0 L 0.5 1 L 0 1 Z";
+    let params = split_only(1.0).split_enabled(false).merge_length(Some(0.2)).build();
+    let merge = || {
+        let mut sim = Simulation::new(1);
+        sim.set_polygon(Polygon::from_svg_path(strip).unwrap());
+        let report = sim.step(params);
+        (report.vertices_removed, sim)
+    };
+    let (removed, sim) = merge();
+    // Edge 0 goes first, which rules out edge 1; edge 2 goes next, which rules out edge 3.
+    assert_eq!(removed, 2);
+    assert_eq!(sim.vertex_ids(), [0, 2, 4, 5, 6]);
+    let expected = Polygon::from_svg_path("M 0.0625 0 L 0.3125 0 L 0.5 0 L 0.5 1 L 0 1 Z");
+    assert_eq!(sim.polygon(), &expected.unwrap());
+    for _ in 0..5 {
+        assert_eq!(merge().1.polygon(), sim.polygon());
+    }
+}
+
+#[test]
+fn grows_to_the_target_vertex_count() {
+    let params = split_only(0.1).growth_enabled(true).growth_rate(0.01).build();
+    let mut sim = Simulation::with_ngon(1, 0.5, 32);
+    let report = sim.grow_to_vertex_count(params, 200, 1000);
+    assert!(report.target_reached);
+    assert!(sim.polygon().len() >= 200);
+    assert_eq!(report.vertices_added - report.vertices_removed, sim.polygon().len() - 32);
+    let generation = sim.generation();
+    assert!(generation < 1000);
+
+    // Already there: no steps. Out of budget: stops short and says so.
+    assert!(sim.grow_to_vertex_count(params, 100, 1000).target_reached);
+    assert_eq!(sim.generation(), generation);
+    let report = sim.grow_to_vertex_count(params, 10_000, 3);
+    assert!(!report.target_reached);
+    assert_eq!(sim.generation(), generation + 3);
+}
+
+// Splits only the edges leaving an even-indexed vertex, whatever their length.
+#[derive(Debug, Clone, Copy)]
+struct EvenStart;
+
+impl SplitPredicate for EvenStart {
+    fn should_split(&self, edge: EdgeContext) -> bool {
+        edge.start.is_multiple_of(2)
+    }
+
+    fn box_clone(&self) -> Box<dyn SplitPredicate> {
+        Box::new(*self)
+    }
+}
+
+#[test]
+fn custom_predicate_picks_the_edges_to_split() {
+    // Unit edges, so a split length of 1 halves every chosen edge.
+    let params = split_only(1.0).build();
+    let mut sim = Simulation::with_ngon(1, 1.0, 6);
+    sim.set_split_predicate(Box::new(EvenStart));
+    assert_eq!(sim.step(params).vertices_added, 3);
+    // New vertices 6, 7 and 8 land after the even-indexed originals.
+    assert_eq!(sim.vertex_ids(), [0, 6, 1, 2, 7, 3, 4, 8, 5]);
+
+    // Cloning keeps the predicate; clearing it returns to length splitting, under which
+    // only the three unit edges left whole are too long.
+    let mut copy = sim.clone();
+    assert!(copy.split_predicate().is_some());
+    copy.clear_split_predicate();
+    assert_eq!(copy.step(split_only(0.75).build()).vertices_added, 3);
+}
@@ -0,0 +1,96 @@
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::sim::Simulation;
+
+#[test]
+fn offset_pentagon_is_centered_where_asked() {
+    let center = Vec2::new(2.0, 3.0);
+    let pentagon = Polygon::regular_ngon_at(center, 0.5, 5);
+    assert_eq!(pentagon.len(), 5);
+    assert!(pentagon.centroid().unwrap().distance(center) < 1e-5);
+    assert!(pentagon.area_centroid().unwrap().distance(center) < 1e-5);
+
+    let mut sim = Simulation::new(1);
+    sim.rebuild_polygon_at(center, 0.5, 5);
+    assert_eq!(sim.polygon(), &pentagon);
+}
+
+#[test]
+fn perturbation_stays_within_its_magnitude() {
+    let start = Polygon::regular_ngon(1.0, 32);
+    let mut sim = Simulation::with_ngon(5, 1.0, 32);
+    sim.perturb(0.0);
+    assert_eq!(sim.polygon(), &start);
+
+    let magnitude = 0.02;
+    sim.perturb(magnitude);
+    for (a, b) in start.vertices().iter().zip(sim.polygon().vertices()) {
+        let moved = a.distance(*b);
+        assert!(moved > 0.0 && moved <= magnitude * (1.0 + 1e-5), "moved {moved}");
+    }
+}
+
+#[test]
+fn star_and_rounded_rect_vertex_counts() {
+    assert_eq!(Polygon::star(5, 0.4, 1.0).len(), 10);
+    assert!(Polygon::star(1, 0.4, 1.0).is_empty());
+    // Four arcs of `segments + 1` vertices, and the plain rectangle at radius zero.
+    assert_eq!(Polygon::rounded_rect(2.0, 1.0, 0.25, 6).len(), 28);
+    assert_eq!(Polygon::rounded_rect(2.0, 1.0, 0.0, 6).len(), 4);
+    assert!(Polygon::rounded_rect(0.0, 1.0, 0.25, 6).is_empty());
+}
+
+#[test]
+fn rounded_rect_has_no_sharp_corners() {
+    let segments = 6;
+    let rect = Polygon::rounded_rect(2.0, 1.0, 0.25, segments);
+    let v = rect.vertices();
+    let n = v.len();
+    let sharpest = (0..n)
+        .map(|i| {
+            let a = v[(i + 1) % n] - v[i];
+            let b = v[(i + 2) % n] - v[(i + 1) % n];
+            a.perp_dot(b).atan2(a.dot(b)).abs()
+        })
+        .fold(0.0, Real::max);
+    let step = std::f64::consts::FRAC_PI_2 as Real / segments as Real;
+    assert!(sharpest <= step * 1.001, "turns by {sharpest} rad");
+}
+
+#[test]
+fn symmetric_ngon_is_exactly_mirrored_across_the_y_axis() {
+    for sides in [3, 4, 7, 12, 33] {
+        let ngon = Polygon::mirror_symmetric_ngon(1.0, sides, Vec2::Y);
+        let v = ngon.vertices();
+        assert_eq!(v.len(), sides);
+        // Vertex 0 sits on the axis at the top, and vertex `i` mirrors vertex `n - i`.
+        assert_eq!(v[0].x, 0.0);
+        assert!(v[0].y > 0.0);
+        for i in 0..sides {
+            let mirror = v[(sides - i) % sides];
+            assert_eq!((mirror.x, mirror.y), (-v[i].x, v[i].y), "{sides} sides, vertex {i}");
+        }
+        assert!(ngon.signed_area() > 0.0);
+    }
+}
+
+#[test]
+fn symmetric_ngon_mirrors_across_any_axis() {
+    let axis = Vec2::new(1.0, 2.0);
+    let up = axis.normalize();
+    let reflect = |v: Vec2| 2.0 * v.dot(up) * up - v;
+    for sides in [3, 4, 7, 12] {
+        let ngon = Polygon::mirror_symmetric_ngon(1.0, sides, axis);
+        let v = ngon.vertices();
+        assert_eq!(v.len(), sides);
+        assert!(v[0].distance(up) < 1e-6, "{sides} sides start at {}", v[0]);
+        for i in 0..sides {
+            let mirror = v[(sides - i) % sides];
+            assert!(mirror.distance(reflect(v[i])) < 1e-6, "{sides} sides, vertex {i}");
+        }
+        assert!(ngon.signed_area() > 0.0);
+        // Same shape as the y-axis one, turned.
+        let upright = Polygon::mirror_symmetric_ngon(1.0, sides, Vec2::Y);
+        assert!((ngon.area() - upright.area()).abs() < 1e-6);
+    }
+    assert!(Polygon::mirror_symmetric_ngon(1.0, 5, Vec2::ZERO).is_empty());
+}
@@ -0,0 +1,198 @@
+use dg4::colormap::Colormap;
+use dg4::error::GrowthError;
+use dg4::geometry::{Polygon, Real, Vec2};
+use dg4::history::History;
+use dg4::sim::{SimParams, Simulation};
+use dg4::svg::{
+    displacement_svg_document, extract_path_data, history_trail_svg, polygons_from_svg,
+    svg_document, svg_document_colored, ExportReduction,
+};
+use std::collections::HashSet;
+
+#[test]
+fn exported_path_parses_back() {
+    let mut sim = Simulation::benchmark(120);
+    for _ in 0..5 {
+        sim.step(SimParams::default());
+    }
+    let polygon = sim.polygon();
+    let parsed = Polygon::from_svg_path(&polygon.to_svg_path()).unwrap();
+    // Shortest round-trip formatting makes the text exact.
+    assert_eq!(&parsed, polygon);
+
+    let document = polygon.to_svg_document(1.0);
+    let (reloaded, errors) = polygons_from_svg(&document);
+    assert!(errors.is_empty());
+    assert_eq!(reloaded.len(), 1);
+    assert_eq!(&reloaded[0], polygon);
+}
+
+#[test]
+fn only_closed_outlines_end_in_close_path() {
+    let square = Polygon::regular_ngon(1.0, 4);
+    assert!(square.to_svg_path().ends_with('Z'));
+    let open = square.subpath(0, 2).to_svg_path();
+    assert!(!open.contains('Z'), "{open}");
+    assert_eq!(open.matches(" L ").count(), 2);
+}
+
+#[test]
+fn translated_copy_displaces_every_vertex_by_the_translation() {
+    let from = Polygon::regular_ngon(1.0, 12);
+    let shift = Vec2::new(0.25, -0.5);
+    let mut to = from.clone();
+    to.map_vertices(|v| v + shift);
+    let displacements = from.displacements_to(&to);
+    assert_eq!(displacements.len(), 12);
+    for ((origin, offset), v) in displacements.iter().zip(from.vertices()) {
+        assert_eq!(origin, v);
+        assert!(offset.distance(shift) < 1e-6, "{offset} != {shift}");
+    }
+    let document = displacement_svg_document(&[from], &[to], 0.01);
+    assert_eq!(document.matches("<line ").count(), 12);
+}
+
+#[test]
+fn colored_export_has_one_path_per_edge() {
+    let square = Polygon::regular_ngon(1.0, 4);
+    let paths = square
+        .to_svg_path_colored(&[0.0, 1.0, 2.0, 3.0], Colormap::Viridis)
+        .unwrap();
+    assert_eq!(paths.matches("<path ").count(), 4);
+    let strokes: HashSet<_> = paths
+        .split("stroke=\"")
+        .skip(1)
+        .map(|rest| &rest[..7])
+        .collect();
+    assert_eq!(strokes.len(), 4, "{paths}");
+
+    let mismatched = square.to_svg_path_colored(&[0.0; 3], Colormap::Viridis);
+    assert!(matches!(mismatched, Err(GrowthError::InvalidParameter(_))));
+
+    // An open chain has no closing edge to color.
+    let chain = square.subpath(0, 3);
+    let lengths: Vec<Real> = chain.edges().map(|(a, b)| a.distance(b)).collect();
+    let paths = chain.to_svg_path_colored(&lengths, Colormap::Viridis).unwrap();
+    assert_eq!(paths.matches("<path ").count(), 3);
+}
+
+#[test]
+fn simplified_export_has_fewer_line_commands() {
+    let mut sim = Simulation::benchmark(200);
+    sim.perturb(0.002);
+    let raw = sim.polygon();
+    let lines = |polygon: &Polygon| polygon.to_svg_document(1.0).matches(" L ").count();
+    let simplified = ExportReduction::Simplify.apply(raw, 0.01, 0);
+    assert!(lines(&simplified) < lines(raw) / 2, "{} vs {}", lines(&simplified), lines(raw));
+    assert_eq!(&ExportReduction::None.apply(raw, 0.01, 0), raw);
+    assert_eq!(ExportReduction::Decimate.apply(raw, 0.0, 32).len(), 32);
+}
+
+fn parse_error(d: &str) -> String {
+    match Polygon::from_svg_path(d) {
+        Err(GrowthError::Parse(message)) => message,
+        other => panic!("expected a parse error for {d:?}, got {other:?}"),
+    }
+}
+
+#[test]
+fn invalid_path_data_is_a_parse_error() {
+    assert_eq!(parse_error("M 0 0 L 1 # Z"), "unexpected character '#' in path data");
+    assert_eq!(parse_error("M 0 0 C 1 1 2 2 3 3"), "unsupported path command 'C'");
+    assert_eq!(parse_error("M 0 0 L 1"), "missing coordinate for 'L' command");
+    assert_eq!(parse_error("M 0 0 L 1 0"), "path has 2 vertices; need at least 3");
+
+    let err = Polygon::from_svg_path("1 2 3").unwrap_err();
+    assert_eq!(err.to_string(), "path data must start with a command");
+    assert!(std::error::Error::source(&err).is_none());
+
+    let document = r#"<svg><path d="M 0 0 L 1 0 L 0 1 Z"/><path d="M 0 0 Q 1 1"/></svg>"#;
+    let (polygons, errors) = polygons_from_svg(document);
+    assert_eq!(polygons.len(), 1);
+    assert!(matches!(errors.as_slice(), [(1, GrowthError::Parse(_))]));
+}
+
+#[test]
+fn every_path_loads_into_its_own_polygon() {
+    let document = r#"<svg>
+        <path d="M 0 0 L 1 0 L 0 1 Z"/>
+        <path d="M 0 0 Q 1 1"/>
+        <path d="M 10 0 L 11 0 L 11 1 L 10 1 Z"/>
+    </svg>"#;
+    let (polygons, errors) = polygons_from_svg(document);
+    assert!(matches!(errors.as_slice(), [(1, GrowthError::Parse(_))]));
+    let mut sim = Simulation::with_polygons(1, polygons);
+    let counts: Vec<usize> = sim.polygons().iter().map(Polygon::len).collect();
+    assert_eq!(counts, [3, 4]);
+    assert_eq!(sim.vertex_count(), 7);
+    assert_eq!(sim.vertex_ids(), [0, 1, 2, 3, 4, 5, 6]);
+
+    // Each outline refines and moves on its own, and the tracking follows all of them.
+    let params = SimParams {
+        split_enabled: true,
+        ..SimParams::default()
+    };
+    let report = sim.grow_to_vertex_count(params, 25, 20);
+    assert!(report.target_reached);
+    assert_eq!(sim.polygons().len(), 2);
+    assert!(sim.polygons().iter().all(|p| p.len() > 4));
+    assert_eq!(sim.vertex_ids().len(), sim.vertex_count());
+    assert_eq!(sim.masses().len(), sim.vertex_count());
+    let (_, left_max) = sim.polygons()[0].bounds().unwrap();
+    let (right_min, _) = sim.polygons()[1].bounds().unwrap();
+    assert!(left_max.x < 5.0 && right_min.x > 5.0);
+}
+
+#[test]
+fn history_trail_fades_in_toward_the_latest() {
+    let mut history = History::new(8, 1);
+    let mut sim = Simulation::benchmark(32);
+    for generation in 0..3 {
+        assert!(history.record(generation, sim.polygons()));
+        sim.step(SimParams {
+            growth_enabled: true,
+            growth_rate: 0.05,
+            ..SimParams::default()
+        });
+    }
+    let svg = history_trail_svg(&history);
+    let opacities: Vec<Real> = svg
+        .split("stroke-opacity=\"")
+        .skip(1)
+        .map(|rest| rest[..rest.find('"').unwrap()].parse().unwrap())
+        .collect();
+    assert_eq!(opacities.len(), 3);
+    assert_eq!(svg.matches("<path ").count(), 3);
+    assert!(opacities.windows(2).all(|pair| pair[0] < pair[1]), "{opacities:?}");
+    assert_eq!(opacities[2], 1.0);
+    // Oldest first, each with its own outline.
+    for (snapshot, d) in history.iter().zip(extract_path_data(&svg)) {
+        assert_eq!(d, snapshot.polygons[0].to_svg_path());
+    }
+}
+
+#[test]
+fn every_polygon_is_recorded_and_exported() {
+    let left = Polygon::regular_ngon_at(Vec2::new(-2.0, 0.0), 1.0, 6);
+    let right = Polygon::regular_ngon_at(Vec2::new(2.0, 0.0), 1.0, 8);
+    let sim = Simulation::with_polygons(1, vec![left, right]);
+
+    let document = svg_document(sim.polygons(), 0.01);
+    let (reloaded, errors) = polygons_from_svg(&document);
+    assert!(errors.is_empty());
+    assert_eq!(reloaded, sim.polygons());
+
+    let values: Vec<Real> = (0..14).map(|i| i as Real).collect();
+    let colored = svg_document_colored(sim.polygons(), &values, Colormap::Viridis, 0.01).unwrap();
+    assert_eq!(colored.matches("<path ").count(), 14);
+    assert!(svg_document_colored(sim.polygons(), &values[..6], Colormap::Viridis, 0.01).is_err());
+
+    let mut history = History::new(4, 1);
+    assert!(history.record(sim.generation(), sim.polygons()));
+    assert_eq!(history.iter().next().unwrap().polygons, sim.polygons());
+    assert_eq!(history_trail_svg(&history).matches("<path ").count(), 2);
+    let mut shifted = sim.polygons().to_vec();
+    shifted.iter_mut().for_each(|p| p.map_vertices(|v| v + Vec2::X));
+    let arrows = displacement_svg_document(sim.polygons(), &shifted, 0.01);
+    assert_eq!(arrows.matches("<line ").count(), 14);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "text")]
+
+use dg4::error::GrowthError;
+use dg4::geometry::Polygon;
+use dg4::sim::{SimParams, Simulation};
+use dg4::text::glyph_polygons;
+use epaint_default_fonts::HACK_REGULAR;
+
+#[test]
+fn capital_i_is_one_closed_straight_outline() {
+    let outline = glyph_polygons(HACK_REGULAR, 'I', 1.0, 0.001).unwrap();
+    assert_eq!(outline.len(), 1);
+    let i = &outline[0];
+    assert!(i.is_closed());
+    // A bar with serifs top and bottom is all straight edges: a few corners each.
+    assert!((4..=16).contains(&i.len()), "{} vertices", i.len());
+    let (min, max) = i.bounds().unwrap();
+    assert!((min + max).length() < 1e-6, "centered on the origin");
+    let height = max.y - min.y;
+    assert!(height > 0.5 && height < 1.0, "cap height {height} of a unit em");
+    assert_eq!(glyph_polygons(HACK_REGULAR, 'I', 1.0, 0.001).unwrap(), outline);
+}
+
+#[test]
+fn curved_glyphs_flatten_finer_at_lower_tolerance() {
+    let coarse = glyph_polygons(HACK_REGULAR, 'O', 1.0, 0.01).unwrap();
+    let fine = glyph_polygons(HACK_REGULAR, 'O', 1.0, 0.0001).unwrap();
+    // The outer contour and the counter.
+    assert_eq!((coarse.len(), fine.len()), (2, 2));
+    assert!(fine[0].len() > 2 * coarse[0].len());
+    assert!(coarse[0].signed_area() * coarse[1].signed_area() < 0.0, "opposite windings");
+
+    assert!(matches!(glyph_polygons(b"not a font", 'O', 1.0, 0.01), Err(GrowthError::Parse(_))));
+    assert!(glyph_polygons(HACK_REGULAR, 'O', 0.0, 0.01).is_err());
+}
+
+#[test]
+fn glyph_contours_grow_without_crossing() {
+    let contours = glyph_polygons(HACK_REGULAR, 'O', 1.0, 0.01).unwrap();
+    let counts: Vec<usize> = contours.iter().map(Polygon::len).collect();
+    let mut sim = Simulation::with_polygons(1, contours);
+    let params = SimParams {
+        growth_enabled: true,
+        split_enabled: true,
+        split_length: 0.05,
+        target_edge_length: 0.03,
+        repulsion_radius: 0.05,
+        ..SimParams::default()
+    };
+    for _ in 0..200 {
+        sim.step(params);
+    }
+    let [outer, counter] = sim.polygons() else {
+        panic!("expected the outer contour and the counter");
+    };
+    assert!(outer.len() > counts[0] && counter.len() > counts[1], "both contours grew");
+    assert_eq!(outer.crossings(counter), []);
+    assert!(counter.vertices().iter().all(|&v| outer.contains_point(v)));
+}
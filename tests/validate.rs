@@ -0,0 +1,37 @@
+use dg4::geometry::Polygon;
+use dg4::validate::PolygonWarning;
+
+fn path(d: &str) -> Polygon {
+    Polygon::from_svg_path(d).unwrap()
+}
+
+#[test]
+fn duplicate_vertex_is_reported_and_repaired() {
+    let mut square = path("M 0 0 L 1 0 L 1 0 L 1 1 L 0 1 Z");
+    // The edges either side of the zero-length one also touch at the repeated point.
+    assert_eq!(
+        square.validate(),
+        [
+            PolygonWarning::DegenerateEdge { index: 1 },
+            PolygonWarning::SelfIntersection { first: 0, second: 2 },
+        ]
+    );
+    assert_eq!(square.repair(), [0, 1, 3, 4]);
+    assert!(square.validate().is_empty());
+    assert_eq!(square, path("M 0 0 L 1 0 L 1 1 L 0 1 Z"));
+}
+
+#[test]
+fn repair_reorients_clockwise_loops_but_keeps_crossings() {
+    let mut square = path("M 0 0 L 0 1 L 1 1 L 1 0 Z");
+    assert_eq!(square.validate(), [PolygonWarning::Clockwise]);
+    assert_eq!(square.repair(), [0, 3, 2, 1]);
+    assert!(square.validate().is_empty());
+    assert!(square.signed_area() > 0.0);
+
+    let mut bowtie = path("M 0 0 L 1 1 L 1 0 L 0 1 Z");
+    let crossing = PolygonWarning::SelfIntersection { first: 0, second: 2 };
+    assert!(bowtie.validate().contains(&crossing));
+    bowtie.repair();
+    assert!(bowtie.validate().contains(&crossing));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "gui")]
+
+use dg4::geometry::{Real, Vec2};
+use dg4::view::ViewTransform;
+use eframe::egui::Pos2;
+
+#[test]
+fn to_world_inverts_to_screen() {
+    let points = [
+        Vec2::ZERO,
+        Vec2::new(1.0, -2.0),
+        Vec2::new(-0.37, 0.81),
+        Vec2::new(12.5, 3.25),
+    ];
+    for scale in [0.5, 40.0, 120.0, 2500.0] {
+        let view = ViewTransform {
+            screen_center: Pos2::new(400.0, 300.0),
+            center: Vec2::new(0.25, -0.5),
+            scale,
+            pan: Vec2::new(-1.5, 0.75),
+        };
+        for p in points {
+            let back = view.to_world(view.to_screen(p));
+            // Screen positions are f32, so the error grows with the distance in pixels.
+            let tolerance: Real = 1e-3 / scale + 1e-5 * p.length();
+            assert!(back.distance(p) < tolerance, "{p} came back as {back} at scale {scale}");
+        }
+    }
+}
+
+#[test]
+fn screen_y_points_down() {
+    let view = ViewTransform {
+        screen_center: Pos2::new(100.0, 100.0),
+        center: Vec2::ZERO,
+        scale: 10.0,
+        pan: Vec2::ZERO,
+    };
+    assert_eq!(view.to_screen(Vec2::new(1.0, 1.0)), Pos2::new(110.0, 90.0));
+}